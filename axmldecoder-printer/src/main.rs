@@ -1,112 +1,223 @@
-use std::{fs, env};
-use anyhow::Result;
-use axmldecoder::{Cdata, Element, Node};
-
-fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() <= 1 {
-        for path in fs::read_dir("../examples").unwrap() {
-            let f = std::fs::read(path?.path())?;
-            let xml = axmldecoder::parse(&f)?;
-    
-            let root = xml.get_root().as_ref().unwrap();
-            let mut s = String::new();
-            s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
-            format_xml(&root, 0_usize, &mut s);
-    
-            let s = s.trim().to_string();
-            println!("{}", s);
-        }
-    } else {
-        let fname = args.get(1).unwrap();
-
-        let f = std::fs::read(fname)?;
-        let xml = axmldecoder::parse(&f)?;
+use anyhow::{anyhow, bail, Result};
+use axmldecoder::XmlDocument;
+use std::io::{Read, Write};
+use std::process::ExitCode;
+use std::{env, fs, io};
+
+const USAGE: &str = "Usage:
+    axmldecoder-printer print <file>
+    axmldecoder-printer json <file>
+    axmldecoder-printer get <file> <path>
+
+<file> is a binary XML file, an APK or - for stdin. <path> names elements
+from the root down and an attribute, e.g.
+manifest/application/activity@android:name";
+
+// What the command line asks for.
+#[derive(Debug, PartialEq)]
+enum Command<'a> {
+    // Print every document in the examples directory.
+    Examples,
+    Print(&'a str),
+    Json(&'a str),
+    Get(&'a str, &'a str),
+}
 
-        let root = xml.get_root().as_ref().unwrap();
-        let mut s = String::new();
-        s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
-        format_xml(&root, 0_usize, &mut s);
+fn main() -> Result<ExitCode> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
 
-        let s = s.trim().to_string();
-        println!("{}", s);
-    }
+    let code = run(parse_args(&args)?, io::stdin(), &mut io::stdout())?;
+    Ok(ExitCode::from(code))
+}
 
-    Ok(())
+fn parse_args<'a>(args: &[&'a str]) -> Result<Command<'a>> {
+    Ok(match *args {
+        [] => Command::Examples,
+        ["print", fname] => Command::Print(fname),
+        // A file on its own is printed, as before there were subcommands.
+        [fname] if !matches!(fname, "print" | "json" | "get") => Command::Print(fname),
+        ["json", fname] => Command::Json(fname),
+        ["get", fname, path] => Command::Get(fname, path),
+        _ => bail!("{}", USAGE),
+    })
 }
 
-fn format_xml(e: &Node, level: usize, output: &mut String) {
-    match e {
-        Node::Element(e) => {
-            output.push_str(&format!(
-                "{:indent$}{}\n",
-                "",
-                &format_start_element(&e),
-                indent = level * 2
-            ));
-
-            for child in e.get_children() {
-                format_xml(&child, level + 1, output)
-            }
+// Runs `command`, reading a document named "-" from `stdin`. Returns the
+// exit code of the process.
+fn run(command: Command, stdin: impl Read, out: &mut impl Write) -> Result<u8> {
+    match command {
+        Command::Examples => {
+            for path in fs::read_dir("../examples")? {
+                let xml = axmldecoder::parse_file(path?.path())?;
 
-            if !e.get_children().is_empty() {
-                output.push_str(&format!(
-                    "{:indent$}{}\n",
-                    "",
-                    &format_end_element(&e),
-                    indent = level * 2
-                ));
+                write!(out, "{}", xml)?;
             }
         }
-        Node::Cdata(e) => {
-            output.push_str(&format!(
-                "{:indent$}{}\n",
-                "",
-                &format_cdata(&e, level),
-                indent = level * 2
-            ));
+        Command::Print(fname) => write!(out, "{}", load(fname, stdin)?)?,
+        Command::Json(fname) => writeln!(out, "{}", load(fname, stdin)?.to_json_string())?,
+        Command::Get(fname, path) => {
+            let values = get(&load(fname, stdin)?, path)?;
+            for value in &values {
+                writeln!(out, "{}", value)?;
+            }
+            // Like grep, nothing matching is not an error but still fails.
+            if values.is_empty() {
+                return Ok(1);
+            }
         }
     }
+
+    Ok(0)
 }
 
-fn format_cdata(e: &Cdata, level: usize) -> String {
-    let indent = format!("{:indent$}", "", indent = level * 2);
-    let mut s = String::new();
-    s.push_str("<![CDATA[");
-    s.push_str(&e.get_data().replace("\n", &format!("\n{}", &indent)));
-    s.push_str("]]>");
-    s
+// Reads a binary XML document, or the manifest of an APK, from a file or
+// from `stdin` if `fname` is "-".
+fn load(fname: &str, mut stdin: impl Read) -> Result<XmlDocument> {
+    let data = if fname == "-" {
+        let mut data = Vec::new();
+        stdin.read_to_end(&mut data)?;
+        data
+    } else {
+        fs::read(fname)?
+    };
+    decode(data)
 }
 
-fn format_start_element(e: &Element) -> String {
-    let mut s = String::new();
-    s.push('<');
-    s.push_str(e.get_tag());
-
-    for (key, val) in e.get_attributes().iter() {
-        s.push(' ');
-        s.push_str(key);
-        s.push('=');
-        s.push('"');
-        s.push_str(val);
-        s.push('"');
+// Decodes a binary XML document, or the manifest of `data` if it is an APK.
+fn decode(data: Vec<u8>) -> Result<XmlDocument> {
+    if data.starts_with(b"PK\x03\x04") {
+        return load_apk(data);
     }
+    Ok(axmldecoder::parse(&data)?)
+}
 
-    if e.get_children().is_empty() {
-        s.push('/');
-    }
+#[cfg(feature = "apk")]
+fn load_apk(data: Vec<u8>) -> Result<XmlDocument> {
+    Ok(axmldecoder::parse_apk_reader(io::Cursor::new(data))?)
+}
 
-    s.push('>');
+#[cfg(not(feature = "apk"))]
+fn load_apk(_: Vec<u8>) -> Result<XmlDocument> {
+    bail!("Reading APK files requires the apk feature")
+}
+
+// Splits a `get` path into the `/` separated tags from the root down and
+// the attribute named after the `@`. Empty tags are skipped, so a leading
+// `/` is allowed.
+fn parse_path(path: &str) -> Result<(Vec<&str>, &str)> {
+    let (tags, attribute) = path
+        .split_once('@')
+        .ok_or_else(|| anyhow!("Path {} does not name an attribute", path))?;
+    let tags: Vec<&str> = tags.split('/').filter(|tag| !tag.is_empty()).collect();
+    if tags.is_empty() {
+        bail!("Path {} does not name an element", path);
+    }
+    Ok((tags, attribute))
+}
 
-    s
+// Returns the values of the attribute named by `path` on every element that
+// matches its tags, in document order.
+fn get(doc: &XmlDocument, path: &str) -> Result<Vec<String>> {
+    let (tags, attribute) = parse_path(path)?;
+    Ok(doc
+        .iter_elements_by_path(&tags.join("/"))
+        .filter_map(|e| e.get_attribute(attribute))
+        .map(str::to_string)
+        .collect())
 }
 
-fn format_end_element(e: &Element) -> String {
-    let mut s = String::new();
-    s.push('<');
-    s.push('/');
-    s.push_str(e.get_tag());
-    s.push('>');
-    s
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &[u8] = include_bytes!("../../examples/AndroidManifest.xml");
+
+    fn output(command: Command, stdin: &[u8]) -> (u8, String) {
+        let mut out = Vec::new();
+        let code = run(command, stdin, &mut out).unwrap();
+        (code, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&[]).unwrap(), Command::Examples);
+        assert_eq!(parse_args(&["a.xml"]).unwrap(), Command::Print("a.xml"));
+        assert_eq!(
+            parse_args(&["print", "a.xml"]).unwrap(),
+            Command::Print("a.xml")
+        );
+        assert_eq!(parse_args(&["json", "-"]).unwrap(), Command::Json("-"));
+        assert_eq!(
+            parse_args(&["get", "a.xml", "manifest@package"]).unwrap(),
+            Command::Get("a.xml", "manifest@package")
+        );
+
+        assert!(parse_args(&["print"]).is_err());
+        assert!(parse_args(&["get", "a.xml"]).is_err());
+        assert!(parse_args(&["print", "a.xml", "b.xml"]).is_err());
+    }
+
+    #[test]
+    fn test_stdin() {
+        let expected = axmldecoder::parse(MANIFEST).unwrap().to_string();
+        assert_eq!(output(Command::Print("-"), MANIFEST), (0, expected));
+
+        let (code, json) = output(Command::Json("-"), MANIFEST);
+        assert_eq!(code, 0);
+        assert!(json.starts_with('{'));
+    }
+
+    #[test]
+    fn test_zip_input() {
+        let data = include_bytes!("../../tests/fixtures/apk/Example.apk").to_vec();
+        let result = decode(data);
+        if cfg!(feature = "apk") {
+            assert!(result
+                .unwrap()
+                .find_first_element_by_tag("manifest")
+                .is_some());
+        } else {
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("manifest/application@android:label").unwrap(),
+            (vec!["manifest", "application"], "android:label")
+        );
+        assert_eq!(
+            parse_path("/manifest@package").unwrap(),
+            (vec!["manifest"], "package")
+        );
+        assert!(parse_path("manifest/application").is_err());
+        assert!(parse_path("@package").is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let doc = axmldecoder::parse(MANIFEST).unwrap();
+        assert_eq!(
+            get(&doc, "manifest/application/activity@android:name").unwrap(),
+            ["TCActivity"]
+        );
+        assert!(get(&doc, "application/activity@android:name")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_exit_code() {
+        let path = "manifest/application/activity/intent-filter/action@android:name";
+        assert_eq!(
+            output(Command::Get("-", path), MANIFEST),
+            (0, "android.intent.action.MAIN\n".to_string())
+        );
+        assert_eq!(
+            output(Command::Get("-", "manifest@missing"), MANIFEST),
+            (1, String::new())
+        );
+    }
 }
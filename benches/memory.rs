@@ -0,0 +1,84 @@
+// Reports how much memory the parsed documents of the examples hold on to,
+// compared with the same documents if every element kept its own copy of
+// each string instead of sharing it.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use axmldecoder::{parse, XmlDocument};
+
+struct Counter;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+// Returns the bytes taken by the strings the elements of `doc` share, and
+// the bytes they would take if every use had its own allocation.
+fn string_bytes(doc: &XmlDocument) -> (usize, usize) {
+    // An `Arc<str>` allocation holds the two reference counts too.
+    let size = |s: &str| s.len() + 2 * std::mem::size_of::<usize>();
+
+    let mut seen = HashSet::new();
+    let (mut shared, mut copied) = (0, 0);
+    for e in doc.iter_elements() {
+        let strings = std::iter::once(e.get_tag())
+            .chain(e.get_attributes().flat_map(|(k, v)| [k, v]))
+            .chain(e.get_typed_attributes().map(|(k, _)| k));
+        for s in strings {
+            if seen.insert(s.as_ptr()) {
+                shared += size(s);
+            }
+            copied += size(s);
+        }
+    }
+    (shared, copied)
+}
+
+fn main() {
+    let dir = format!("{}/examples", env!("CARGO_MANIFEST_DIR"));
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+
+    let mut total = 0;
+    let mut baseline_total = 0;
+    for path in paths {
+        let data = std::fs::read(&path).unwrap();
+        let before = ALLOCATED.load(Ordering::Relaxed);
+        let doc = parse(&data).unwrap();
+        let retained = ALLOCATED.load(Ordering::Relaxed) - before;
+
+        let (shared, copied) = string_bytes(&doc);
+        let baseline = retained - shared + copied;
+        total += retained;
+        baseline_total += baseline;
+
+        println!(
+            "{:<48} {:>8} bytes {:>8} without interning {:>6} strings",
+            path.file_name().unwrap().to_string_lossy(),
+            retained,
+            baseline,
+            doc.get_distinct_string_count()
+        );
+    }
+    println!(
+        "{:<48} {:>8} bytes {:>8} without interning",
+        "total", total, baseline_total
+    );
+}
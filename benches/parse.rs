@@ -0,0 +1,41 @@
+use axmldecoder::{parse, Event, EventReader};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// Collects the package name and requested permissions, stopping at the
+// <application> element.
+fn permissions(data: &[u8]) -> (Option<String>, Vec<String>) {
+    let mut package = None;
+    let mut permissions = Vec::new();
+
+    for event in EventReader::new(data).unwrap() {
+        if let Event::StartElement {
+            tag, attributes, ..
+        } = event.unwrap()
+        {
+            match tag.as_str() {
+                "manifest" => package = attributes.get("package").map(|v| v.to_string()),
+                "uses-permission" => {
+                    if let Some(name) = attributes.get("android:name") {
+                        permissions.push(name.to_string());
+                    }
+                }
+                "application" => break,
+                _ => {}
+            }
+        }
+    }
+
+    (package, permissions)
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let data = include_bytes!("../examples/AndroidManifestWithComment.xml");
+
+    c.bench_function("parse", |b| b.iter(|| parse(black_box(data)).unwrap()));
+    c.bench_function("event_reader_permissions", |b| {
+        b.iter(|| permissions(black_box(data)))
+    });
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
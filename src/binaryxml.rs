@@ -1,21 +1,278 @@
-use crate::stringpool::StringPool;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::resource_value::ResourceValue;
+use crate::ParseError;
+use byteorder::ByteOrder;
+use byteorder::LittleEndian;
+use deku::error::NeedSize;
 use deku::prelude::*;
-use std::rc::Rc;
 
-#[derive(Debug, DekuRead)]
-pub(crate) struct BinaryXmlDocument {
-    #[allow(dead_code)] // `header` is used by `deku`
-    pub(crate) header: ChunkHeader,
-    pub(crate) string_pool: StringPool,
-    pub(crate) resource_map: ResourceMap,
-    #[deku(bytes_read = "header.size -
-            u32::try_from(header.header_size).unwrap() -
-            string_pool.header.chunk_header.size -
-            resource_map.header.size")]
-    pub(crate) elements: Vec<XmlNode>,
+// A chunk found by walking the chunk headers of a binary XML document. `data`
+// covers the whole chunk, including its header.
+pub(crate) struct RawChunk<'a> {
+    pub(crate) typ: u16,
+    pub(crate) header_size: u16,
+    pub(crate) data: &'a [u8],
+}
+
+impl<'a> RawChunk<'a> {
+    // Wraps a whole chunk whose header has been checked by `chunk_size`.
+    pub(crate) fn checked(data: &'a [u8]) -> Self {
+        Self {
+            typ: LittleEndian::read_u16(&data[0..2]),
+            header_size: LittleEndian::read_u16(&data[2..4]),
+            data,
+        }
+    }
+
+    pub(crate) fn is_xml_node(&self) -> bool {
+        (ResourceType::XmlStartNameSpace as u16..=ResourceType::XmlCdata as u16).contains(&self.typ)
+    }
+
+    // Decodes the chunk as an XML node. Start elements are first rewritten
+    // to the layout that aapt writes if needed, see `compact_start_element`.
+    pub(crate) fn read_node(&self) -> Result<XmlNode, ParseError> {
+        let data = if self.typ == ResourceType::XmlStartElement as u16 {
+            self.compact_start_element()?
+        } else {
+            Cow::Borrowed(self.data)
+        };
+        let (_, node) = XmlNode::from_bytes((&data, 0)).map_err(ParseError::DekuError)?;
+        Ok(node)
+    }
+
+    // The deku structs expect the attribute extension straight after a
+    // 16 byte node header, and 20 byte attributes straight after that. Like
+    // Android, this instead honours the header size and the attribute start
+    // and size in the extension, which some toolchains and packers pad. The
+    // attribute start is relative to the extension.
+    fn compact_start_element(&self) -> Result<Cow<'_, [u8]>, ParseError> {
+        let invalid = || {
+            ParseError::DekuError(DekuError::Parse(format!(
+                "Invalid attribute layout in start element of {} bytes",
+                self.data.len()
+            )))
+        };
+
+        let ext = usize::from(self.header_size);
+        let ext_data = self
+            .data
+            .get(ext..ext + ATTR_EXT_SIZE)
+            .ok_or_else(invalid)?;
+        let start = LittleEndian::read_u16(&ext_data[8..10]);
+        let size = LittleEndian::read_u16(&ext_data[10..12]);
+        let count = usize::from(LittleEndian::read_u16(&ext_data[12..14]));
+        if self.header_size == NODE_HEADER_SIZE && start == ATTRIBUTE_SIZE && size == ATTRIBUTE_SIZE
+        {
+            return Ok(Cow::Borrowed(self.data));
+        }
+        if self.header_size < NODE_HEADER_SIZE || size < ATTRIBUTE_SIZE {
+            return Err(invalid());
+        }
+
+        let mut data = Vec::with_capacity(
+            usize::from(NODE_HEADER_SIZE) + ATTR_EXT_SIZE + count * usize::from(ATTRIBUTE_SIZE),
+        );
+        data.extend_from_slice(&self.data[..usize::from(NODE_HEADER_SIZE)]);
+        data.extend_from_slice(ext_data);
+        for i in 0..count {
+            let offset = ext + usize::from(start) + i * usize::from(size);
+            let attribute = self
+                .data
+                .get(offset..offset + usize::from(ATTRIBUTE_SIZE))
+                .ok_or_else(invalid)?;
+            data.extend_from_slice(attribute);
+        }
+
+        let node_size = u32::try_from(data.len()).unwrap();
+        LittleEndian::write_u16(&mut data[2..4], NODE_HEADER_SIZE);
+        LittleEndian::write_u32(&mut data[4..8], node_size);
+        let ext = usize::from(NODE_HEADER_SIZE);
+        LittleEndian::write_u16(&mut data[ext + 8..ext + 10], ATTRIBUTE_SIZE);
+        LittleEndian::write_u16(&mut data[ext + 10..ext + 12], ATTRIBUTE_SIZE);
+        Ok(Cow::Owned(data))
+    }
+
+    // Returns the type of the chunk, or `None` if it is not a known type.
+    #[cfg(feature = "raw")]
+    pub(crate) fn resource_type(&self) -> Option<ResourceType> {
+        ResourceType::from_bytes((self.data, 0))
+            .ok()
+            .map(|(_, typ)| typ)
+    }
+}
+
+// Splits the chunk at the start of `input` from the chunks that follow it.
+// Only the chunk header is read, so chunks of unknown types can be skipped.
+// The sizes in the header are checked so that `data` always covers the
+// declared header.
+pub(crate) fn next_chunk(input: &[u8]) -> Result<(RawChunk<'_>, &[u8]), ParseError> {
+    let size = chunk_size(input, input.len())?;
+    let (data, rest) = input.split_at(size);
+    let padding = padding_len(size, rest, rest.len());
+    Ok((RawChunk::checked(data), &rest[padding..]))
+}
+
+// Returns the number of NUL bytes to skip after a chunk of `size` bytes.
+// Some tools pad chunks to a multiple of four bytes without counting the
+// padding in the chunk size. `next` holds at least the first
+// `min(8, available)` of the `available` bytes that follow the chunk. The
+// padding is only skipped if the next chunk header cannot be read without
+// skipping it, as the types of the XML nodes start with a NUL byte too.
+pub(crate) fn padding_len(size: usize, next: &[u8], available: usize) -> usize {
+    let padding = ((4 - size % 4) % 4).min(available);
+    if padding == 0
+        || next[..padding].iter().any(|b| *b != 0)
+        || chunk_size(next, available).is_ok()
+    {
+        return 0;
+    }
+    padding
+}
+
+// Returns the size of the chunk whose header is at the start of `input`,
+// checking it the same way as `next_chunk` when `available` bytes are left
+// in the document. Only the chunk header needs to be in `input`.
+pub(crate) fn chunk_size(input: &[u8], available: usize) -> Result<usize, ParseError> {
+    if input.len() < usize::from(CHUNK_HEADER_SIZE) {
+        return Err(ParseError::DekuError(DekuError::Incomplete(NeedSize::new(
+            (usize::from(CHUNK_HEADER_SIZE) - input.len()) * 8,
+        ))));
+    }
+
+    let typ = LittleEndian::read_u16(&input[0..2]);
+    let chunk_header_size = LittleEndian::read_u16(&input[2..4]);
+    let declared = LittleEndian::read_u32(&input[4..8]);
+    let size = usize::try_from(declared).unwrap();
+    if chunk_header_size < CHUNK_HEADER_SIZE
+        || size < usize::from(chunk_header_size)
+        || size > available
+    {
+        return Err(ParseError::InvalidChunkSize {
+            typ,
+            header_size: chunk_header_size,
+            declared,
+            available,
+        });
+    }
+
+    Ok(size)
+}
+
+// Returns the chunk at the start of `input` cut short at the end of the
+// input, for a chunk whose declared size is larger than what is left. The
+// header itself must be complete. The declared size is returned alongside
+// the chunk.
+pub(crate) fn truncated_chunk(input: &[u8]) -> Option<(RawChunk<'_>, usize)> {
+    if input.len() < usize::from(CHUNK_HEADER_SIZE) {
+        return None;
+    }
+
+    let header_size = LittleEndian::read_u16(&input[2..4]);
+    let size = usize::try_from(LittleEndian::read_u32(&input[4..8])).unwrap();
+    if header_size < CHUNK_HEADER_SIZE
+        || usize::from(header_size) > input.len()
+        || size <= input.len()
+    {
+        return None;
+    }
+
+    Some((
+        RawChunk {
+            typ: LittleEndian::read_u16(&input[0..2]),
+            header_size,
+            data: input,
+        },
+        size,
+    ))
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, DekuRead, DekuWrite)]
+pub(crate) const CHUNK_HEADER_SIZE: u16 = 8;
+
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+// Returns an error for input that is some other kind of file passed by
+// mistake. Only the first bytes are looked at, as that is all that has
+// been read from a stream at this point.
+pub(crate) fn sniff(input: &[u8]) -> Result<(), ParseError> {
+    let text = input.strip_prefix(UTF8_BOM).unwrap_or(input);
+    if text.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'<') {
+        return Err(ParseError::PlainTextXml);
+    }
+    if input.starts_with(ZIP_MAGIC) {
+        return Err(ParseError::LooksLikeZip);
+    }
+    Ok(())
+}
+
+// Returns the chunk type that `input` starts with, if any.
+pub(crate) fn first_chunk_type(input: &[u8]) -> Option<u16> {
+    Some(u16::from_le_bytes([*input.first()?, *input.get(1)?]))
+}
+
+// Returns an error if `input` does not start with the XML chunk, or with the
+// string pool of a document that has no document header, which aapt
+// accepts.
+pub(crate) fn check_document_type(input: &[u8]) -> Result<(), ParseError> {
+    match first_chunk_type(input) {
+        Some(found)
+            if found != ResourceType::Xml as u16 && found != ResourceType::StringPool as u16 =>
+        {
+            Err(ParseError::NotBinaryXml { found })
+        }
+        _ => Ok(()),
+    }
+}
+
+// Returns whether `input` is a document without a document header, which
+// starts with the string pool instead.
+pub(crate) fn is_headerless(input: &[u8]) -> bool {
+    first_chunk_type(input) == Some(ResourceType::StringPool as u16)
+}
+
+pub(crate) const NODE_HEADER_SIZE: u16 = 16;
+const ATTR_EXT_SIZE: usize = 20;
+pub(crate) const ATTRIBUTE_SIZE: u16 = 20;
+
+// Assembles a binary XML document from an encoded string pool, the resource
+// map and the XML nodes.
+pub(crate) fn write_document(
+    string_pool: &[u8],
+    resource_ids: Vec<u32>,
+    nodes: &[XmlNode],
+) -> Result<Vec<u8>, DekuError> {
+    let resource_map = ResourceMap {
+        header: ChunkHeader {
+            typ: ResourceType::XmlResourceMap,
+            header_size: CHUNK_HEADER_SIZE,
+            size: u32::from(CHUNK_HEADER_SIZE) + u32::try_from(resource_ids.len()).unwrap() * 4,
+        },
+        resource_ids,
+    }
+    .to_bytes()?;
+
+    let mut body = Vec::new();
+    body.extend_from_slice(string_pool);
+    body.extend_from_slice(&resource_map);
+    for node in nodes {
+        body.extend_from_slice(&node.to_bytes()?);
+    }
+
+    let header = ChunkHeader {
+        typ: ResourceType::Xml,
+        header_size: CHUNK_HEADER_SIZE,
+        size: u32::from(CHUNK_HEADER_SIZE) + u32::try_from(body.len()).unwrap(),
+    };
+
+    let mut data = header.to_bytes()?;
+    data.extend_from_slice(&body);
+    Ok(data)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, DekuRead, DekuWrite)]
 #[deku(type = "u16")]
 pub(crate) enum ResourceType {
     NullType = 0x000,
@@ -42,10 +299,25 @@ pub(crate) struct ChunkHeader {
     pub(crate) size: u32,
 }
 
+impl ChunkHeader {
+    // Returns whether the sizes in the header are consistent. The type is
+    // known, as the header could not be read otherwise.
+    pub(crate) fn is_plausible(&self) -> bool {
+        self.header_size >= CHUNK_HEADER_SIZE && self.size >= u32::from(self.header_size)
+    }
+}
+
+// Returns whether `input` starts with a plausible chunk header. Input that
+// is not binary XML at all, such as text XML or an image, is told apart
+// from a damaged document this way.
+pub(crate) fn has_plausible_header(input: &[u8]) -> bool {
+    ChunkHeader::from_bytes((input, 0)).is_ok_and(|(_, header)| header.is_plausible())
+}
+
 #[derive(Debug, DekuRead, DekuWrite)]
 pub(crate) struct ResourceMap {
     pub(crate) header: ChunkHeader,
-    #[deku(count = "(header.size - u32::from(header.header_size)) / 4")]
+    #[deku(count = "header.size.saturating_sub(u32::from(header.header_size)) / 4")]
     pub(crate) resource_ids: Vec<u32>,
 }
 
@@ -103,50 +375,6 @@ pub(crate) struct XmlAttrExt {
     pub(crate) style_index: u16,
 }
 
-#[derive(Debug, DekuRead, DekuWrite)]
-pub(crate) struct ResourceValue {
-    pub(crate) size: u16,
-    pub(crate) res: u8,
-    pub(crate) data_type: ResourceValueType,
-    pub(crate) data: u32,
-}
-
-impl ResourceValue {
-    pub(crate) fn get_value(&self, string_pool: &StringPool) -> Rc<String> {
-        match &self.data_type {
-            ResourceValueType::String => string_pool
-                .get(usize::try_from(self.data).unwrap())
-                .unwrap(),
-            ResourceValueType::Dec => Rc::new(self.data.to_string()),
-            ResourceValueType::Hex => Rc::new(format!("0x{}", self.data)),
-            ResourceValueType::Boolean => Rc::new(match self.data {
-                0 => "false".to_string(),
-                _ => "true".to_string(),
-            }),
-            n => Rc::new(format!("ResourceValueType::{:?}/{}", n, self.data)),
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
-#[deku(type = "u8")]
-pub(crate) enum ResourceValueType {
-    Null = 0x00,
-    Reference = 0x01,
-    Attribute = 0x02,
-    String = 0x03,
-    Float = 0x04,
-    Dimension = 0x05,
-    Fraction = 0x06,
-    Dec = 0x10,
-    Hex = 0x11,
-    Boolean = 0x12,
-    ColorArgb8 = 0x1c,
-    ColorRgb8 = 0x1d,
-    ColorArgb4 = 0x1e,
-    ColorRgb4 = 0x1f,
-}
-
 #[derive(Debug, DekuRead, DekuWrite)]
 pub(crate) struct XmlAttribute {
     pub(crate) ns: u32,
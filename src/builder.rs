@@ -0,0 +1,243 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::resource_value::AttributeValue;
+use crate::xml::get_resource_id;
+use crate::{EncodeError, Event, IndexMap, ParseError, XmlDocument, ANDROID_NAMESPACE};
+
+///Builder that writes a binary XML document one node at a time, in the
+///same order as the nodes appear in the document.
+///
+///Elements and namespaces that are still open when the document is built
+///are closed. The `android` prefix can be used without declaring it.
+///```rust
+///use axmldecoder::{parse, AttributeValue, DocumentBuilder};
+///let data = DocumentBuilder::new()
+///    .start_namespace("android", "http://schemas.android.com/apk/res/android")
+///    .start_element("manifest", &[("package", AttributeValue::from("com.example"))])
+///    .start_element("uses-sdk", &[("android:minSdkVersion", AttributeValue::Int(21))])
+///    .end_element()
+///    .build()?;
+///
+///let doc = parse(&data)?;
+///let uses_sdk = doc.find_first_element_by_tag("uses-sdk").unwrap();
+///assert_eq!(uses_sdk.get_attribute("android:minSdkVersion"), Some("21"));
+///# Ok::<(), Box<dyn std::error::Error>>(())
+///```
+#[derive(Debug, Clone)]
+pub struct DocumentBuilder {
+    events: Vec<Event>,
+    // `(prefix, uri)` pairs of the namespaces in scope, innermost last
+    namespaces: Vec<(String, String)>,
+    // Tags of the open elements and the number of namespaces that were in
+    // scope when each was started
+    open_elements: Vec<(String, usize)>,
+}
+
+impl Default for DocumentBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocumentBuilder {
+    ///Creates a builder for an empty document.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            namespaces: vec![("android".to_string(), ANDROID_NAMESPACE.to_string())],
+            open_elements: Vec::new(),
+        }
+    }
+
+    ///Declares `prefix` for `uri`. The namespace is in scope until the
+    ///matching [`DocumentBuilder::end_namespace`], or until the element
+    ///that is open when it is declared ends.
+    pub fn start_namespace(&mut self, prefix: &str, uri: &str) -> &mut Self {
+        self.namespaces.push((prefix.to_string(), uri.to_string()));
+        self.events.push(Event::StartNamespace {
+            prefix: prefix.to_string(),
+            uri: uri.to_string(),
+        });
+        self
+    }
+
+    ///Ends the innermost namespace declaration. Does nothing if no
+    ///namespace was declared.
+    pub fn end_namespace(&mut self) -> &mut Self {
+        let declared = self.open_elements.last().map_or(1, |(_, scope)| *scope);
+        if self.namespaces.len() > declared {
+            let (prefix, uri) = self.namespaces.pop().unwrap();
+            self.events.push(Event::EndNamespace { prefix, uri });
+        }
+        self
+    }
+
+    ///Starts an element. Prefixed tags and attribute names, such as
+    ///`android:name`, are placed in the namespace declared for the prefix.
+    pub fn start_element(&mut self, tag: &str, attributes: &[(&str, AttributeValue)]) -> &mut Self {
+        let namespace = tag
+            .split_once(':')
+            .and_then(|(prefix, _)| self.namespace_uri(prefix))
+            .map(str::to_string);
+
+        let mut values = IndexMap::default();
+        let mut resource_ids = IndexMap::default();
+        for (name, value) in attributes {
+            values.insert((*name).to_string(), value.clone());
+
+            // Android identifies its own attributes by resource ID.
+            if let Some((prefix, local)) = name.split_once(':') {
+                if self.namespace_uri(prefix) == Some(ANDROID_NAMESPACE) {
+                    if let Some(id) = get_resource_id(local) {
+                        resource_ids.insert((*name).to_string(), id);
+                    }
+                }
+            }
+        }
+
+        self.events.push(Event::StartElement {
+            tag: tag.to_string(),
+            namespace,
+            attributes: values,
+            raw_values: IndexMap::default(),
+            resource_ids,
+            id_attribute: None,
+            class_attribute: None,
+            style_attribute: None,
+            line: 0,
+            comment: None,
+        });
+        self.open_elements
+            .push((tag.to_string(), self.namespaces.len()));
+        self
+    }
+
+    ///Adds character data to the open element. Does nothing if no element
+    ///is open.
+    pub fn cdata(&mut self, data: &str) -> &mut Self {
+        if !self.open_elements.is_empty() {
+            self.events.push(Event::Cdata {
+                data: data.to_string(),
+                line: 0,
+                comment: None,
+            });
+        }
+        self
+    }
+
+    ///Ends the innermost open element, along with the namespaces declared
+    ///inside it. Does nothing if no element is open.
+    pub fn end_element(&mut self) -> &mut Self {
+        if let Some((tag, scope)) = self.open_elements.pop() {
+            self.events.push(Event::EndElement { tag });
+            while self.namespaces.len() > scope {
+                let (prefix, uri) = self.namespaces.pop().unwrap();
+                self.events.push(Event::EndNamespace { prefix, uri });
+            }
+        }
+        self
+    }
+
+    ///Builds the document as a [`XmlDocument`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ParseError::NoRootElement`] if no element was started.
+    pub fn build_document(&self) -> Result<XmlDocument, ParseError> {
+        let mut builder = self.clone();
+        while !builder.open_elements.is_empty() {
+            builder.end_element();
+        }
+        XmlDocument::from_events(builder.events)
+    }
+
+    ///Builds the document and encodes it into Android's binary XML format
+    ///with [`encode`](crate::encode).
+    ///
+    /// # Errors
+    ///
+    /// Will return `EncodeError` if no element was started, or if an
+    /// attribute uses a namespace prefix that is not declared.
+    pub fn build(&self) -> Result<Vec<u8>, EncodeError> {
+        self.build_document()
+            .map_err(|_| EncodeError::MissingRoot)?
+            .to_binary()
+    }
+
+    fn namespace_uri(&self, prefix: &str) -> Option<&str> {
+        self.namespaces
+            .iter()
+            .rev()
+            .find(|(p, _)| p == prefix)
+            .map(|(_, uri)| uri.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, Node};
+
+    #[test]
+    fn test_build() {
+        let data = DocumentBuilder::new()
+            .start_namespace("android", ANDROID_NAMESPACE)
+            .start_element(
+                "LinearLayout",
+                &[("android:orientation", AttributeValue::Int(1))],
+            )
+            .start_namespace("tools", "http://schemas.android.com/tools")
+            .start_element(
+                "TextView",
+                &[
+                    ("android:enabled", AttributeValue::Boolean(true)),
+                    ("tools:ignore", AttributeValue::from("HardcodedText")),
+                ],
+            )
+            .cdata("text")
+            .end_element()
+            .start_element("Button", &[])
+            .build()
+            .unwrap();
+
+        let doc = parse(&data).unwrap();
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+<LinearLayout xmlns:android="http://schemas.android.com/apk/res/android" xmlns:tools="http://schemas.android.com/tools" android:orientation="1">
+  <TextView xmlns:tools="http://schemas.android.com/tools" android:enabled="true" tools:ignore="HardcodedText">
+    <![CDATA[text]]>
+  </TextView>
+  <Button/>
+</LinearLayout>
+"#;
+        assert_eq!(doc.to_xml_string(), expected);
+        assert!(doc.get_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_build_errors() {
+        assert!(matches!(
+            DocumentBuilder::new().build(),
+            Err(EncodeError::MissingRoot)
+        ));
+        assert!(matches!(
+            DocumentBuilder::new()
+                .start_element("manifest", &[("app:name", AttributeValue::from("x"))])
+                .build(),
+            Err(EncodeError::UnknownNamespacePrefix(prefix)) if prefix == "app"
+        ));
+
+        // Unbalanced calls are ignored
+        let doc = DocumentBuilder::new()
+            .end_element()
+            .end_namespace()
+            .cdata("text")
+            .start_element("manifest", &[])
+            .end_namespace()
+            .build_document()
+            .unwrap();
+        assert!(matches!(doc.root(), Node::Element(e) if e.get_tag() == "manifest"));
+    }
+}
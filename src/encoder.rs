@@ -0,0 +1,294 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::binaryxml::{
+    self, ChunkHeader, ResourceType, XmlAttrExt, XmlAttribute, XmlCdata, XmlEndElement,
+    XmlEndNameSpace, XmlNode, XmlNodeHeader, XmlNodeType, XmlStartElement, XmlStartNameSpace,
+    ATTRIBUTE_SIZE, NODE_HEADER_SIZE,
+};
+use crate::resource_value::{ResourceValue, ResourceValueType};
+use crate::stringpool::StringPool;
+use crate::{Cdata, Element, EncodeError, IndexMap, IndexSet, Node, XmlDocument};
+
+const NO_ENTRY: u32 = u32::MAX;
+
+pub(crate) fn encode(doc: &XmlDocument) -> Result<Vec<u8>, EncodeError> {
+    let root = match doc.root() {
+        Node::Element(root) => root,
+        _ => return Err(EncodeError::MissingRoot),
+    };
+
+    let mut encoder = Encoder {
+        namespaces: doc.get_namespaces().clone(),
+        ..Encoder::default()
+    };
+    encoder.collect_resource_names(root)?;
+    encoder.encode_document(root)
+}
+
+#[derive(Default)]
+struct Encoder {
+    // prefix -> uri
+    namespaces: IndexMap<String, String>,
+    // Attribute names that have a resource ID, with the ID. These are
+    // placed at the start of the string pool so that their index matches
+    // the index of their ID in the resource map.
+    resource_names: IndexSet<(String, u32)>,
+    strings: IndexSet<String>,
+    nodes: Vec<XmlNode>,
+}
+
+impl Encoder {
+    fn collect_resource_names(&mut self, e: &Element) -> Result<(), EncodeError> {
+        for (key, _) in e.get_attributes() {
+            if key.starts_with("xmlns:") {
+                continue;
+            }
+
+            let (_, name) = self.split_name(e, key)?;
+            if let Some(id) = e.get_attribute_resource_id(key) {
+                self.resource_names.insert((name.to_string(), id));
+            }
+        }
+
+        for child in e.get_children() {
+            if let Node::Element(child) = child {
+                self.collect_resource_names(child)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_document(mut self, root: &Element) -> Result<Vec<u8>, EncodeError> {
+        self.encode_element(root)?;
+
+        let strings: Vec<&str> = self
+            .resource_names
+            .iter()
+            .map(|(name, _)| name)
+            .chain(self.strings.iter())
+            .map(String::as_str)
+            .collect();
+        let string_pool = StringPool::encode(&strings)?;
+
+        let resource_ids: Vec<u32> = self.resource_names.iter().map(|(_, id)| *id).collect();
+        Ok(binaryxml::write_document(
+            &string_pool,
+            resource_ids,
+            &self.nodes,
+        )?)
+    }
+
+    fn encode_element(&mut self, e: &Element) -> Result<(), EncodeError> {
+        // Namespaces are declared immediately before the element they were
+        // declared on when parsed, and closed after it.
+        let namespaces: Vec<(u32, u32)> = e
+            .get_declared_namespaces()
+            .iter()
+            .map(|(prefix, uri)| (self.string_index(prefix), self.string_index(uri)))
+            .collect();
+        self.push_start_namespaces(&namespaces);
+
+        let ns = match e.get_namespace() {
+            Some(uri) => self.string_index(uri),
+            None => NO_ENTRY,
+        };
+
+        // The tag is prefixed with the namespace prefix (or URI) if the
+        // element has a namespace.
+        let tag = match e.get_namespace() {
+            Some(_) => e.get_tag().rsplit_once(':').map_or(e.get_tag(), |t| t.1),
+            None => e.get_tag(),
+        };
+        let name = self.string_index(tag);
+
+        let mut attributes = Vec::new();
+        let mut keys = Vec::new();
+        for (key, value) in e.get_typed_attributes() {
+            if key.starts_with("xmlns:") {
+                continue;
+            }
+
+            let (uri, attr_name) = self.split_name(e, key)?;
+            let resource_id = e.get_attribute_resource_id(key);
+            let (uri, attr_name) = (uri.map(str::to_string), attr_name.to_string());
+
+            let ns = match uri {
+                Some(uri) => self.string_index(&uri),
+                None => NO_ENTRY,
+            };
+            let name = match resource_id {
+                Some(id) => {
+                    let i = self.resource_names.get_index_of(&(attr_name, id)).unwrap();
+                    u32::try_from(i).unwrap()
+                }
+                None => self.string_index(&attr_name),
+            };
+
+            let typed_value = value.to_resource_value(|s| self.string_index(s));
+            let raw_value = match (&typed_value.data_type, e.get_raw_attribute(key)) {
+                (ResourceValueType::String, _) => typed_value.data,
+                (_, Some(raw)) => self.string_index(raw),
+                (_, None) => NO_ENTRY,
+            };
+
+            attributes.push(XmlAttribute {
+                ns,
+                name,
+                raw_value,
+                typed_value,
+            });
+            keys.push(key);
+        }
+
+        let attribute_count = u16::try_from(attributes.len())
+            .map_err(|_| EncodeError::TooManyAttributes(attributes.len()))?;
+
+        // The special attributes are marked by their 1-based index, with 0
+        // for none. The index fits as the count does.
+        let special_index = |attribute: Option<(&str, &str)>| {
+            attribute
+                .and_then(|(name, _)| keys.iter().position(|key| *key == name))
+                .map_or(0, |i| u16::try_from(i + 1).unwrap())
+        };
+        let id_index = special_index(e.get_id_attribute());
+        let class_index = special_index(e.get_class_attribute());
+        let style_index = special_index(e.get_style_attribute());
+
+        self.push_node_with_source(
+            ResourceType::XmlStartElement,
+            u32::from(ATTRIBUTE_SIZE) + u32::from(attribute_count) * u32::from(ATTRIBUTE_SIZE),
+            XmlNodeType::XmlStartElement(XmlStartElement {
+                attr_ext: XmlAttrExt {
+                    ns,
+                    name,
+                    attribute_start: ATTRIBUTE_SIZE,
+                    attribute_size: ATTRIBUTE_SIZE,
+                    attribute_count,
+                    id_index,
+                    class_index,
+                    style_index,
+                },
+                attributes,
+            }),
+            e.get_line_number(),
+            e.get_comment(),
+        );
+
+        for child in e.get_children() {
+            match child {
+                Node::Element(child) => self.encode_element(child)?,
+                Node::Cdata(cdata) => self.encode_cdata(cdata),
+            }
+        }
+
+        self.push_node_with_source(
+            ResourceType::XmlEndElement,
+            8,
+            XmlNodeType::XmlEndElement(XmlEndElement { ns, name }),
+            e.get_line_number(),
+            None,
+        );
+
+        self.push_end_namespaces(&namespaces);
+
+        Ok(())
+    }
+
+    fn encode_cdata(&mut self, cdata: &Cdata) {
+        let data = self.string_index(cdata.get_data());
+        self.push_node_with_source(
+            ResourceType::XmlCdata,
+            12,
+            XmlNodeType::XmlCdata(XmlCdata {
+                data,
+                typed_data: ResourceValue::new(ResourceValueType::Null, 0),
+            }),
+            cdata.get_line_number(),
+            cdata.get_comment(),
+        );
+    }
+
+    fn push_start_namespaces(&mut self, namespaces: &[(u32, u32)]) {
+        for (prefix, uri) in namespaces {
+            self.push_node(
+                ResourceType::XmlStartNameSpace,
+                8,
+                XmlNodeType::XmlStartNameSpace(XmlStartNameSpace {
+                    prefix: *prefix,
+                    uri: *uri,
+                }),
+            );
+        }
+    }
+
+    fn push_end_namespaces(&mut self, namespaces: &[(u32, u32)]) {
+        for (prefix, uri) in namespaces.iter().rev() {
+            self.push_node(
+                ResourceType::XmlEndNameSpace,
+                8,
+                XmlNodeType::XmlEndNameSpace(XmlEndNameSpace {
+                    prefix: *prefix,
+                    uri: *uri,
+                }),
+            );
+        }
+    }
+
+    fn push_node(&mut self, typ: ResourceType, body_size: u32, element: XmlNodeType) {
+        self.push_node_with_source(typ, body_size, element, 0, None);
+    }
+
+    fn push_node_with_source(
+        &mut self,
+        typ: ResourceType,
+        body_size: u32,
+        element: XmlNodeType,
+        line_no: u32,
+        comment: Option<&str>,
+    ) {
+        let comment = match comment {
+            Some(comment) => self.string_index(comment),
+            None => NO_ENTRY,
+        };
+
+        self.nodes.push(XmlNode {
+            header: XmlNodeHeader {
+                chunk_header: ChunkHeader {
+                    typ,
+                    header_size: NODE_HEADER_SIZE,
+                    size: u32::from(NODE_HEADER_SIZE) + body_size,
+                },
+                line_no,
+                comment,
+            },
+            element,
+        });
+    }
+
+    // Splits an attribute name of `e` into its namespace URI and local name.
+    // A prefix can be bound to different URIs in different parts of the
+    // document, so the URI it had where the element was parsed is preferred.
+    fn split_name<'e, 'k>(
+        &'e self,
+        e: &'e Element,
+        key: &'k str,
+    ) -> Result<(Option<&'e str>, &'k str), EncodeError> {
+        match key.split_once(':') {
+            Some((prefix, name)) => {
+                let uri = e
+                    .scoped_namespace(prefix)
+                    .or_else(|| self.namespaces.get(prefix).map(String::as_str))
+                    .ok_or_else(|| EncodeError::UnknownNamespacePrefix(prefix.to_string()))?;
+                Ok((Some(uri), name))
+            }
+            None => Ok((None, key)),
+        }
+    }
+
+    fn string_index(&mut self, s: &str) -> u32 {
+        let (i, _) = self.strings.insert_full(s.to_string());
+        u32::try_from(self.resource_names.len() + i).unwrap()
+    }
+}
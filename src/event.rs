@@ -0,0 +1,1158 @@
+use alloc::borrow::Cow;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use deku::prelude::*;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+use crate::binaryxml::{
+    self, RawChunk, ResourceMap, ResourceType, XmlCdata, XmlEndElement, XmlEndNameSpace, XmlNode,
+    XmlNodeHeader, XmlNodeType, XmlStartElement, XmlStartNameSpace,
+};
+use crate::resource_value::{AttributeValue, ResourceValueType};
+use crate::stringpool::StringPool;
+use crate::xml::get_resource_string;
+use crate::{DocumentInfo, IndexMap, ParseError, ParseOptions, ANDROID_NAMESPACE};
+
+///Event emitted by [`EventReader`] while walking a binary XML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+pub enum Event {
+    ///A namespace declaration. The namespace is in scope until the matching
+    ///[`Event::EndNamespace`].
+    ///
+    ///Some packers reference namespace URIs that are never declared. The
+    ///reader then declares the URI itself, just before the element that
+    ///first uses it, with the last segment of the URI as its prefix. If that
+    ///prefix is already declared, it is reused without a new declaration.
+    StartNamespace { prefix: String, uri: String },
+    ///The end of a namespace declaration.
+    EndNamespace { prefix: String, uri: String },
+    ///The start of an element.
+    ///
+    ///`tag` and the attribute names are prefixed the same way as
+    ///[`Element::get_tag`](crate::Element::get_tag) and
+    ///[`Element::get_attributes`](crate::Element::get_attributes).
+    ///
+    ///`raw_values` holds the raw strings stored alongside attribute values
+    ///that are not strings, as returned by
+    ///[`Element::get_raw_attribute`](crate::Element::get_raw_attribute).
+    ///`resource_ids` holds the Android resource IDs of the attribute names
+    ///that have one, as returned by
+    ///[`Element::get_attribute_resource_id`](crate::Element::get_attribute_resource_id).
+    ///`id_attribute`, `class_attribute` and `style_attribute` are the names
+    ///of the attributes that the element marks as its ID, class and style.
+    StartElement {
+        tag: String,
+        namespace: Option<String>,
+        attributes: IndexMap<String, AttributeValue>,
+        raw_values: IndexMap<String, String>,
+        resource_ids: IndexMap<String, u32>,
+        id_attribute: Option<String>,
+        class_attribute: Option<String>,
+        style_attribute: Option<String>,
+        line: u32,
+        comment: Option<String>,
+    },
+    ///The end of an element.
+    EndElement { tag: String },
+    ///Character data found within an element.
+    Cdata {
+        data: String,
+        line: u32,
+        comment: Option<String>,
+    },
+    ///A chunk of a type that does not belong in binary XML, for example one
+    ///added by a newer version of AAPT. These are skipped over by the
+    ///parser in both strict and lenient mode. `data` holds the whole chunk,
+    ///including its header.
+    UnknownChunk { typ: u16, data: Vec<u8> },
+}
+
+///Reader that decodes a binary XML document one node at a time.
+///
+///Only the string pool and resource map are decoded up front, so callers
+///that only need part of the document can stop iterating early. Iteration
+///stops after the first error. In lenient mode, chunks that cannot be
+///decoded are skipped instead and reported by
+///[`EventReader::get_warnings`].
+///```rust
+///use axmldecoder::{Event, EventReader};
+///# use axmldecoder::ParseError;
+///let data = include_bytes!("../examples/AndroidManifest.xml");
+///let package = EventReader::new(data)?.find_map(|event| match event {
+///    Ok(Event::StartElement { tag, mut attributes, .. }) if tag == "manifest" => {
+///        attributes.remove("package").map(|v| v.to_string())
+///    }
+///    _ => None,
+///});
+///assert_eq!(package.as_deref(), Some("org.t0t0.androguard.TC"));
+///# Ok::<(), ParseError>(())
+///```
+pub struct EventReader<'a> {
+    input: Input<'a>,
+    string_pool: StringPool,
+    resource_map: Vec<u32>,
+    has_resource_map: bool,
+    // `(uri, prefix)` pairs of the namespaces in scope, innermost last
+    namespaces: Vec<(Arc<String>, Arc<String>)>,
+    // Events that are returned before reading the next chunk
+    queued: VecDeque<Event>,
+    depth: usize,
+    // Namespaces declared by the reader, with the depth of the element
+    // they were declared on
+    fallback_namespaces: Vec<(usize, String, String)>,
+    // Depths of the start elements that were skipped in lenient mode, so
+    // that their end elements are skipped too
+    skipped_elements: Vec<usize>,
+    info: DocumentInfo,
+    warnings: Vec<String>,
+    // Size of the document and offset of the chunk being read, for warnings
+    size: usize,
+    chunk_offset: usize,
+    // Index of the next XML node, for errors
+    node_index: usize,
+    lenient: bool,
+    prefer_raw_value: bool,
+    failed: bool,
+}
+
+impl<'a> EventReader<'a> {
+    ///Creates a reader over an Android binary XML document. Like aapt,
+    ///documents without a document header, which start with the string
+    ///pool, are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ParseError::PlainTextXml`] for text XML,
+    /// [`ParseError::LooksLikeZip`] for an APK or other ZIP archive, and
+    /// [`ParseError::NotBinaryXml`] if `input` starts with any other chunk
+    /// type than the XML chunk, such as for a DEX file. Will return
+    /// [`ParseError::InvalidMagicOrHeader`] if the sizes in the document
+    /// header make no sense. Will return another `ParseError` if
+    /// the document header, string pool or resource map cannot be parsed,
+    /// if the document has no string pool, or if `input` is shorter than
+    /// the size given in the document header.
+    pub fn new(input: &'a [u8]) -> Result<Self, ParseError> {
+        Self::with_options(input, &ParseOptions::default())
+    }
+
+    ///Creates a reader over an Android binary XML document using the given
+    ///[`ParseOptions`]. [`ParseOptions::max_depth`] is not checked by the
+    ///reader.
+    ///
+    /// # Errors
+    ///
+    /// Will return `ParseError` under the same conditions as
+    /// [`EventReader::new`]. If `options.lenient` is set, the type and sizes
+    /// of the document header are not checked and a truncated document is
+    /// read up to the end of `input`.
+    pub fn with_options(input: &'a [u8], options: &ParseOptions) -> Result<Self, ParseError> {
+        let input_size = input.len();
+        let mut warnings = Vec::new();
+        let (input, size, declared_size) = document_chunks(input, options, &mut warnings)?;
+        let info = DocumentInfo {
+            declared_size,
+            input_size,
+            ..DocumentInfo::default()
+        };
+
+        // The string pool and resource map are usually the first two chunks,
+        // but some packers drop the resource map or move it between the XML
+        // nodes. Like the Android framework, the chunks are located by type
+        // instead. If a chunk is duplicated, the first one is used.
+        let mut string_pool = None;
+        let mut resource_map = None;
+        let mut rest = input;
+        while !rest.is_empty() {
+            let (chunk, next) = match binaryxml::next_chunk(rest) {
+                Ok(chunk) => chunk,
+                // Reported when the nodes are read
+                Err(_) if options.lenient => break,
+                Err(e) => return Err(e),
+            };
+            if chunk.typ == ResourceType::StringPool as u16 && string_pool.is_none() {
+                let (_, pool) =
+                    StringPool::from_bytes((chunk.data, 0)).map_err(ParseError::DekuError)?;
+                string_pool = Some(pool);
+            } else if chunk.typ == ResourceType::XmlResourceMap as u16 && resource_map.is_none() {
+                let (_, map) =
+                    ResourceMap::from_bytes((chunk.data, 0)).map_err(ParseError::DekuError)?;
+                resource_map = Some(map.resource_ids);
+            }
+            rest = next;
+        }
+        let string_pool = string_pool.ok_or(ParseError::MissingStringPool)?;
+
+        Ok(Self::from_parts(
+            Input::Slice(input),
+            string_pool,
+            resource_map,
+            info,
+            warnings,
+            size,
+            options,
+        ))
+    }
+
+    fn from_parts(
+        input: Input<'a>,
+        string_pool: StringPool,
+        resource_map: Option<Vec<u32>>,
+        mut info: DocumentInfo,
+        warnings: Vec<String>,
+        size: usize,
+        options: &ParseOptions,
+    ) -> Self {
+        info.is_utf8 = string_pool.header.is_utf8();
+        info.string_count = string_pool.header.string_count;
+        info.style_count = string_pool.header.style_count;
+
+        // There are some files without the XmlStartNameSpace element.
+        // We should assume that the android namespace is always present even
+        // if not explicitly defined in the document.
+        //
+        // examples/AndroidManifestNoNamespace.xml
+        let namespaces = vec![(
+            Arc::new(ANDROID_NAMESPACE.to_string()),
+            Arc::new("android".to_string()),
+        )];
+
+        Self {
+            input,
+            string_pool,
+            has_resource_map: resource_map.is_some(),
+            resource_map: resource_map.unwrap_or_default(),
+            namespaces,
+            queued: VecDeque::new(),
+            depth: 0,
+            fallback_namespaces: Vec::new(),
+            skipped_elements: Vec::new(),
+            info,
+            warnings,
+            size,
+            chunk_offset: 0,
+            node_index: 0,
+            lenient: options.lenient,
+            prefer_raw_value: options.prefer_raw_value,
+            failed: false,
+        }
+    }
+
+    ///Returns information about the document and the nodes that have been
+    ///read so far.
+    #[must_use]
+    pub fn get_info(&self) -> &DocumentInfo {
+        &self.info
+    }
+
+    ///Returns a description of each problem that was worked around in
+    ///lenient mode so far, with the byte offset at which it was found.
+    #[must_use]
+    pub fn get_warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    // Reads the next XML node or unknown chunk, skipping over the string pool
+    // and resource map. Returns `None` once the end of the document is
+    // reached.
+    fn read_event(&mut self) -> Option<Result<Event, ParseError>> {
+        loop {
+            self.chunk_offset = self.input.offset(self.size);
+            let data = match self.input.next_chunk() {
+                Ok(Some(data)) => data,
+                Ok(None) => return None,
+                Err(e) if self.lenient => {
+                    self.warnings.push(format!(
+                        "Stopped reading at offset {}: {}",
+                        self.chunk_offset, e
+                    ));
+                    self.input = Input::Slice(&[]);
+                    return None;
+                }
+                Err(e) => return Some(Err(e)),
+            };
+            let chunk = RawChunk::checked(&data);
+
+            if chunk.is_xml_node() {
+                let index = self.node_index;
+                self.node_index += 1;
+                if chunk.typ == ResourceType::XmlEndElement as u16
+                    && self.skipped_elements.last() == Some(&self.depth)
+                {
+                    self.skipped_elements.pop();
+                    self.depth -= 1;
+                    continue;
+                }
+
+                let event = chunk.read_node().and_then(|node| self.process_node(&node));
+                match event {
+                    Err(e) if self.lenient => self.skip_chunk(chunk.typ, &e),
+                    Err(ParseError::DekuError(error)) => {
+                        return Some(Err(ParseError::ChunkDecode {
+                            offset: self.chunk_offset,
+                            typ: chunk.typ,
+                            size: chunk.data.len(),
+                            index,
+                            error,
+                        }))
+                    }
+                    event => return Some(event),
+                }
+                continue;
+            }
+
+            if chunk.typ == ResourceType::XmlResourceMap as u16 && !self.has_resource_map {
+                match ResourceMap::from_bytes((chunk.data, 0)) {
+                    Ok((_, map)) => {
+                        self.resource_map = map.resource_ids;
+                        self.has_resource_map = true;
+                    }
+                    Err(e) => return Some(Err(ParseError::DekuError(e))),
+                }
+            } else if chunk.typ != ResourceType::StringPool as u16
+                && chunk.typ != ResourceType::XmlResourceMap as u16
+            {
+                return Some(Ok(Event::UnknownChunk {
+                    typ: chunk.typ,
+                    data: chunk.data.to_vec(),
+                }));
+            }
+        }
+    }
+
+    fn process_node(&mut self, node: &XmlNode) -> Result<Event, ParseError> {
+        match &node.element {
+            XmlNodeType::XmlStartNameSpace(e) => {
+                let (uri, prefix) = self.process_start_namespace(e)?;
+                let event = Event::StartNamespace {
+                    prefix: prefix.to_string(),
+                    uri: uri.to_string(),
+                };
+                self.namespaces.push((uri, prefix));
+                Ok(event)
+            }
+            XmlNodeType::XmlEndNameSpace(e) => {
+                let event = self.process_end_namespace(e)?;
+                if let Event::EndNamespace { prefix, uri } = &event {
+                    self.end_namespace(prefix, uri);
+                }
+                Ok(event)
+            }
+            XmlNodeType::XmlStartElement(e) => {
+                let scope = self.namespaces.len();
+                let declared = self.declare_fallback_namespaces(e);
+                let event = match self.process_start_element(e, &node.header) {
+                    Ok(event) => event,
+                    Err(e) => {
+                        self.namespaces.truncate(scope);
+                        return Err(e);
+                    }
+                };
+                self.info.element_count += 1;
+                self.depth += 1;
+
+                // The declarations are returned first, followed by the
+                // element.
+                let mut events = declared.into_iter().map(|(prefix, uri)| {
+                    self.fallback_namespaces
+                        .push((self.depth, prefix.clone(), uri.clone()));
+                    Event::StartNamespace { prefix, uri }
+                });
+                match events.next() {
+                    Some(first) => {
+                        let rest: Vec<Event> = events.collect();
+                        self.queued.extend(rest);
+                        self.queued.push_back(event);
+                        Ok(first)
+                    }
+                    None => Ok(event),
+                }
+            }
+            XmlNodeType::XmlEndElement(e) => {
+                let event = self.process_end_element(e)?;
+                while let Some((depth, prefix, uri)) = self.fallback_namespaces.pop() {
+                    if depth != self.depth {
+                        self.fallback_namespaces.push((depth, prefix, uri));
+                        break;
+                    }
+                    self.end_namespace(&prefix, &uri);
+                    self.queued.push_back(Event::EndNamespace { prefix, uri });
+                }
+                self.depth = self.depth.saturating_sub(1);
+                Ok(event)
+            }
+            XmlNodeType::XmlCdata(e) => {
+                let event = self.process_cdata(e, &node.header)?;
+                self.info.cdata_count += 1;
+                Ok(event)
+            }
+        }
+    }
+
+    // Skips a node chunk that could not be read in lenient mode. The
+    // children of a skipped start element are kept and end up in its
+    // parent.
+    fn skip_chunk(&mut self, typ: u16, error: &ParseError) {
+        self.warnings.push(format!(
+            "Skipped invalid chunk of type 0x{:04x} at offset {}: {}",
+            typ, self.chunk_offset, error
+        ));
+        if typ == ResourceType::XmlStartElement as u16 {
+            self.depth += 1;
+            self.skipped_elements.push(self.depth);
+        }
+    }
+
+    fn get_string(&mut self, index: u32) -> Result<Arc<String>, ParseError> {
+        match self.string_pool.get(usize::try_from(index).unwrap()) {
+            Some(s) => Ok(s),
+            None if self.lenient => Ok(Arc::new(self.unresolved_string(index))),
+            None => Err(ParseError::StringNotFound(index)),
+        }
+    }
+
+    // Like `get_string`, but -1, which is the index used for no string, is
+    // read as an empty string. Some toolchains write it for a namespace
+    // prefix or for character data.
+    fn get_string_or_empty(&mut self, index: u32) -> Result<Arc<String>, ParseError> {
+        if index == u32::MAX {
+            return Ok(Arc::new(String::new()));
+        }
+        self.get_string(index)
+    }
+
+    // Returns the placeholder used in lenient mode for a string pool index
+    // that is out of range.
+    fn unresolved_string(&mut self, index: u32) -> String {
+        self.warnings.push(format!(
+            "String pool index 0x{:x} is out of range at offset {}",
+            index, self.chunk_offset
+        ));
+        format!("!!unresolved-string-0x{:x}!!", index)
+    }
+
+    // Comments are informational only, so an invalid index is treated as
+    // no comment.
+    fn get_comment(&self, header: &XmlNodeHeader) -> Option<String> {
+        self.string_pool
+            .get(usize::try_from(header.comment).unwrap())
+            .map(|s| s.to_string())
+    }
+
+    fn process_cdata(&mut self, e: &XmlCdata, header: &XmlNodeHeader) -> Result<Event, ParseError> {
+        Ok(Event::Cdata {
+            data: self.get_string_or_empty(e.data)?.to_string(),
+            line: header.line_no,
+            comment: self.get_comment(header),
+        })
+    }
+
+    fn process_start_namespace(
+        &mut self,
+        e: &XmlStartNameSpace,
+    ) -> Result<(Arc<String>, Arc<String>), ParseError> {
+        let uri = self.get_string(e.uri)?;
+        // A namespace without a prefix is the default namespace
+        let prefix = self.get_string_or_empty(e.prefix)?;
+
+        Ok((uri, prefix))
+    }
+
+    fn process_end_namespace(&mut self, e: &XmlEndNameSpace) -> Result<Event, ParseError> {
+        let uri = self.get_string(e.uri)?;
+        // Without a prefix, the innermost declaration of the URI is ended
+        let prefix = if e.prefix == u32::MAX {
+            self.namespaces
+                .iter()
+                .rev()
+                .find(|(u, _)| *u == uri)
+                .map_or_else(String::new, |(_, p)| p.to_string())
+        } else {
+            self.get_string(e.prefix)?.to_string()
+        };
+
+        Ok(Event::EndNamespace {
+            prefix,
+            uri: uri.to_string(),
+        })
+    }
+
+    fn process_end_element(&mut self, e: &XmlEndElement) -> Result<Event, ParseError> {
+        let name = self.get_string(e.name)?;
+        let namespace = self.string_pool.get(usize::try_from(e.ns).unwrap());
+
+        Ok(Event::EndElement {
+            tag: self.element_tag(namespace.as_deref(), &name),
+        })
+    }
+
+    fn element_tag(&self, namespace: Option<&String>, name: &str) -> String {
+        let mut tag = String::new();
+        if let Some(prefix) = self.namespace_prefix(namespace) {
+            tag.push_str(&prefix);
+            tag.push(':');
+        }
+        tag.push_str(name);
+        tag
+    }
+
+    // Removes the innermost declaration of `prefix` for `uri` from the
+    // namespaces in scope. Declarations are not always closed in order, so
+    // this does not simply pop the last one.
+    fn end_namespace(&mut self, prefix: &str, uri: &str) {
+        if let Some(i) = self
+            .namespaces
+            .iter()
+            .rposition(|(u, p)| **u == uri && **p == prefix)
+        {
+            self.namespaces.remove(i);
+        }
+    }
+
+    // Returns the innermost prefix in scope for `uri`. A declaration is
+    // hidden if its prefix is declared again in an inner scope, even if
+    // that is for a different URI.
+    fn scoped_prefix(&self, uri: &str) -> Option<&Arc<String>> {
+        let mut rebound: Vec<&Arc<String>> = Vec::new();
+        for (u, prefix) in self.namespaces.iter().rev() {
+            if **u == uri && !rebound.contains(&prefix) {
+                return Some(prefix);
+            }
+            rebound.push(prefix);
+        }
+        None
+    }
+
+    // Returns the prefix used for `uri`, which is the last segment of the
+    // URI if it is not in scope.
+    fn namespace_prefix(&self, uri: Option<&String>) -> Option<String> {
+        let uri = uri.filter(|uri| !uri.is_empty())?;
+        match self.scoped_prefix(uri) {
+            // Names in the default namespace are not prefixed
+            Some(prefix) if prefix.is_empty() => None,
+            Some(prefix) => Some(prefix.to_string()),
+            None => Some(fallback_prefix(uri)),
+        }
+    }
+
+    // Declares the namespaces used by the element and its attributes that
+    // are not in scope. Returns the new
+    // `(prefix, uri)` pairs.
+    fn declare_fallback_namespaces(&mut self, e: &XmlStartElement) -> Vec<(String, String)> {
+        let uris = core::iter::once(e.attr_ext.ns)
+            .chain(e.attributes.iter().map(|attr| attr.ns))
+            .filter_map(|ns| self.string_pool.get(usize::try_from(ns).unwrap()));
+
+        let mut declared = Vec::new();
+        for uri in uris {
+            if uri.is_empty() || self.scoped_prefix(&uri).is_some() {
+                continue;
+            }
+
+            let prefix = fallback_prefix(&uri);
+            if self.namespaces.iter().any(|(_, p)| **p == prefix) {
+                continue;
+            }
+
+            self.namespaces
+                .push((uri.clone(), Arc::new(prefix.clone())));
+            declared.push((prefix, uri.to_string()));
+        }
+        declared
+    }
+
+    fn process_start_element(
+        &mut self,
+        e: &XmlStartElement,
+        header: &XmlNodeHeader,
+    ) -> Result<Event, ParseError> {
+        let name = self.get_string(e.attr_ext.name)?;
+        let namespace = self
+            .string_pool
+            .get(usize::try_from(e.attr_ext.ns).unwrap());
+        let tag = self.element_tag(namespace.as_deref(), &name);
+
+        let mut attributes: IndexMap<String, AttributeValue> = IndexMap::default();
+        let mut raw_values: IndexMap<String, String> = IndexMap::default();
+        let mut resource_ids: IndexMap<String, u32> = IndexMap::default();
+        let mut names = Vec::with_capacity(e.attributes.len());
+
+        // Specially handle the <manifest> element by adding the namespace
+        // attributes to it.
+        if *name == "manifest" {
+            for (i, (url, name)) in self.namespaces.iter().enumerate() {
+                // Skip declarations shadowed by an inner one and the default
+                // namespace, which the printer declares
+                if name.is_empty() || self.namespaces[i + 1..].iter().any(|(u, _)| u == url) {
+                    continue;
+                }
+                attributes.insert(
+                    format!("xmlns:{}", name),
+                    AttributeValue::String(url.to_string()),
+                );
+            }
+        }
+
+        for attr in &e.attributes {
+            let ns = self.string_pool.get(usize::try_from(attr.ns).unwrap());
+            let name = self.get_string(attr.name)?;
+            let value = if self.prefer_raw_value
+                && attr.raw_value != u32::MAX
+                && attr.typed_value.data_type == ResourceValueType::String
+            {
+                AttributeValue::String(self.get_string(attr.raw_value)?.to_string())
+            } else {
+                match attr.typed_value.get_value(&self.string_pool) {
+                    Err(ParseError::StringNotFound(index)) if self.lenient => {
+                        AttributeValue::String(self.unresolved_string(index))
+                    }
+                    value => value?,
+                }
+            };
+
+            let mut final_name = String::new();
+            if let Some(prefix) = self.namespace_prefix(ns.as_deref()) {
+                final_name.push_str(&prefix);
+                final_name.push(':');
+            }
+
+            if name.is_empty() || !is_valid_attribute_name(&name) {
+                // Obfuscated files blank out or mangle the attribute name in
+                // the string pool. The name can still be recovered from the
+                // resource ID, which is what Android uses to resolve it.
+                let resource_id = self.resource_map.get(usize::try_from(attr.name).unwrap());
+                let resource_str = resource_id
+                    .and_then(|id| get_resource_string(*id))
+                    .filter(|s| s != "UNKNOWN");
+                match resource_str {
+                    Some(resource_str) => {
+                        if final_name.is_empty() {
+                            final_name.push_str("android:");
+                        }
+                        final_name.push_str(&resource_str);
+                        self.info.resource_map_attributes += 1;
+                    }
+                    None if name.is_empty() => {
+                        // Attributes newer than the known ones are named
+                        // after their ID, which keeps the names unique.
+                        let resource_id =
+                            resource_id.ok_or(ParseError::ResourceIdNotFound(attr.name))?;
+                        final_name.push_str(&format!("unknown-attr-{:#010x}", resource_id));
+                        self.info.resource_map_attributes += 1;
+                    }
+                    None => {
+                        final_name.push_str(&name);
+                        self.info.string_pool_attributes += 1;
+                    }
+                }
+            } else {
+                final_name.push_str(&name);
+                self.info.string_pool_attributes += 1;
+            }
+
+            // Some toolchains keep the original text of a value that was
+            // compiled to another type, such as a reference, as the raw value.
+            if !matches!(value, AttributeValue::String(_)) {
+                if let Some(raw) = self
+                    .string_pool
+                    .get(usize::try_from(attr.raw_value).unwrap())
+                {
+                    raw_values.insert(final_name.clone(), raw.to_string());
+                }
+            }
+
+            if let Some(id) = self.resource_map.get(usize::try_from(attr.name).unwrap()) {
+                resource_ids.insert(final_name.clone(), *id);
+            }
+
+            names.push(final_name.clone());
+            attributes.insert(final_name, value);
+        }
+
+        // The special attributes are marked by their 1-based index, with 0
+        // for none. Indexes past the last attribute are ignored.
+        let special = |index: u16| {
+            usize::from(index)
+                .checked_sub(1)
+                .and_then(|i| names.get(i))
+                .cloned()
+        };
+
+        Ok(Event::StartElement {
+            tag,
+            namespace: namespace.map(|ns| ns.to_string()),
+            attributes,
+            raw_values,
+            resource_ids,
+            id_attribute: special(e.attr_ext.id_index),
+            class_attribute: special(e.attr_ext.class_index),
+            style_attribute: special(e.attr_ext.style_index),
+            line: header.line_no,
+            comment: self.get_comment(header),
+        })
+    }
+}
+
+impl Iterator for EventReader<'_> {
+    type Item = Result<Event, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        if let Some(event) = self.queued.pop_front() {
+            return Some(Ok(event));
+        }
+
+        let event = self.read_event()?;
+        self.failed = event.is_err();
+        Some(event)
+    }
+}
+
+// Where the chunks of a document are read from
+enum Input<'a> {
+    Slice(&'a [u8]),
+    // Chunks that have been read by a `StreamReader`, with their offset
+    #[cfg(feature = "std")]
+    Chunks(VecDeque<(usize, Vec<u8>)>),
+}
+
+impl<'a> Input<'a> {
+    // Returns the offset of the next chunk in a document of `size` bytes.
+    fn offset(&self, size: usize) -> usize {
+        match self {
+            Input::Slice(input) => size - input.len(),
+            #[cfg(feature = "std")]
+            Input::Chunks(chunks) => chunks.front().map_or(size, |(offset, _)| *offset),
+        }
+    }
+
+    // Returns the next chunk, including its header, or `None` if there are
+    // no more chunks.
+    fn next_chunk(&mut self) -> Result<Option<Cow<'a, [u8]>>, ParseError> {
+        match self {
+            Input::Slice(input) if input.is_empty() => Ok(None),
+            Input::Slice(input) => {
+                let (chunk, rest) = binaryxml::next_chunk(input)?;
+                *input = rest;
+                Ok(Some(Cow::Borrowed(chunk.data)))
+            }
+            #[cfg(feature = "std")]
+            Input::Chunks(chunks) => Ok(chunks.pop_front().map(|(_, data)| Cow::Owned(data))),
+        }
+    }
+}
+
+// Checks the document header of `input` and returns the chunks after it,
+// the size of the document and the size given in the document header.
+fn document_chunks<'a>(
+    input: &'a [u8],
+    options: &ParseOptions,
+    warnings: &mut Vec<String>,
+) -> Result<(&'a [u8], usize, usize), ParseError> {
+    binaryxml::sniff(input)?;
+    if !options.lenient {
+        binaryxml::check_document_type(input)?;
+        let header_size = usize::from(binaryxml::CHUNK_HEADER_SIZE);
+        if input.len() >= header_size && !binaryxml::has_plausible_header(input) {
+            return Err(ParseError::InvalidMagicOrHeader);
+        }
+    }
+
+    if binaryxml::is_headerless(input) {
+        // The chunks of the document follow each other without an XML
+        // chunk around them.
+        return Ok((input, input.len(), input.len()));
+    }
+
+    let (header, declared_size) = match binaryxml::next_chunk(input) {
+        Ok((header, _)) => {
+            let size = header.data.len();
+            (header, size)
+        }
+        Err(e) => match binaryxml::truncated_chunk(input).filter(|_| options.lenient) {
+            Some(truncated) => {
+                warnings.push(format!("Document is truncated: {}", e));
+                truncated
+            }
+            None => return Err(e),
+        },
+    };
+    let body = &header.data[usize::from(header.header_size)..];
+    Ok((body, header.data.len(), declared_size))
+}
+
+// Reads the string pool of the document in `input`, without reading any of
+// the chunks after it.
+pub(crate) fn read_string_pool(input: &[u8]) -> Result<StringPool, ParseError> {
+    let (mut rest, _, _) = document_chunks(input, &ParseOptions::default(), &mut Vec::new())?;
+    while !rest.is_empty() {
+        let (chunk, next) = binaryxml::next_chunk(rest)?;
+        if chunk.typ == ResourceType::StringPool as u16 {
+            let (_, pool) =
+                StringPool::from_bytes((chunk.data, 0)).map_err(ParseError::DekuError)?;
+            return Ok(pool);
+        }
+        rest = next;
+    }
+    Err(ParseError::MissingStringPool)
+}
+
+// Source of the events that a document is built from
+pub(crate) trait EventSource: Iterator<Item = Result<Event, ParseError>> {
+    fn warnings(&self) -> &[String];
+}
+
+impl EventSource for EventReader<'_> {
+    fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
+
+// Reader that decodes a binary XML document while it is read from `R`, one
+// chunk at a time, so that only the chunks that have not been decoded yet
+// are held in memory. Chunks that come before the string pool are held
+// until it is found. A resource map that comes after the first XML node
+// only applies to the nodes after it.
+#[cfg(feature = "std")]
+pub(crate) struct StreamReader<R> {
+    events: EventReader<'static>,
+    stream: Stream<R>,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> StreamReader<R> {
+    pub(crate) fn new(mut reader: R) -> Result<Self, ParseError> {
+        let mut header = [0; binaryxml::CHUNK_HEADER_SIZE as usize];
+        read_exact(&mut reader, &mut header)?;
+        binaryxml::sniff(&header)?;
+        binaryxml::check_document_type(&header)?;
+        if !binaryxml::has_plausible_header(&header) {
+            return Err(ParseError::InvalidMagicOrHeader);
+        }
+
+        // The string pool of a document without a document header has been
+        // partly read already, and the document ends with the reader.
+        let mut first = None;
+        let (mut stream, size) = if binaryxml::is_headerless(&header) {
+            let mut data = header.to_vec();
+            let size = binaryxml::chunk_size(&header, usize::MAX)?;
+            read_chunk_body(&mut reader, &mut data, size)?;
+            let stream = Stream {
+                reader,
+                offset: data.len(),
+                remaining: usize::MAX,
+                last_size: data.len(),
+                until_eof: true,
+            };
+            first = Some((0, data));
+            (stream, 0)
+        } else {
+            let size = binaryxml::chunk_size(&header, usize::MAX)?;
+            let header = RawChunk::checked(&header);
+
+            // The rest of the document header is not used
+            let mut extra = vec![0; usize::from(header.header_size) - header.data.len()];
+            read_exact(&mut reader, &mut extra)?;
+
+            let stream = Stream {
+                reader,
+                offset: usize::from(header.header_size),
+                remaining: size - usize::from(header.header_size),
+                last_size: 0,
+                until_eof: false,
+            };
+            (stream, size)
+        };
+
+        let mut chunks = VecDeque::new();
+        let mut resource_map = None;
+        let string_pool = loop {
+            let (offset, data) = match first.take() {
+                Some(chunk) => chunk,
+                None => (
+                    stream.offset,
+                    stream.read_chunk()?.ok_or(ParseError::MissingStringPool)?,
+                ),
+            };
+            let chunk = RawChunk::checked(&data);
+            if chunk.typ == ResourceType::StringPool as u16 {
+                let (_, pool) =
+                    StringPool::from_bytes((chunk.data, 0)).map_err(ParseError::DekuError)?;
+                break pool;
+            } else if chunk.typ == ResourceType::XmlResourceMap as u16 && resource_map.is_none() {
+                let (_, map) =
+                    ResourceMap::from_bytes((chunk.data, 0)).map_err(ParseError::DekuError)?;
+                resource_map = Some(map.resource_ids);
+            } else {
+                chunks.push_back((offset, data));
+            }
+        };
+
+        let info = DocumentInfo {
+            declared_size: size,
+            input_size: size,
+            ..DocumentInfo::default()
+        };
+        let events = EventReader::from_parts(
+            Input::Chunks(chunks),
+            string_pool,
+            resource_map,
+            info,
+            Vec::new(),
+            size,
+            &ParseOptions::default(),
+        );
+        Ok(Self { events, stream })
+    }
+}
+
+#[cfg(feature = "std")]
+struct Stream<R> {
+    reader: R,
+    // Offset of the next chunk and the bytes of the document left to read
+    offset: usize,
+    remaining: usize,
+    // Size of the last chunk read, for skipping the padding after it
+    last_size: usize,
+    // Whether the document ends with the reader instead, for documents
+    // without a document header giving their size
+    until_eof: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Stream<R> {
+    // Reads the next chunk, including its header, or returns `None` at the
+    // end of the document.
+    fn read_chunk(&mut self) -> Result<Option<Vec<u8>>, ParseError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        let header_size = usize::from(binaryxml::CHUNK_HEADER_SIZE);
+        let mut data = vec![0; self.remaining.min(header_size)];
+        let mut read = 0;
+        if self.until_eof {
+            read = self.reader.read(&mut data)?;
+            if read == 0 {
+                return Ok(None);
+            }
+        }
+        read_exact(&mut self.reader, &mut data[read..])?;
+
+        let padding = binaryxml::padding_len(self.last_size, &data, self.remaining);
+        if padding > 0 {
+            data.drain(..padding);
+            self.offset += padding;
+            self.remaining -= padding;
+            if self.remaining == 0 {
+                return Ok(None);
+            }
+            let read = data.len();
+            data.resize(self.remaining.min(header_size), 0);
+            read_exact(&mut self.reader, &mut data[read..])?;
+        }
+
+        let size = binaryxml::chunk_size(&data, self.remaining)?;
+        read_chunk_body(&mut self.reader, &mut data, size)?;
+        self.offset += size;
+        self.remaining -= size;
+        self.last_size = size;
+        Ok(Some(data))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Iterator for StreamReader<R> {
+    type Item = Result<Event, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.events.next() {
+                return Some(event);
+            }
+            if self.events.failed {
+                return None;
+            }
+
+            let offset = self.stream.offset;
+            match self.stream.read_chunk() {
+                Ok(Some(data)) => {
+                    if let Input::Chunks(chunks) = &mut self.events.input {
+                        chunks.push_back((offset, data));
+                    }
+                }
+                Ok(None) => return None,
+                Err(e) => {
+                    self.events.failed = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> EventSource for StreamReader<R> {
+    fn warnings(&self) -> &[String] {
+        &self.events.warnings
+    }
+}
+
+// Reads exactly enough bytes to fill `buf`, returning
+// `ParseError::UnexpectedEof` if the reader ends first.
+#[cfg(feature = "std")]
+fn read_exact(reader: &mut impl Read, buf: &mut [u8]) -> Result<(), ParseError> {
+    reader.read_exact(buf).map_err(|e| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => ParseError::UnexpectedEof,
+        _ => ParseError::IoError(e),
+    })
+}
+
+// Reads the rest of a chunk of `size` bytes, whose start is already in
+// `data`. The size comes from the input, so the buffer only grows as the
+// bytes arrive instead of being allocated up front.
+#[cfg(feature = "std")]
+fn read_chunk_body(
+    reader: &mut impl Read,
+    data: &mut Vec<u8>,
+    size: usize,
+) -> Result<(), ParseError> {
+    let rest = size - data.len();
+    reader.by_ref().take(rest as u64).read_to_end(data)?;
+    if data.len() < size {
+        return Err(ParseError::UnexpectedEof);
+    }
+    Ok(())
+}
+
+// Derives a prefix for a namespace URI that was never declared, as apktool
+// does. There are samples where the namespace is the raw prefix instead of
+// a URI, e.g. examples/AndroidManifestUnknownNamespace.xml, which this maps
+// back to that prefix.
+fn fallback_prefix(uri: &str) -> String {
+    uri.rsplit(['/', ':', '#'])
+        .find(|segment| !segment.is_empty())
+        .filter(|segment| is_valid_attribute_name(segment))
+        .unwrap_or("ns")
+        .to_string()
+}
+
+// Returns whether `name` is usable as an XML attribute name.
+fn is_valid_attribute_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        let events: Vec<Event> = EventReader::new(data)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            events.first(),
+            Some(&Event::StartNamespace {
+                prefix: "android".to_string(),
+                uri: "http://schemas.android.com/apk/res/android".to_string(),
+            })
+        );
+        assert!(matches!(
+            events.last(),
+            Some(Event::EndNamespace { prefix, .. }) if prefix == "android"
+        ));
+
+        let Some(Event::StartElement {
+            tag,
+            namespace,
+            attributes,
+            raw_values,
+            resource_ids,
+            id_attribute,
+            class_attribute,
+            style_attribute,
+            line,
+            comment,
+        }) = events.get(1)
+        else {
+            panic!("missing manifest element");
+        };
+        assert_eq!(tag, "manifest");
+        assert_eq!(namespace, &None);
+        assert_eq!(
+            attributes.get("package"),
+            Some(&AttributeValue::String(
+                "org.t0t0.androguard.TC".to_string()
+            ))
+        );
+        assert!(raw_values.is_empty());
+        assert_eq!(resource_ids.get("android:versionCode"), Some(&0x0101_021b));
+        assert_eq!(resource_ids.get("package"), None);
+        assert_eq!(
+            (id_attribute, class_attribute, style_attribute),
+            (&None, &None, &None)
+        );
+        assert_eq!(*line, 2);
+        assert_eq!(comment, &None);
+
+        let tags: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                Event::EndElement { tag } => Some(tag.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            tags,
+            [
+                "action",
+                "category",
+                "intent-filter",
+                "activity",
+                "application",
+                "manifest"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_truncated_header() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        assert!(EventReader::new(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_missing_string_pool() {
+        let header = [0x03, 0x00, 0x08, 0x00, 0x08, 0x00, 0x00, 0x00];
+        assert!(matches!(
+            EventReader::new(&header),
+            Err(ParseError::MissingStringPool)
+        ));
+    }
+
+    #[test]
+    fn test_stops_after_error() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        let mut reader = EventReader::new(data).unwrap();
+        // Corrupt the remaining nodes
+        let offset = reader.input.offset(reader.size);
+        reader.input = Input::Slice(&data[offset + 1..]);
+
+        assert!(matches!(reader.next(), Some(Err(_))));
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,91 @@
+///Information about how a document is stored, as returned by
+///[`parse_with_info`](crate::parse_with_info).
+///
+///Obfuscated files often differ from what aapt produces in ways that are
+///not visible in the parsed document, such as data after the end of the
+///document.
+///```rust
+///use axmldecoder::parse_with_info;
+///# use axmldecoder::ParseError;
+///let data = include_bytes!("../examples/AndroidManifest.xml");
+///let (_, info) = parse_with_info(data)?;
+///assert!(!info.is_utf8());
+///assert_eq!(info.get_element_count(), 6);
+///assert_eq!(info.get_declared_size(), info.get_input_size());
+///# Ok::<(), ParseError>(())
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentInfo {
+    pub(crate) is_utf8: bool,
+    pub(crate) string_count: u32,
+    pub(crate) style_count: u32,
+    pub(crate) element_count: usize,
+    pub(crate) cdata_count: usize,
+    pub(crate) string_pool_attributes: usize,
+    pub(crate) resource_map_attributes: usize,
+    pub(crate) declared_size: usize,
+    pub(crate) input_size: usize,
+}
+
+impl DocumentInfo {
+    ///Returns whether the strings are encoded as UTF-8 instead of UTF-16.
+    #[must_use]
+    pub fn is_utf8(&self) -> bool {
+        self.is_utf8
+    }
+
+    ///Returns the number of strings in the string pool.
+    #[must_use]
+    pub fn get_string_count(&self) -> u32 {
+        self.string_count
+    }
+
+    ///Returns the number of styled strings in the string pool.
+    #[must_use]
+    pub fn get_style_count(&self) -> u32 {
+        self.style_count
+    }
+
+    ///Returns the number of elements read, including any after the root
+    ///element.
+    #[must_use]
+    pub fn get_element_count(&self) -> usize {
+        self.element_count
+    }
+
+    ///Returns the number of character data nodes read.
+    #[must_use]
+    pub fn get_cdata_count(&self) -> usize {
+        self.cdata_count
+    }
+
+    ///Returns the number of attributes whose name was read from the string
+    ///pool.
+    #[must_use]
+    pub fn get_string_pool_attributes(&self) -> usize {
+        self.string_pool_attributes
+    }
+
+    ///Returns the number of attributes whose name was missing or mangled in
+    ///the string pool and was recovered from the resource map instead.
+    ///Missing names with a resource ID that is not known are counted too,
+    ///as they are named after the ID, e.g. `unknown-attr-0x01010572`.
+    #[must_use]
+    pub fn get_resource_map_attributes(&self) -> usize {
+        self.resource_map_attributes
+    }
+
+    ///Returns the document size declared in the document header, or the
+    ///size of the input for a document without a document header.
+    #[must_use]
+    pub fn get_declared_size(&self) -> usize {
+        self.declared_size
+    }
+
+    ///Returns the size of the input. Anything after
+    ///[`DocumentInfo::get_declared_size`] is ignored when parsing.
+    #[must_use]
+    pub fn get_input_size(&self) -> usize {
+        self.input_size
+    }
+}
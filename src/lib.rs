@@ -2,10 +2,12 @@
 //!
 //!This library implements the minimal amount of parsing required obtain
 //!useful information from a binary `AndroidManifest.xml`. It does not
-//!support parsing generic binary XML documents and does not have
-//!support for decoding resource identifiers. In return, the compiled
-//!footprint of the library is _much_ lighter as it does not have to
-//!link in Android's `resources.arsc` file.
+//!support parsing generic binary XML documents. Resource identifiers are
+//!shown as IDs, except that the `android-res-names` feature names the
+//!common public resources of the Android framework with
+//![`resnames::lookup`]. In return, the compiled footprint of the library
+//!is _much_ lighter as it does not have to link in Android's
+//!`resources.arsc` file.
 //!
 //!For a full-featured Rust binary XML parser,
 //![abxml-rs](https://github.com/SUPERAndroidAnalyzer/abxml-rs)
@@ -14,21 +16,116 @@
 //!
 //!Please file an issue with the relevant binary `AndroidManifest.xml` if
 //!if any issues are encountered.
+//!
+//!Enabling the `manifest` feature adds [`Manifest`], which extracts the
+//!commonly used information from a parsed `AndroidManifest.xml`, and
+//![`validate_manifest`], which checks it for common problems.
+//!
+//!Enabling the `raw` feature adds the [`raw`] module, which reports the
+//!layout of the chunks in a document for inspecting packed or tampered
+//!files.
+//!
+//!Enabling the `android-res-names` feature adds the [`resnames`] module,
+//!and shows references to well-known Android framework resources by name
+//!instead of by resource ID.
+//!
+//!Enabling the `serde` feature implements `serde::Serialize` for
+//![`XmlDocument`] and the types it contains. The `serde_json` feature
+//!additionally adds [`XmlDocument::to_json_string`].
+//!
+//!Enabling the `zip` feature adds [`parse_apk`] and [`parse_apk_reader`],
+//!which read the `AndroidManifest.xml` out of an APK. It implies `std`.
+//!
+//!The `std` feature is enabled by default. Without it the library only
+//!depends on `alloc`, and [`parse_reader`] and [`parse_file`] are not
+//!available.
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
 
 mod binaryxml;
+mod builder;
+mod encoder;
+mod event;
+mod info;
+#[cfg(feature = "manifest")]
+mod manifest;
+mod printer;
+#[cfg(feature = "raw")]
+pub mod raw;
+#[cfg(feature = "android-res-names")]
+pub mod resnames;
+mod resource_value;
+#[cfg(feature = "serde")]
+mod serialize;
 mod stringpool;
+#[cfg(feature = "manifest")]
+mod validate;
 mod xml;
 
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+use crate::event::read_string_pool;
+#[cfg(feature = "std")]
+use crate::event::StreamReader;
+#[cfg(feature = "zip")]
+use std::io::Seek;
+#[cfg(feature = "std")]
+use std::path::Path;
 use thiserror::Error;
 
-use crate::binaryxml::BinaryXmlDocument;
+pub use crate::builder::DocumentBuilder;
+pub use crate::event::{Event, EventReader};
+pub use crate::info::DocumentInfo;
+#[cfg(feature = "manifest")]
+pub use crate::manifest::{Component, IntentFilter, Manifest};
+pub use crate::resource_value::{AttributeValue, DimensionUnit, FractionType};
+#[cfg(feature = "manifest")]
+pub use crate::validate::{validate_manifest, ManifestWarning};
 pub use crate::xml::{Cdata, Element, Node, XmlDocument};
 
+pub(crate) const ANDROID_NAMESPACE: &str = "http://schemas.android.com/apk/res/android";
+
+// Without std there is no randomly seeded hasher, so the maps fall back to
+// one with fixed keys.
+#[cfg(feature = "std")]
+pub(crate) type IndexMap<K, V> = indexmap::IndexMap<K, V>;
+#[cfg(not(feature = "std"))]
+pub(crate) type IndexMap<K, V> = indexmap::IndexMap<K, V, FixedHasher>;
+#[cfg(feature = "std")]
+pub(crate) type IndexSet<T> = indexmap::IndexSet<T>;
+#[cfg(not(feature = "std"))]
+pub(crate) type IndexSet<T> = indexmap::IndexSet<T, FixedHasher>;
+#[cfg(not(feature = "std"))]
+type FixedHasher = core::hash::BuildHasherDefault<ahash::AHasher>;
+
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ParseError {
     #[error("parse error: {0}")]
     DekuError(deku::DekuError),
 
+    #[error("Document has no string pool")]
+    MissingStringPool,
+
+    #[error("Input is plain text XML, not Android binary XML")]
+    PlainTextXml,
+
+    #[error("Input looks like a ZIP archive, such as an APK, not Android binary XML")]
+    LooksLikeZip,
+
+    #[error("Input is not an Android binary XML document: found chunk type 0x{found:04x}")]
+    NotBinaryXml { found: u16 },
+
+    #[error("Input does not start with a valid chunk header")]
+    InvalidMagicOrHeader,
+
     #[error("StringPool missing index: {0}")]
     StringNotFound(u32),
 
@@ -38,11 +135,166 @@ pub enum ParseError {
     #[error("Unknown resource string: {0}")]
     UnknownResourceString(u32),
 
+    #[error("End element found without a matching start element")]
+    UnexpectedEndElement,
+
+    #[error("Cdata found outside of an element")]
+    CdataOutsideElement,
+
+    #[error("Document ended before the root element was closed")]
+    UnexpectedEof,
+
+    #[error("Document has no root element")]
+    NoRootElement,
+
+    #[error("Element has no attribute named {0}")]
+    AttributeNotFound(String),
+
+    #[cfg(feature = "manifest")]
+    #[error("Root element is <{0}>, not <manifest>")]
+    NotManifest(String),
+
+    #[error(
+        "Invalid size {declared} for chunk of type 0x{typ:04x} with header size \
+         {header_size} and {available} bytes available"
+    )]
+    InvalidChunkSize {
+        typ: u16,
+        header_size: u16,
+        declared: u32,
+        available: usize,
+    },
+
+    #[error(
+        "Invalid chunk of type 0x{typ:04x} and size {size} at offset {offset} \
+         (node {index}): {error}"
+    )]
+    ChunkDecode {
+        offset: usize,
+        typ: u16,
+        size: usize,
+        index: usize,
+        #[cfg_attr(feature = "std", source)]
+        error: deku::DekuError,
+    },
+
+    #[error("String pool offset out of bounds: {0}")]
+    StringOffsetOutOfBounds(usize),
+
+    #[error("Elements are nested deeper than the maximum depth of {0}")]
+    MaxDepthExceeded(usize),
+
     #[error(transparent)]
-    Utf8StringParseError(std::string::FromUtf8Error),
+    Utf8StringParseError(alloc::string::FromUtf8Error),
 
     #[error(transparent)]
-    Utf16StringParseError(std::string::FromUtf16Error),
+    Utf16StringParseError(alloc::string::FromUtf16Error),
+
+    #[cfg(feature = "std")]
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+
+    #[cfg(feature = "zip")]
+    #[error(transparent)]
+    ZipError(#[from] zip::result::ZipError),
+}
+
+// std::string::FromUtf16Error and std::io::Error do not implement PartialEq,
+// so this cannot be derived.
+impl PartialEq for ParseError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ParseError::DekuError(a), ParseError::DekuError(b)) => a == b,
+            (ParseError::StringNotFound(a), ParseError::StringNotFound(b))
+            | (ParseError::ResourceIdNotFound(a), ParseError::ResourceIdNotFound(b))
+            | (ParseError::UnknownResourceString(a), ParseError::UnknownResourceString(b)) => {
+                a == b
+            }
+            (ParseError::MissingStringPool, ParseError::MissingStringPool)
+            | (ParseError::PlainTextXml, ParseError::PlainTextXml)
+            | (ParseError::LooksLikeZip, ParseError::LooksLikeZip)
+            | (ParseError::InvalidMagicOrHeader, ParseError::InvalidMagicOrHeader)
+            | (ParseError::UnexpectedEndElement, ParseError::UnexpectedEndElement)
+            | (ParseError::UnexpectedEof, ParseError::UnexpectedEof)
+            | (ParseError::NoRootElement, ParseError::NoRootElement)
+            | (ParseError::CdataOutsideElement, ParseError::CdataOutsideElement)
+            | (ParseError::Utf16StringParseError(_), ParseError::Utf16StringParseError(_)) => true,
+            (ParseError::Utf8StringParseError(a), ParseError::Utf8StringParseError(b)) => a == b,
+            (ParseError::MaxDepthExceeded(a), ParseError::MaxDepthExceeded(b))
+            | (ParseError::StringOffsetOutOfBounds(a), ParseError::StringOffsetOutOfBounds(b)) => {
+                a == b
+            }
+            (ParseError::AttributeNotFound(a), ParseError::AttributeNotFound(b)) => a == b,
+            #[cfg(feature = "manifest")]
+            (ParseError::NotManifest(a), ParseError::NotManifest(b)) => a == b,
+            (ParseError::NotBinaryXml { found: a }, ParseError::NotBinaryXml { found: b }) => {
+                a == b
+            }
+            (
+                ParseError::InvalidChunkSize {
+                    typ,
+                    header_size,
+                    declared,
+                    available,
+                },
+                ParseError::InvalidChunkSize {
+                    typ: t,
+                    header_size: h,
+                    declared: d,
+                    available: a,
+                },
+            ) => typ == t && header_size == h && declared == d && available == a,
+            (
+                ParseError::ChunkDecode {
+                    offset,
+                    typ,
+                    size,
+                    index,
+                    error,
+                },
+                ParseError::ChunkDecode {
+                    offset: o,
+                    typ: t,
+                    size: s,
+                    index: i,
+                    error: e,
+                },
+            ) => offset == o && typ == t && size == s && index == i && error == e,
+            #[cfg(feature = "std")]
+            (ParseError::IoError(a), ParseError::IoError(b)) => a.kind() == b.kind(),
+            #[cfg(feature = "zip")]
+            (ParseError::ZipError(a), ParseError::ZipError(b)) => {
+                core::mem::discriminant(a) == core::mem::discriminant(b)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ParseError {}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum EncodeError {
+    // deku only implements `Error` with std, so it can only be the source
+    // there.
+    #[error("encode error: {0}")]
+    DekuError(#[cfg_attr(feature = "std", source)] deku::DekuError),
+
+    #[error("Document has no root element")]
+    MissingRoot,
+
+    #[error("Undeclared namespace prefix: {0}")]
+    UnknownNamespacePrefix(String),
+
+    #[error("Element has {0} attributes, more than the 65535 binary XML allows")]
+    TooManyAttributes(usize),
+}
+
+impl From<deku::DekuError> for EncodeError {
+    fn from(e: deku::DekuError) -> Self {
+        EncodeError::DekuError(e)
+    }
 }
 
 ///Parses an Android binary XML and returns a [`XmlDocument`] object.
@@ -58,15 +310,256 @@ pub enum ParseError {
 ///# Ok::<(), ParseError>(())
 ///```
 pub fn parse(input: &[u8]) -> Result<XmlDocument, ParseError> {
-    let binaryxml = BinaryXmlDocument::try_from(input).map_err(ParseError::DekuError)?;
-    XmlDocument::new(binaryxml)
+    parse_with_options(input, &ParseOptions::default())
+}
+
+///Options controlling how strictly a document is parsed.
+///
+///The default options are strict and are the ones used by [`parse`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    ///Tolerate damaged documents, such as obfuscated files with garbage in
+    ///unused fields, and return as much of the document as can be read. The
+    ///type of the document header is not checked and string pool indexes
+    ///that are out of range are read as a placeholder such as
+    ///`!!unresolved-string-0x2a!!` instead of returning
+    ///[`ParseError::StringNotFound`]. XML nodes that cannot be decoded are
+    ///skipped, and a truncated document is read up to where it is cut off.
+    ///Elements that are still open when the document ends are closed instead
+    ///of returning [`ParseError::UnexpectedEof`].
+    ///
+    ///Everything that was worked around is reported by
+    ///[`XmlDocument::get_warnings`], along with its byte offset in the
+    ///input.
+    pub lenient: bool,
+    ///Maximum depth that elements may be nested to, with the root element
+    ///at depth 1. Deeper documents return [`ParseError::MaxDepthExceeded`].
+    pub max_depth: Option<usize>,
+    ///Read string attributes from their raw string instead of their typed
+    ///value when the two differ. Some obfuscators point the typed value at
+    ///a decoy string. Android itself returns the raw string from
+    ///`XmlPullParser.getAttributeValue`, but resolves the typed value when
+    ///the attribute is read through resources, so neither is always the
+    ///one the app sees.
+    pub prefer_raw_value: bool,
+}
+
+///Parses an Android binary XML using the given [`ParseOptions`] and returns a
+///[`XmlDocument`] object.
+///
+/// # Errors
+///
+/// Will return `ParseError` if `input` cannot be parsed
+///```rust
+///use axmldecoder::{parse_with_options, ParseOptions};
+///# use axmldecoder::ParseError;
+///let data = include_bytes!("../examples/AndroidManifest.xml");
+///let options = ParseOptions {
+///    lenient: true,
+///    max_depth: Some(32),
+///    ..ParseOptions::default()
+///};
+///parse_with_options(data, &options)?;
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_with_options(input: &[u8], options: &ParseOptions) -> Result<XmlDocument, ParseError> {
+    XmlDocument::from_reader(&mut EventReader::with_options(input, options)?, options)
 }
 
-#[cfg(test)]
+///Parses an Android binary XML like [`parse`] and also returns
+///[`DocumentInfo`] describing how the document is stored.
+///
+/// # Errors
+///
+/// Will return `ParseError` if `input` cannot be parsed
+///```rust
+///use axmldecoder::parse_with_info;
+///# use axmldecoder::ParseError;
+///let data = include_bytes!("../examples/AndroidManifestUTF8Strings.xml");
+///let (_, info) = parse_with_info(data)?;
+///assert!(info.is_utf8());
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_with_info(input: &[u8]) -> Result<(XmlDocument, DocumentInfo), ParseError> {
+    let options = ParseOptions::default();
+    let mut reader = EventReader::with_options(input, &options)?;
+    let doc = XmlDocument::from_reader(&mut reader, &options)?;
+    Ok((doc, reader.get_info().clone()))
+}
+
+///Returns an iterator over the [`Event`]s of an Android binary XML without
+///building the element tree. This is the same as [`EventReader::new`].
+///
+/// # Errors
+///
+/// Will return `ParseError` if the document header, string pool or resource
+/// map cannot be parsed. Errors in the XML nodes are returned by the
+/// iterator.
+///```rust
+///use axmldecoder::{parse_events, Event};
+///# use axmldecoder::ParseError;
+///let data = include_bytes!("../examples/AndroidManifestUTF8Strings.xml");
+///let mut permissions = Vec::new();
+///for event in parse_events(data)? {
+///    if let Event::StartElement { tag, attributes, .. } = event? {
+///        if tag == "uses-permission" {
+///            permissions.extend(attributes.get("android:name").map(|v| v.to_string()));
+///        }
+///    }
+///}
+///assert_eq!(permissions.len(), 10);
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_events(input: &[u8]) -> Result<EventReader<'_>, ParseError> {
+    EventReader::new(input)
+}
+
+///Returns every string in the string pool of an Android binary XML, in
+///pool order. Only the document header and the chunks up to the string
+///pool are read, so this is faster than [`parse`] when only the strings
+///are needed. The pool may hold strings that the document no longer uses.
+///
+/// # Errors
+///
+/// Will return `ParseError` if the document header or string pool cannot
+/// be parsed, or if the document has no string pool
+///```rust
+///use axmldecoder::extract_strings;
+///# use axmldecoder::ParseError;
+///let data = include_bytes!("../examples/AndroidManifestUTF8Strings.xml");
+///let permissions: Vec<String> = extract_strings(data)?
+///    .into_iter()
+///    .filter(|s| s.starts_with("android.permission."))
+///    .collect();
+///assert!(permissions.contains(&"android.permission.INTERNET".to_string()));
+///# Ok::<(), ParseError>(())
+///```
+pub fn extract_strings(input: &[u8]) -> Result<Vec<String>, ParseError> {
+    let string_pool = read_string_pool(input)?;
+    Ok(string_pool
+        .strings
+        .iter()
+        .map(|s| String::clone(s))
+        .collect())
+}
+
+///Reads an Android binary XML from `reader` and returns a [`XmlDocument`]
+///object.
+///
+///The document is read one chunk at a time, so apart from the document
+///itself only the largest chunk, usually the string pool, is held in
+///memory. Reading stops at the end of the size given in the document
+///header, so the reader can hold more data after the document.
+///
+/// # Errors
+///
+/// Will return [`ParseError::UnexpectedEof`] if `reader` ends before the
+/// size given in the document header, [`ParseError::IoError`] if reading
+/// from `reader` fails, or another `ParseError` if the data read cannot be
+/// parsed
+///```rust
+///use axmldecoder::parse_reader;
+///# use axmldecoder::ParseError;
+///let file = std::fs::File::open("examples/AndroidManifest.xml")?;
+///parse_reader(std::io::BufReader::new(file))?;
+///# Ok::<(), ParseError>(())
+///```
+#[cfg(feature = "std")]
+pub fn parse_reader<R: Read>(reader: R) -> Result<XmlDocument, ParseError> {
+    XmlDocument::from_reader(&mut StreamReader::new(reader)?, &ParseOptions::default())
+}
+
+///Reads the Android binary XML file at `path` and returns a [`XmlDocument`]
+///object.
+///
+/// # Errors
+///
+/// Will return `ParseError` if the file cannot be read or if its contents
+/// cannot be parsed
+///```rust
+///use axmldecoder::parse_file;
+///# use axmldecoder::ParseError;
+///parse_file("examples/AndroidManifest.xml")?;
+///# Ok::<(), ParseError>(())
+///```
+#[cfg(feature = "std")]
+pub fn parse_file(path: impl AsRef<Path>) -> Result<XmlDocument, ParseError> {
+    parse(&std::fs::read(path)?)
+}
+
+///Reads the `AndroidManifest.xml` out of the APK at `path` and returns a
+///[`XmlDocument`] object.
+///
+/// # Errors
+///
+/// Will return `ParseError` if the file cannot be read, if it is not a zip
+/// archive with an `AndroidManifest.xml` entry, or if the manifest cannot be
+/// parsed
+///```rust
+///use axmldecoder::parse_apk;
+///# use axmldecoder::ParseError;
+///let doc = parse_apk("tests/fixtures/apk/Example.apk")?;
+///assert!(doc.find_first_element_by_tag("application").is_some());
+///# Ok::<(), ParseError>(())
+///```
+#[cfg(feature = "zip")]
+pub fn parse_apk(path: impl AsRef<Path>) -> Result<XmlDocument, ParseError> {
+    let file = std::fs::File::open(path)?;
+    parse_apk_reader(std::io::BufReader::new(file))
+}
+
+///Reads the `AndroidManifest.xml` out of the APK read from `reader` and
+///returns a [`XmlDocument`] object.
+///
+/// # Errors
+///
+/// Will return `ParseError` if the data read is not a zip archive with an
+/// `AndroidManifest.xml` entry, or if the manifest cannot be parsed
+///```rust
+///use axmldecoder::parse_apk_reader;
+///# use axmldecoder::ParseError;
+///let data = include_bytes!("../tests/fixtures/apk/Example.apk");
+///parse_apk_reader(std::io::Cursor::new(data))?;
+///# Ok::<(), ParseError>(())
+///```
+#[cfg(feature = "zip")]
+pub fn parse_apk_reader<R: Read + Seek>(reader: R) -> Result<XmlDocument, ParseError> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let manifest = archive.by_name("AndroidManifest.xml")?;
+    parse_reader(manifest)
+}
+
+///Encodes a [`XmlDocument`] into Android's binary XML format.
+///
+///The string pool is rebuilt from the strings found in the document and
+///is always encoded as UTF-16. Attributes in the `android` namespace that
+///are known to this library are assigned their resource ID in the resource
+///map.
+///
+/// # Errors
+///
+/// Will return `EncodeError` if `doc` has no root element, uses a
+/// namespace prefix that is never declared or has an element with more
+/// attributes than binary XML can hold.
+///```rust
+///use axmldecoder::{encode, parse};
+///let data = include_bytes!("../examples/AndroidManifest.xml");
+///let doc = parse(data)?;
+///let encoded = encode(&doc)?;
+///parse(&encoded)?;
+///# Ok::<(), Box<dyn std::error::Error>>(())
+///```
+pub fn encode(doc: &XmlDocument) -> Result<Vec<u8>, EncodeError> {
+    encoder::encode(doc)
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
+    use alloc::format;
+    use alloc::string::ToString;
     use std::fs::File;
-    use std::io::Read;
+    use std::io::BufReader;
     use std::path::PathBuf;
 
     #[test]
@@ -76,10 +569,970 @@ mod tests {
 
         for entry in std::fs::read_dir(examples).unwrap() {
             let entry = entry.unwrap();
-            let mut f = File::open(entry.path()).unwrap();
-            let mut buf = Vec::new();
-            f.read_to_end(&mut buf).unwrap();
-            parse(&buf).unwrap_or_else(|_| panic!("{} failed to parse", entry.path().display()));
+            parse_file(entry.path())
+                .unwrap_or_else(|_| panic!("{} failed to parse", entry.path().display()));
+        }
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let mut examples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        examples.push("examples");
+
+        for entry in std::fs::read_dir(examples).unwrap() {
+            let path = entry.unwrap().path();
+            let doc = parse_file(&path).unwrap();
+            let namespaces = doc.get_namespaces();
+            assert_eq!(
+                namespaces.get("android").map(String::as_str),
+                Some(ANDROID_NAMESPACE),
+                "{}",
+                path.display()
+            );
+
+            // The document-level map is a flattened view of the namespaces
+            // declared on the elements.
+            for e in doc.iter_elements() {
+                for (prefix, _) in e.get_declared_namespaces() {
+                    assert!(namespaces.contains_key(prefix), "{}", path.display());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+
+        // manifest > application > activity > intent-filter > action
+        let options = ParseOptions {
+            max_depth: Some(5),
+            ..ParseOptions::default()
+        };
+        assert_eq!(parse_with_options(data, &options), parse(data));
+
+        let options = ParseOptions {
+            max_depth: Some(4),
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            parse_with_options(data, &options),
+            Err(ParseError::MaxDepthExceeded(4))
+        );
+    }
+
+    #[test]
+    fn test_lenient_document_type() {
+        let mut data = include_bytes!("../examples/AndroidManifest.xml").to_vec();
+        // Replace the XML chunk type of the document header with garbage
+        data[0..2].copy_from_slice(&[0x05, 0x02]);
+
+        assert!(parse(&data).is_err());
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            parse_with_options(&data, &options),
+            parse(include_bytes!("../examples/AndroidManifest.xml"))
+        );
+    }
+
+    #[test]
+    fn test_parse_file() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("examples/AndroidManifest.xml");
+
+        let expected = parse(include_bytes!("../examples/AndroidManifest.xml")).unwrap();
+        assert_eq!(parse_file(&path).unwrap(), expected);
+        assert_eq!(parse_file(path.as_path()).unwrap(), expected);
+        assert_eq!(parse_file(path.to_str().unwrap()).unwrap(), expected);
+
+        path.set_file_name("DoesNotExist.xml");
+        assert!(matches!(
+            parse_file(path),
+            Err(ParseError::IoError(e)) if e.kind() == std::io::ErrorKind::NotFound
+        ));
+    }
+
+    #[test]
+    fn test_parse_with_info() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        let (doc, info) = parse_with_info(data).unwrap();
+        assert_eq!(doc, parse(data).unwrap());
+        assert!(!info.is_utf8());
+        assert_eq!(info.get_string_count(), 21);
+        assert_eq!(info.get_style_count(), 0);
+        assert_eq!(info.get_element_count(), 6);
+        assert_eq!(info.get_cdata_count(), 0);
+        assert_eq!(info.get_string_pool_attributes(), 10);
+        assert_eq!(info.get_resource_map_attributes(), 0);
+        assert_eq!(info.get_declared_size(), data.len());
+        assert_eq!(info.get_input_size(), data.len());
+
+        let data = include_bytes!("../examples/AndroidManifestUTF8Strings.xml");
+        let (_, info) = parse_with_info(data).unwrap();
+        assert!(info.is_utf8());
+
+        // Attribute names that are blanked out in the string pool
+        let data = include_bytes!("../examples/AndroidManifestEmptyAttributeNames.xml");
+        let (_, info) = parse_with_info(data).unwrap();
+        assert_eq!(info.get_resource_map_attributes(), 2);
+
+        // Four bytes of garbage follow the document
+        let data = include_bytes!("../examples/AndroidManifestChunkSlack.xml");
+        let (_, info) = parse_with_info(data).unwrap();
+        assert_eq!(info.get_declared_size() + 4, info.get_input_size());
+    }
+
+    #[test]
+    fn test_parse_reader() {
+        let mut examples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        examples.push("examples");
+
+        for entry in std::fs::read_dir(examples).unwrap() {
+            let path = entry.unwrap().path();
+            let expected = parse(&std::fs::read(&path).unwrap()).unwrap();
+            let reader = BufReader::new(File::open(&path).unwrap());
+            assert_eq!(parse_reader(reader).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_reader_one_byte_at_a_time() {
+        struct ByteReader<'a>(&'a [u8]);
+
+        impl Read for ByteReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                match (self.0.split_first(), buf.first_mut()) {
+                    (Some((byte, rest)), Some(out)) => {
+                        *out = *byte;
+                        self.0 = rest;
+                        Ok(1)
+                    }
+                    _ => Ok(0),
+                }
+            }
+        }
+
+        let mut examples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        examples.push("examples");
+
+        for entry in std::fs::read_dir(examples).unwrap() {
+            let path = entry.unwrap().path();
+            let data = std::fs::read(&path).unwrap();
+            assert_eq!(
+                parse_reader(ByteReader(&data)).unwrap(),
+                parse(&data).unwrap(),
+                "{} did not parse incrementally",
+                path.display()
+            );
+        }
+
+        // Short reads fail instead of waiting for the rest of the document,
+        // including in the end of the namespace after the root element.
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        for len in [0, 4, 8, 100, data.len() - 25, data.len() - 10] {
+            assert_eq!(
+                parse_reader(ByteReader(&data[..len])),
+                Err(ParseError::UnexpectedEof)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_reader_huge_chunk() {
+        // A chunk declaring almost 4 GiB is read as far as the input goes
+        // instead of being allocated up front.
+        let chunk = |typ: u16, header_size: u16, size: u32| {
+            let mut data = typ.to_le_bytes().to_vec();
+            data.extend_from_slice(&header_size.to_le_bytes());
+            data.extend_from_slice(&size.to_le_bytes());
+            data
+        };
+
+        let headerless = chunk(0x0001, 0x1c, 0xffff_fff0);
+        assert_eq!(
+            parse_reader(&headerless[..]),
+            Err(ParseError::UnexpectedEof)
+        );
+
+        let mut data = chunk(0x0003, 8, 0xffff_fff8);
+        data.extend_from_slice(&chunk(0x0001, 0x1c, 0xffff_fff0));
+        assert_eq!(parse_reader(&data[..]), Err(ParseError::UnexpectedEof));
+    }
+
+    #[test]
+    fn test_parse_reader_io_error() {
+        struct FailingReader;
+
+        impl Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+            }
+        }
+
+        assert!(matches!(
+            parse_reader(FailingReader),
+            Err(ParseError::IoError(e)) if e.kind() == std::io::ErrorKind::BrokenPipe
+        ));
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut examples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        examples.push("examples");
+
+        for entry in std::fs::read_dir(examples).unwrap() {
+            let entry = entry.unwrap();
+            let doc = parse_file(entry.path()).unwrap();
+            let cloned = doc.clone();
+            assert_eq!(doc, cloned, "{} did not clone", entry.path().display());
+
+            let root = doc.root();
+            assert_eq!(root.clone(), *root);
+            let root = root.as_element().unwrap();
+            assert_eq!(root.clone(), *root);
+        }
+    }
+
+    #[test]
+    fn test_eq() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        assert_eq!(parse(data).unwrap(), parse(data).unwrap());
+
+        let other = include_bytes!("../examples/AndroidManifestUTF8Strings.xml");
+        assert_ne!(parse(data).unwrap(), parse(other).unwrap());
+    }
+
+    #[test]
+    fn test_parse_error_eq() {
+        assert_eq!(
+            parse(&[0x03, 0x00, 0x08, 0x00, 0x08, 0x00, 0x00, 0x00]).unwrap_err(),
+            parse(&[0x03, 0x00, 0x08, 0x00, 0x08, 0x00, 0x00, 0x00]).unwrap_err(),
+        );
+        assert_ne!(ParseError::StringNotFound(1), ParseError::StringNotFound(2));
+        assert_ne!(
+            ParseError::StringNotFound(1),
+            ParseError::ResourceIdNotFound(1)
+        );
+    }
+
+    #[test]
+    fn test_typed_attributes() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        let xml = parse(data).unwrap();
+
+        let Node::Element(root) = xml.root() else {
+            panic!("missing root element");
+        };
+        let attributes: Vec<(&str, &AttributeValue)> = root.get_typed_attributes().collect();
+        assert_eq!(attributes.len(), root.get_attributes().len());
+        assert!(attributes.contains(&("android:versionCode", &AttributeValue::Int(1))));
+        assert_eq!(
+            root.get_typed_attribute("android:versionCode"),
+            Some(&AttributeValue::Int(1))
+        );
+        assert_eq!(
+            root.get_typed_attribute("package"),
+            Some(&AttributeValue::String(
+                "org.t0t0.androguard.TC".to_string()
+            ))
+        );
+
+        let Some(Node::Element(application)) = root.get_children().first() else {
+            panic!("missing application element");
+        };
+        assert_eq!(
+            application.get_typed_attribute("android:icon"),
+            Some(&AttributeValue::Reference(0x7f02_0000))
+        );
+        assert_eq!(
+            application.get_attribute("android:icon"),
+            Some("@ref/0x7f020000")
+        );
+        assert_eq!(
+            application.get_typed_attribute("android:debuggable"),
+            Some(&AttributeValue::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_element_namespace() {
+        let data = include_bytes!("../examples/AndroidManifestElementNamespace.xml");
+        let xml = parse(data).unwrap();
+
+        let Node::Element(root) = xml.root() else {
+            panic!("missing root element");
+        };
+        assert_eq!(root.get_namespace(), None);
+
+        let Some(Node::Element(application)) = root.get_children().first() else {
+            panic!("missing application element");
+        };
+        let Some(Node::Element(feature)) = application.get_children().first() else {
+            panic!("missing enable-feature element");
+        };
+        assert_eq!(feature.get_tag(), "amazon:enable-feature");
+        assert_eq!(
+            feature.get_namespace(),
+            Some("http://schemas.amazon.com/apk/res/android")
+        );
+        assert_eq!(
+            feature.get_attribute("android:name"),
+            Some("com.amazon.feature")
+        );
+    }
+
+    #[test]
+    fn test_namespace_declarations() {
+        let data = include_bytes!("../examples/AndroidManifestDoubleNamespace.xml");
+        let xml = parse(data).unwrap();
+
+        let android = "http://schemas.android.com/apk/res/android";
+        assert_eq!(
+            xml.get_namespaces().get("android").map(String::as_str),
+            Some(android)
+        );
+        assert_eq!(
+            xml.get_namespaces().get("andorid").map(String::as_str),
+            Some(android)
+        );
+
+        let root = xml.find_first_element_by_tag("manifest").unwrap();
+        assert_eq!(
+            root.get_declared_namespaces(),
+            [("android".to_string(), android.to_string())]
+        );
+
+        let application = xml.find_first_element_by_tag("application").unwrap();
+        assert!(application.get_declared_namespaces().is_empty());
+
+        let activities = application.find_children_by_tag("activity");
+        let declared: Vec<_> = activities
+            .iter()
+            .flat_map(|e| e.get_declared_namespaces())
+            .collect();
+        assert_eq!(declared, [&("andorid".to_string(), android.to_string())]);
+    }
+
+    #[test]
+    fn test_empty_attribute_names() {
+        let data = include_bytes!("../examples/AndroidManifestEmptyAttributeNames.xml");
+        let xml = parse(data).unwrap();
+
+        let permission = xml.find_first_element_by_tag("uses-permission").unwrap();
+        assert_eq!(
+            permission.get_attribute("android:name"),
+            Some("android.permission.INTERNET")
+        );
+
+        let application = xml.find_first_element_by_tag("application").unwrap();
+        assert_eq!(
+            application.get_attribute("android:debuggable"),
+            Some("true")
+        );
+    }
+
+    #[test]
+    fn test_unknown_attribute_ids() {
+        // Two attributes of the manifest have empty names and resource IDs
+        // that are newer than the known attributes.
+        let data = include_bytes!("../examples/AndroidManifestUnknownAttributeIds.xml");
+        let (xml, info) = parse_with_info(data).unwrap();
+        assert_eq!(info.get_resource_map_attributes(), 2);
+
+        let manifest = xml.root().as_element().unwrap();
+        let keys: Vec<&str> = manifest.get_attributes().map(|(key, _)| key).collect();
+        assert_eq!(
+            keys,
+            [
+                "xmlns:android",
+                "android:versionCode",
+                "package",
+                "unknown-attr-0x01010572",
+                "unknown-attr-0x01010573"
+            ]
+        );
+        assert_eq!(
+            manifest.get_attribute("unknown-attr-0x01010572"),
+            Some("30")
+        );
+        assert_eq!(
+            manifest.get_attribute("unknown-attr-0x01010573"),
+            Some("11")
+        );
+        assert_eq!(
+            manifest.get_attribute_resource_id("unknown-attr-0x01010573"),
+            Some(0x0101_0573)
+        );
+    }
+
+    #[test]
+    fn test_fractions() {
+        let data = include_bytes!("../examples/AndroidManifestFractions.xml");
+        let xml = parse(data).unwrap();
+
+        let layout = xml.find_first_element_by_tag("layout").unwrap();
+        assert_eq!(
+            layout.get_typed_attribute("android:defaultWidth"),
+            Some(&AttributeValue::Fraction {
+                value: 0.5,
+                typ: FractionType::PercentParent
+            })
+        );
+        assert_eq!(layout.get_attribute("android:defaultWidth"), Some("50%p"));
+        assert_eq!(
+            layout.get_typed_attribute("android:defaultHeight"),
+            Some(&AttributeValue::Fraction {
+                value: 0.25,
+                typ: FractionType::Percent
+            })
+        );
+        assert_eq!(layout.get_attribute("android:defaultHeight"), Some("25%"));
+    }
+
+    #[test]
+    fn test_missing_resource_map() {
+        let data = include_bytes!("../examples/AndroidManifestNoResourceMap.xml");
+        let xml = parse(data).unwrap();
+
+        let application = xml.find_first_element_by_tag("application").unwrap();
+        assert_eq!(application.get_attribute("android:label"), Some("My App"));
+        assert!(xml.get_warnings().is_empty());
+
+        // The resource map is only consulted for attribute names that are
+        // missing or mangled in the string pool.
+        let mut reader = EventReader::new(data).unwrap();
+        assert!(reader.all(|event| event.is_ok()));
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        let events: Vec<Event> = parse_events(data)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let starts = events
+            .iter()
+            .filter(|event| matches!(event, Event::StartElement { .. }))
+            .count();
+        let ends = events
+            .iter()
+            .filter(|event| matches!(event, Event::EndElement { .. }))
+            .count();
+        assert_eq!((starts, ends), (6, 6));
+        assert!(parse_events(&data[..4]).is_err());
+    }
+
+    #[test]
+    fn test_padded_attributes() {
+        let data = include_bytes!("../examples/AndroidManifestPaddedAttributes.xml");
+        let xml = parse(data).unwrap();
+
+        let manifest = xml.root().as_element().unwrap();
+        assert_eq!(
+            manifest.get_attribute("package"),
+            Some("com.example.padded")
+        );
+        let activity = xml.find_first_element_by_tag("activity").unwrap();
+        assert_eq!(
+            activity.get_attribute("android:name"),
+            Some(".MainActivity")
+        );
+        assert_eq!(
+            activity.get_typed_attribute("android:label"),
+            Some(&AttributeValue::Reference(0x7f01_0000))
+        );
+
+        // The encoder writes the attributes without padding
+        let unpadded = encode(&xml).unwrap();
+        assert!(unpadded.len() < data.len());
+        assert_eq!(parse(&unpadded).unwrap(), xml);
+    }
+
+    #[test]
+    fn test_special_attributes() {
+        let data = include_bytes!("../examples/LayoutSpecialAttributes.xml");
+        let xml = parse(data).unwrap();
+
+        let view = xml.root().as_element().unwrap();
+        assert_eq!(
+            view.get_style_attribute(),
+            Some(("style", "@ref/0x7f0c0001"))
+        );
+        let fragment = view.find_child_by_tag("fragment").unwrap();
+        assert_eq!(
+            fragment.get_id_attribute(),
+            Some(("android:id", "@ref/0x7f080002"))
+        );
+        assert_eq!(
+            fragment.get_class_attribute(),
+            Some(("class", "com.example.ListFragment"))
+        );
+        assert_eq!(fragment.get_style_attribute(), None);
+
+        let decoded = parse(&encode(&xml).unwrap()).unwrap();
+        assert_eq!(decoded, xml);
+
+        let mut view = view.clone();
+        view.remove_attribute("class");
+        assert_eq!(view.get_class_attribute(), None);
+        assert_eq!(Element::new("view").get_id_attribute(), None);
+    }
+
+    #[test]
+    fn test_raw_values() {
+        let data = include_bytes!("../examples/AndroidManifestRawValues.xml");
+        let xml = parse(data).unwrap();
+        let manifest = xml.root().as_element().unwrap();
+
+        assert_eq!(
+            manifest.get_typed_attribute("platformBuildVersionName"),
+            Some(&AttributeValue::Null)
+        );
+        assert_eq!(
+            manifest.get_attribute("platformBuildVersionName"),
+            Some("14")
+        );
+        assert_eq!(manifest.get_raw_attribute("package"), None);
+        assert_eq!(manifest.get_raw_attribute("android:versionCode"), None);
+
+        let decoded = parse(&encode(&xml).unwrap()).unwrap();
+        assert_eq!(decoded, xml);
+
+        let mut manifest = manifest.clone();
+        manifest.set_attribute(
+            "android:versionName",
+            AttributeValue::Reference(0x7f0b_0002),
+        );
+        assert_eq!(manifest.get_raw_attribute("android:versionName"), None);
+        assert_eq!(
+            manifest.get_attribute("android:versionName"),
+            Some("@ref/0x7f0b0002")
+        );
+    }
+
+    #[test]
+    fn test_prefer_raw_value() {
+        // The typed values of package and android:name point at different
+        // strings than their raw values.
+        let data = include_bytes!("../examples/AndroidManifestRawValueMismatch.xml");
+        let xml = parse(data).unwrap();
+        let manifest = xml.root().as_element().unwrap();
+        let application = manifest.find_child_by_tag("application").unwrap();
+        assert_eq!(manifest.get_attribute("package"), Some("com.example.decoy"));
+        assert_eq!(application.get_attribute("android:name"), Some(".Decoy"));
+
+        let options = ParseOptions {
+            prefer_raw_value: true,
+            ..ParseOptions::default()
+        };
+        let xml = parse_with_options(data, &options).unwrap();
+        let manifest = xml.root().as_element().unwrap();
+        let application = manifest.find_child_by_tag("application").unwrap();
+        assert_eq!(manifest.get_attribute("package"), Some("com.example.real"));
+        assert_eq!(
+            application.get_typed_attribute("android:name"),
+            Some(&AttributeValue::String(".App".to_string()))
+        );
+        assert_eq!(application.get_attribute("android:label"), Some("Example"));
+
+        // Only string values are affected
+        let data = include_bytes!("../examples/AndroidManifestRawValues.xml");
+        assert_eq!(
+            parse_with_options(data, &options).unwrap(),
+            parse(data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_dynamic_references() {
+        let data = include_bytes!("../examples/AndroidManifestDynamicReferences.xml");
+        let xml = parse(data).unwrap();
+        let application = xml.find_first_element_by_tag("application").unwrap();
+
+        assert_eq!(
+            application.get_typed_attribute("android:theme"),
+            Some(&AttributeValue::DynamicReference(0x0003_0005))
+        );
+        assert_eq!(
+            application.get_attribute("android:theme"),
+            Some("@dyn/0x00030005")
+        );
+        assert_eq!(
+            application.get_attribute("android:label"),
+            Some("?dyn/0x00010002")
+        );
+
+        let decoded = parse(&encode(&xml).unwrap()).unwrap();
+        assert_eq!(decoded, xml);
+    }
+
+    #[test]
+    fn test_unknown_value_types() {
+        let data = include_bytes!("../examples/AndroidManifestUnknownValueTypes.xml");
+        let xml = parse(data).unwrap();
+        let application = xml.find_first_element_by_tag("application").unwrap();
+
+        assert_eq!(
+            application.get_typed_attribute("android:label"),
+            Some(&AttributeValue::Unknown {
+                data_type: 0x20,
+                data: 1234
+            })
+        );
+        assert_eq!(
+            application.get_attribute("android:label"),
+            Some("type0x20/1234")
+        );
+        assert_eq!(
+            application.get_attribute("android:icon"),
+            Some("type0xff/2131427329")
+        );
+
+        let decoded = parse(&encode(&xml).unwrap()).unwrap();
+        assert_eq!(decoded, xml);
+    }
+
+    #[test]
+    fn test_no_document_header() {
+        // The chunks of examples/AndroidManifest.xml without the XML chunk
+        // header around them
+        let data = include_bytes!("../examples/AndroidManifestNoDocumentHeader.xml");
+        let expected = parse(include_bytes!("../examples/AndroidManifest.xml")).unwrap();
+        assert_eq!(parse(data).unwrap(), expected);
+        assert_eq!(parse_reader(&data[..]).unwrap(), expected);
+
+        let reader = EventReader::new(data).unwrap();
+        assert_eq!(reader.get_info().get_declared_size(), data.len());
+    }
+
+    #[test]
+    fn test_undeclared_namespace() {
+        // The tools namespace is used without a namespace chunk.
+        let data = include_bytes!("../examples/AndroidManifestUndeclaredNamespace.xml");
+        let xml = parse(data).unwrap();
+
+        let tools = "http://schemas.android.com/tools";
+        let application = xml.find_first_element_by_tag("application").unwrap();
+        assert_eq!(
+            application.get_attribute("tools:replace"),
+            Some("android:label")
+        );
+        assert_eq!(
+            application.get_attribute_ns(tools, "replace"),
+            Some("android:label")
+        );
+        assert_eq!(
+            application.get_declared_namespaces(),
+            [("tools".to_string(), tools.to_string())]
+        );
+        let activity = xml.find_first_element_by_tag("activity").unwrap();
+        assert_eq!(activity.get_attribute("tools:ignore"), Some("MissingClass"));
+
+        // The reader closes the declaration after the element.
+        let events: Vec<Event> = EventReader::new(data)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let declaration = |event: &Event| match event {
+            Event::StartNamespace { prefix, .. } if prefix == "tools" => Some(true),
+            Event::EndNamespace { prefix, .. } if prefix == "tools" => Some(false),
+            _ => None,
+        };
+        let start = events.iter().position(|e| declaration(e) == Some(true));
+        let end = events.iter().position(|e| declaration(e) == Some(false));
+        assert!(matches!(
+            &events[start.unwrap() + 1],
+            Event::StartElement { tag, .. } if tag == "application"
+        ));
+        assert!(matches!(
+            &events[end.unwrap() - 1],
+            Event::EndElement { tag } if tag == "application"
+        ));
+
+        // There are samples that use the raw prefix as the namespace.
+        let data = include_bytes!("../examples/AndroidManifestUnknownNamespace.xml");
+        let xml = parse(data).unwrap();
+        let manifest = xml.root().as_element().unwrap();
+        assert_eq!(manifest.get_attribute("android:installLocation"), Some("2"));
+        assert!(!xml.get_namespaces().values().any(|uri| uri == "android"));
+    }
+
+    #[test]
+    fn test_scoped_namespaces() {
+        // The res-auto namespace is declared as app: on the root and again
+        // as custom: on the TextView. The tools namespace is only declared
+        // on the TextView.
+        let data = include_bytes!("../examples/LayoutScopedNamespaces.xml");
+        let xml = parse(data).unwrap();
+
+        let res_auto = "http://schemas.android.com/apk/res-auto";
+        let text_view = xml.find_first_element_by_tag("TextView").unwrap();
+        assert_eq!(
+            text_view.get_attribute("custom:layout_behavior"),
+            Some(".Behavior")
+        );
+        assert_eq!(
+            text_view.get_attribute("tools:ignore"),
+            Some("HardcodedText")
+        );
+
+        // Once the TextView is closed, the root declarations apply again.
+        let button = xml.find_first_element_by_tag("Button").unwrap();
+        assert_eq!(
+            button.get_attribute("app:layout_behavior"),
+            Some(".Behavior")
+        );
+        assert_eq!(
+            button.get_attribute_ns(res_auto, "layout_behavior"),
+            Some(".Behavior")
+        );
+        assert_eq!(
+            button.get_declared_namespaces(),
+            [(
+                "tools".to_string(),
+                "http://schemas.android.com/tools".to_string()
+            )]
+        );
+        assert_eq!(button.get_attribute("tools:ignore"), Some("HardcodedText"));
+    }
+
+    #[test]
+    fn test_nested_namespaces() {
+        // The app prefix is bound to res-auto on the root and to a library
+        // namespace on the View. The View also closes its x: and y:
+        // declarations out of order.
+        let data = include_bytes!("../examples/LayoutNestedNamespaces.xml");
+        let xml = parse(data).unwrap();
+
+        let res_auto = "http://schemas.android.com/apk/res-auto";
+        let lib = "http://schemas.android.com/apk/lib/com.example";
+        let view = xml.find_first_element_by_tag("View").unwrap();
+        assert_eq!(view.get_attribute("app:size"), Some("10"));
+        assert_eq!(view.get_attribute_ns(lib, "size"), Some("10"));
+        // res-auto cannot use the app prefix while it is rebound.
+        assert_eq!(view.get_attribute("res-auto:color"), Some("red"));
+        assert_eq!(view.get_attribute_ns(res_auto, "color"), Some("red"));
+        assert_eq!(view.get_attribute("x:text"), Some("a"));
+        assert_eq!(view.get_attribute("y:text"), Some("b"));
+
+        let text_view = xml.find_first_element_by_tag("TextView").unwrap();
+        assert_eq!(text_view.get_attribute("app:color"), Some("red"));
+        assert_eq!(text_view.get_attribute_ns(res_auto, "color"), Some("red"));
+        assert_eq!(text_view.get_attribute_ns(lib, "size"), Some("10"));
+        assert_eq!(text_view.get_attribute("app:size"), None);
+
+        // The root keeps its own binding of the prefix when printed.
+        let printed = xml.to_xml_string();
+        assert!(printed.contains(&format!(
+            "<FrameLayout xmlns:android=\"{}\" xmlns:app=\"{}\"",
+            ANDROID_NAMESPACE, res_auto
+        )));
+    }
+
+    #[test]
+    fn test_chunk_padding() {
+        // Three NUL bytes follow the string pool without being counted in
+        // its size.
+        let data = include_bytes!("../examples/AndroidManifestChunkPadding.xml");
+        let xml = parse(data).unwrap();
+        assert!(xml.get_warnings().is_empty());
+
+        let application = xml.find_first_element_by_tag("application").unwrap();
+        assert_eq!(application.get_attribute("android:label"), Some("Padded"));
+
+        // Counting the padding in the string pool gives the same document
+        let mut aligned = data.to_vec();
+        let size = u32::from_le_bytes([data[12], data[13], data[14], data[15]]);
+        aligned[12..16].copy_from_slice(&(size + 3).to_le_bytes());
+        assert_eq!(parse(&aligned).unwrap(), xml);
+    }
+
+    #[test]
+    fn test_reordered_chunks() {
+        // The resource map follows the first namespace chunk and an unknown
+        // chunk type sits between two elements.
+        let data = include_bytes!("../examples/AndroidManifestReorderedChunks.xml");
+        let xml = parse(data).unwrap();
+
+        let activity = xml.find_first_element_by_tag("activity").unwrap();
+        assert_eq!(
+            activity.get_attribute("android:name"),
+            Some(".MainActivity")
+        );
+
+        assert_eq!(
+            xml.get_warnings(),
+            ["Skipped unknown chunk of type 0x7777 (16 bytes)"]
+        );
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        let lenient = parse_with_options(data, &options).unwrap();
+        assert_eq!(lenient, xml);
+
+        let expected = parse(include_bytes!(
+            "../examples/AndroidManifestNoResourceMap.xml"
+        ))
+        .unwrap();
+        assert!(expected.get_warnings().is_empty());
+        assert_eq!(xml.to_xml_string(), expected.to_xml_string());
+        assert_eq!(
+            activity.get_attribute_resource_id("android:name"),
+            Some(0x0101_0003)
+        );
+    }
+
+    #[test]
+    fn test_line_numbers_and_comments() {
+        let data = include_bytes!("../examples/AndroidManifestWithComment.xml");
+        let xml = parse(data).unwrap();
+
+        let root = xml.root().as_element().unwrap();
+        assert_eq!(root.get_line_number(), 2);
+        assert_eq!(root.get_comment(), None);
+
+        let application = root.find_child_by_tag("application").unwrap();
+        assert_eq!(application.get_line_number(), 20);
+
+        let meta_data = application.find_child_by_tag("meta-data").unwrap();
+        assert_eq!(meta_data.get_comment(), Some("installLocation"));
+
+        let activity = application.find_child_by_tag("activity").unwrap();
+        assert_eq!(activity.get_line_number(), 21);
+    }
+
+    #[test]
+    fn test_styled_strings() {
+        let data = include_bytes!("../examples/AndroidManifestStyledStrings.xml");
+        let xml = parse(data).unwrap();
+
+        let Node::Element(root) = xml.root() else {
+            panic!("missing root element");
+        };
+        let Some(Node::Element(application)) = root.get_children().first() else {
+            panic!("missing application element");
+        };
+        assert_eq!(
+            application.get_attribute("android:label"),
+            Some("Hello World")
+        );
+    }
+
+    #[test]
+    fn test_long_utf8_strings() {
+        let data = include_bytes!("../examples/AndroidManifestLongUTF8Strings.xml");
+        let xml = parse(data).unwrap();
+
+        let Node::Element(root) = xml.root() else {
+            panic!("missing root element");
+        };
+        assert_eq!(root.get_attribute("package"), Some("com.example.utf8"));
+
+        let Some(Node::Element(application)) = root.get_children().first() else {
+            panic!("missing application element");
+        };
+        assert_eq!(
+            application.get_attribute("android:label"),
+            Some("应用".repeat(50).as_str())
+        );
+
+        let Some(Node::Element(activity)) = application.get_children().first() else {
+            panic!("missing activity element");
+        };
+        assert_eq!(
+            activity.get_attribute("android:taskAffinity"),
+            Some(format!("com.example.affinity.{}", "task".repeat(40)).as_str())
+        );
+    }
+
+    // Offset of the first XML node chunk, i.e. past the string pool and
+    // resource map chunks.
+    fn nodes_start(data: &[u8]) -> usize {
+        let chunk_size = |offset: usize| {
+            usize::try_from(u32::from_le_bytes(
+                data[offset + 4..offset + 8].try_into().unwrap(),
+            ))
+            .unwrap()
+        };
+
+        let resource_map_start = 8 + chunk_size(8);
+        resource_map_start + chunk_size(resource_map_start)
+    }
+
+    #[test]
+    fn test_truncated_nodes() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+
+        for len in nodes_start(data)..data.len() {
+            assert!(parse(&data[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_corrupted_nodes() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        let start = nodes_start(data);
+
+        // Simple LCG so that the corruptions are reproducible
+        let mut seed: u32 = 0x2545_f491;
+        let mut next = || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            usize::try_from(seed >> 8).unwrap()
+        };
+
+        for _ in 0..2000 {
+            let mut corrupted = data.to_vec();
+            for _ in 0..4 {
+                let i = start + next() % (data.len() - start);
+                corrupted[i] = u8::try_from(next() % 256).unwrap();
+            }
+            let _ = parse(&corrupted);
+        }
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        let mut examples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        examples.push("examples");
+
+        for entry in std::fs::read_dir(examples).unwrap() {
+            let entry = entry.unwrap();
+            let doc = parse_file(entry.path()).unwrap();
+            let encoded = encode(&doc)
+                .unwrap_or_else(|e| panic!("{} failed to encode: {}", entry.path().display(), e));
+            let decoded = parse(&encoded)
+                .unwrap_or_else(|e| panic!("{} failed to parse: {}", entry.path().display(), e));
+
+            // Skipped chunks are not encoded, so only the content of the
+            // documents is compared.
+            let path = entry.path();
+            assert_eq!(
+                doc.root(),
+                decoded.root(),
+                "{} did not roundtrip",
+                path.display()
+            );
+            assert_eq!(doc.get_namespaces(), decoded.get_namespaces());
+            assert!(decoded.get_warnings().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_encode_too_many_attributes() {
+        let mut root = Element::new("manifest");
+        for i in 0..=u16::MAX {
+            root.set_attribute(format!("a{}", i), "");
         }
+        assert!(matches!(
+            encode(&XmlDocument::new(root)),
+            Err(EncodeError::TooManyAttributes(65536))
+        ));
     }
 }
@@ -0,0 +1,260 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::{parse, Element, ParseError, XmlDocument};
+
+///Summary of the commonly used information in an `AndroidManifest.xml`.
+///
+///Attributes are often missing or mangled in obfuscated manifests, so every
+///value that cannot be found or understood is left empty instead of
+///returning an error.
+///```rust
+///use axmldecoder::{parse, Manifest};
+///# use axmldecoder::ParseError;
+///let data = include_bytes!("../examples/AndroidManifest.xml");
+///let manifest = Manifest::from_document(&parse(data)?).unwrap();
+///assert_eq!(manifest.get_package(), Some("org.t0t0.androguard.TC"));
+///assert_eq!(manifest.get_version_code(), Some(1));
+///# Ok::<(), ParseError>(())
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    package: Option<String>,
+    version_code: Option<i64>,
+    version_name: Option<String>,
+    min_sdk: Option<u32>,
+    target_sdk: Option<u32>,
+    permissions: Vec<String>,
+    activities: Vec<Component>,
+    services: Vec<Component>,
+    receivers: Vec<Component>,
+    providers: Vec<Component>,
+}
+
+impl Manifest {
+    ///Extracts the manifest information from a parsed document. Returns
+    ///`None` if the root element is not `<manifest>`.
+    #[must_use]
+    pub fn from_document(doc: &XmlDocument) -> Option<Self> {
+        let root = doc.root().as_element()?;
+        if root.get_tag() != "manifest" {
+            return None;
+        }
+
+        let uses_sdk = root.find_child_by_tag("uses-sdk");
+        let application = root.find_child_by_tag("application");
+        let components = |tag: &str| {
+            application.map_or_else(Vec::new, |application| {
+                application
+                    .find_children_by_tag(tag)
+                    .into_iter()
+                    .map(Component::from_element)
+                    .collect()
+            })
+        };
+
+        Some(Self {
+            package: root.get_attribute("package").map(str::to_string),
+            version_code: version_code(root),
+            version_name: root
+                .get_attribute("android:versionName")
+                .map(str::to_string),
+            min_sdk: uses_sdk.and_then(|e| parse_attribute(e, "android:minSdkVersion")),
+            target_sdk: uses_sdk.and_then(|e| parse_attribute(e, "android:targetSdkVersion")),
+            permissions: ["uses-permission", "uses-permission-sdk-23"]
+                .iter()
+                .flat_map(|tag| root.find_children_by_tag(tag))
+                .filter_map(|e| e.get_attribute("android:name"))
+                .map(str::to_string)
+                .collect(),
+            activities: components("activity"),
+            services: components("service"),
+            receivers: components("receiver"),
+            providers: components("provider"),
+        })
+    }
+
+    ///Parses a binary XML document and extracts the manifest information
+    ///from it.
+    ///```rust
+    ///use axmldecoder::Manifest;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let manifest = Manifest::from_bytes(data)?;
+    ///assert_eq!(manifest.get_target_sdk(), None);
+    ///# Ok::<(), ParseError>(())
+    ///```
+    ///
+    /// # Errors
+    ///
+    /// Will return `ParseError` if the document cannot be parsed, or
+    /// [`ParseError::NotManifest`] if its root element is not `<manifest>`.
+    pub fn from_bytes(input: &[u8]) -> Result<Self, ParseError> {
+        let doc = parse(input)?;
+        Self::from_document(&doc).ok_or_else(|| {
+            let tag = doc.root().as_element().map_or("", Element::get_tag);
+            ParseError::NotManifest(tag.to_string())
+        })
+    }
+
+    ///Returns the package name.
+    #[must_use]
+    pub fn get_package(&self) -> Option<&str> {
+        self.package.as_deref()
+    }
+
+    ///Returns the version code. As in Android's `getLongVersionCode`,
+    ///`android:versionCodeMajor` makes up the upper 32 bits and the signed
+    ///32-bit `android:versionCode` the lower 32 bits. Returns `None` if
+    ///neither is set.
+    #[must_use]
+    pub fn get_version_code(&self) -> Option<i64> {
+        self.version_code
+    }
+
+    ///Returns `android:versionName`.
+    #[must_use]
+    pub fn get_version_name(&self) -> Option<&str> {
+        self.version_name.as_deref()
+    }
+
+    ///Returns `android:minSdkVersion` from `<uses-sdk>`. Preview SDK
+    ///codenames are not numeric and are returned as `None`.
+    #[must_use]
+    pub fn get_min_sdk(&self) -> Option<u32> {
+        self.min_sdk
+    }
+
+    ///Returns `android:targetSdkVersion` from `<uses-sdk>`.
+    #[must_use]
+    pub fn get_target_sdk(&self) -> Option<u32> {
+        self.target_sdk
+    }
+
+    ///Returns the permissions requested by `<uses-permission>` and
+    ///`<uses-permission-sdk-23>`.
+    #[must_use]
+    pub fn get_permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    ///Returns the `<activity>` elements of the application.
+    #[must_use]
+    pub fn get_activities(&self) -> &[Component] {
+        &self.activities
+    }
+
+    ///Returns the `<service>` elements of the application.
+    #[must_use]
+    pub fn get_services(&self) -> &[Component] {
+        &self.services
+    }
+
+    ///Returns the `<receiver>` elements of the application.
+    #[must_use]
+    pub fn get_receivers(&self) -> &[Component] {
+        &self.receivers
+    }
+
+    ///Returns the `<provider>` elements of the application.
+    #[must_use]
+    pub fn get_providers(&self) -> &[Component] {
+        &self.providers
+    }
+}
+
+///An application component, such as an activity or service, declared in
+///the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    name: Option<String>,
+    exported: Option<bool>,
+    intent_filters: Vec<IntentFilter>,
+}
+
+impl Component {
+    fn from_element(e: &Element) -> Self {
+        Self {
+            name: e.get_attribute("android:name").map(str::to_string),
+            exported: parse_attribute(e, "android:exported"),
+            intent_filters: e
+                .find_children_by_tag("intent-filter")
+                .into_iter()
+                .map(IntentFilter::from_element)
+                .collect(),
+        }
+    }
+
+    ///Returns `android:name`.
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    ///Returns `android:exported`. If it is not set, Android decides based
+    ///on whether the component has any intent filters.
+    #[must_use]
+    pub fn get_exported(&self) -> Option<bool> {
+        self.exported
+    }
+
+    ///Returns the `<intent-filter>` elements of the component.
+    #[must_use]
+    pub fn get_intent_filters(&self) -> &[IntentFilter] {
+        &self.intent_filters
+    }
+}
+
+///An `<intent-filter>` declared on a [`Component`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntentFilter {
+    actions: Vec<String>,
+    categories: Vec<String>,
+}
+
+impl IntentFilter {
+    fn from_element(e: &Element) -> Self {
+        let names = |tag: &str| {
+            e.find_children_by_tag(tag)
+                .into_iter()
+                .filter_map(|e| e.get_attribute("android:name"))
+                .map(str::to_string)
+                .collect()
+        };
+
+        Self {
+            actions: names("action"),
+            categories: names("category"),
+        }
+    }
+
+    ///Returns the names of the `<action>` elements.
+    #[must_use]
+    pub fn get_actions(&self) -> &[String] {
+        &self.actions
+    }
+
+    ///Returns the names of the `<category>` elements.
+    #[must_use]
+    pub fn get_categories(&self) -> &[String] {
+        &self.categories
+    }
+}
+
+// Combines `android:versionCodeMajor` and `android:versionCode` the same way
+// as `PackageInfo::getLongVersionCode`.
+fn version_code(root: &Element) -> Option<i64> {
+    let code = root.get_attribute_as_int("android:versionCode");
+    let major = root.get_attribute_as_int("android:versionCodeMajor");
+    if code.is_none() && major.is_none() {
+        return None;
+    }
+    let low = i64::from(code.unwrap_or_default() as u32);
+    Some(i64::from(major.unwrap_or_default()) << 32 | low)
+}
+
+// Values that fail to parse are treated as missing, e.g. a resource
+// reference where a number is expected.
+fn parse_attribute<T: core::str::FromStr>(e: &Element, name: &str) -> Option<T> {
+    e.get_attribute(name)?.parse().ok()
+}
@@ -0,0 +1,153 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use crate::{Cdata, Element, Node, XmlDocument};
+
+// Number of spaces per level used by the `Display` output.
+pub(crate) const INDENT: usize = 2;
+
+// Writes `doc` as text XML. If `indent` is set, elements are placed on their
+// own lines and indented by that many spaces per level.
+pub(crate) fn write_document(
+    doc: &XmlDocument,
+    indent: Option<usize>,
+    f: &mut impl Write,
+) -> fmt::Result {
+    f.write_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>")?;
+    if indent.is_some() {
+        f.write_char('\n')?;
+    }
+
+    let printer = Printer { doc, indent };
+    printer.write_node(doc.root(), 0, true, f)
+}
+
+struct Printer<'a> {
+    doc: &'a XmlDocument,
+    indent: Option<usize>,
+}
+
+impl Printer<'_> {
+    fn write_node(
+        &self,
+        node: &Node,
+        level: usize,
+        is_root: bool,
+        f: &mut impl Write,
+    ) -> fmt::Result {
+        match node {
+            Node::Element(e) => self.write_element(e, level, is_root, f),
+            Node::Cdata(c) => {
+                self.write_indent(level, f)?;
+                write_cdata(c, f)?;
+                self.write_newline(f)
+            }
+        }
+    }
+
+    fn write_element(
+        &self,
+        e: &Element,
+        level: usize,
+        is_root: bool,
+        f: &mut impl Write,
+    ) -> fmt::Result {
+        self.write_indent(level, f)?;
+        write!(f, "<{}", e.get_tag())?;
+
+        // Declare every namespace on the root element so that prefixes used
+        // before their declaration in the binary XML still resolve. The
+        // root's own declarations take precedence, as a prefix may be bound
+        // to another URI further down the document.
+        let mut namespaces: Vec<(&String, &String)> = e
+            .get_declared_namespaces()
+            .iter()
+            .map(|(prefix, uri)| (prefix, uri))
+            .collect();
+        if is_root {
+            for (prefix, uri) in self.doc.get_namespaces() {
+                if !namespaces.iter().any(|(p, _)| *p == prefix) {
+                    namespaces.push((prefix, uri));
+                }
+            }
+        }
+
+        for (prefix, uri) in namespaces {
+            let key = if prefix.is_empty() {
+                String::from("xmlns")
+            } else {
+                format!("xmlns:{}", prefix)
+            };
+            if e.get_attribute(&key).is_none() {
+                write_attribute(&key, uri, f)?;
+            }
+        }
+
+        for (key, value) in e.get_attributes() {
+            write_attribute(key, value, f)?;
+        }
+
+        if e.get_children().is_empty() {
+            f.write_str("/>")?;
+            return self.write_newline(f);
+        }
+
+        f.write_char('>')?;
+        self.write_newline(f)?;
+
+        for child in e.get_children() {
+            self.write_node(child, level + 1, false, f)?;
+        }
+
+        self.write_indent(level, f)?;
+        write!(f, "</{}>", e.get_tag())?;
+        self.write_newline(f)
+    }
+
+    fn write_indent(&self, level: usize, f: &mut impl Write) -> fmt::Result {
+        if let Some(indent) = self.indent {
+            write!(f, "{:indent$}", "", indent = level * indent)?;
+        }
+        Ok(())
+    }
+
+    fn write_newline(&self, f: &mut impl Write) -> fmt::Result {
+        if self.indent.is_some() {
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+fn write_attribute(key: &str, value: &str, f: &mut impl Write) -> fmt::Result {
+    write!(f, " {}=\"", key)?;
+    for c in value.chars() {
+        match c {
+            '&' => f.write_str("&amp;")?,
+            '<' => f.write_str("&lt;")?,
+            '>' => f.write_str("&gt;")?,
+            '"' => f.write_str("&quot;")?,
+            '\t' => f.write_str("&#9;")?,
+            '\n' => f.write_str("&#10;")?,
+            '\r' => f.write_str("&#13;")?,
+            c if is_xml_char(c) => f.write_char(c)?,
+            _ => {}
+        }
+    }
+    f.write_char('"')
+}
+
+fn write_cdata(c: &Cdata, f: &mut impl Write) -> fmt::Result {
+    // "]]>" cannot appear inside a CDATA section, so it is split across two
+    // sections.
+    let data: String = c.get_data().chars().filter(|c| is_xml_char(*c)).collect();
+    write!(f, "<![CDATA[{}]]>", data.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+// Control characters other than whitespace cannot be represented in XML 1.0,
+// even as character references, so they are left out of the output.
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r') || (c >= ' ' && !matches!(c, '\u{fffe}' | '\u{ffff}'))
+}
@@ -0,0 +1,338 @@
+//!Low-level access to the chunks of a binary XML document.
+//!
+//!Packers commonly hide data in oversized chunks or in the unused space of
+//!the string pool, none of which is visible in the parsed document. The
+//!functions in this module report the layout of the chunks as found in the
+//!file instead.
+//!```rust
+//!use axmldecoder::raw::parse_chunks;
+//!# use axmldecoder::ParseError;
+//!let data = include_bytes!("../examples/AndroidManifest.xml");
+//!for chunk in parse_chunks(data)? {
+//!    let unused = chunk.get_content_size().map(|used| chunk.get_size() - used);
+//!    println!(
+//!        "{:#08x} {:<32} {:>6} bytes, unused: {:?}",
+//!        chunk.get_offset(),
+//!        chunk.get_type_name().unwrap_or("unknown"),
+//!        chunk.get_size(),
+//!        unused
+//!    );
+//!}
+//!# Ok::<(), ParseError>(())
+//!```
+
+use alloc::vec::Vec;
+use deku::prelude::*;
+
+use crate::binaryxml::{self, RawChunk, ResourceType, XmlAttrExt};
+use crate::stringpool::{self, StringPoolHeader};
+use crate::ParseError;
+
+///Layout information about a single chunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkInfo {
+    offset: usize,
+    typ: u16,
+    resource_type: Option<ResourceType>,
+    header_size: u16,
+    size: u32,
+    content_size: Option<u32>,
+    string_pool: Option<StringPoolInfo>,
+}
+
+impl ChunkInfo {
+    fn new(offset: usize, chunk: &RawChunk, content_size: Option<usize>) -> Self {
+        let string_pool = match chunk.resource_type() {
+            Some(ResourceType::StringPool) => StringPoolHeader::from_bytes((chunk.data, 0))
+                .ok()
+                .map(|(_, header)| StringPoolInfo::new(&header)),
+            _ => None,
+        };
+
+        Self {
+            offset,
+            typ: chunk.typ,
+            resource_type: chunk.resource_type(),
+            header_size: chunk.header_size,
+            size: u32::try_from(chunk.data.len()).unwrap(),
+            content_size: content_size.and_then(|size| u32::try_from(size).ok()),
+            string_pool,
+        }
+    }
+
+    ///Returns the offset of the chunk from the start of the input.
+    #[must_use]
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    ///Returns the chunk type. Types that the library does not know are
+    ///returned as is.
+    #[must_use]
+    pub fn get_type(&self) -> u16 {
+        self.typ
+    }
+
+    ///Returns the name of the chunk type as used in the Android framework,
+    ///e.g. `RES_XML_START_ELEMENT_TYPE`, or `None` for unknown types.
+    #[must_use]
+    pub fn get_type_name(&self) -> Option<&'static str> {
+        Some(match self.resource_type? {
+            ResourceType::NullType => "RES_NULL_TYPE",
+            ResourceType::StringPool => "RES_STRING_POOL_TYPE",
+            ResourceType::Table => "RES_TABLE_TYPE",
+            ResourceType::Xml => "RES_XML_TYPE",
+            ResourceType::XmlStartNameSpace => "RES_XML_START_NAMESPACE_TYPE",
+            ResourceType::XmlEndNameSpace => "RES_XML_END_NAMESPACE_TYPE",
+            ResourceType::XmlStartElement => "RES_XML_START_ELEMENT_TYPE",
+            ResourceType::XmlEndElement => "RES_XML_END_ELEMENT_TYPE",
+            ResourceType::XmlCdata => "RES_XML_CDATA_TYPE",
+            ResourceType::XmlLastChunk => "RES_XML_LAST_CHUNK_TYPE",
+            ResourceType::XmlResourceMap => "RES_XML_RESOURCE_MAP_TYPE",
+            ResourceType::TablePackage => "RES_TABLE_PACKAGE_TYPE",
+            ResourceType::TableType => "RES_TABLE_TYPE_TYPE",
+            ResourceType::TableTypeSpec => "RES_TABLE_TYPE_SPEC_TYPE",
+            ResourceType::TableLibrary => "RES_TABLE_LIBRARY_TYPE",
+        })
+    }
+
+    ///Returns the header size declared in the chunk header.
+    #[must_use]
+    pub fn get_header_size(&self) -> u16 {
+        self.header_size
+    }
+
+    ///Returns the chunk size declared in the chunk header, including the
+    ///header.
+    #[must_use]
+    pub fn get_size(&self) -> u32 {
+        self.size
+    }
+
+    ///Returns the number of bytes at the start of the chunk that are used by
+    ///its header and contents. Any bytes after that up to
+    ///[`ChunkInfo::get_size`] are not read by Android.
+    ///
+    ///Returns `None` for chunk types whose contents are not understood, or
+    ///if the contents are malformed.
+    #[must_use]
+    pub fn get_content_size(&self) -> Option<u32> {
+        self.content_size
+    }
+
+    ///Returns the string pool header if this is a string pool chunk.
+    #[must_use]
+    pub fn get_string_pool(&self) -> Option<&StringPoolInfo> {
+        self.string_pool.as_ref()
+    }
+}
+
+///The header fields of a string pool chunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringPoolInfo {
+    string_count: u32,
+    style_count: u32,
+    flags: u32,
+    string_start: u32,
+    style_start: u32,
+    is_utf8: bool,
+}
+
+impl StringPoolInfo {
+    fn new(header: &StringPoolHeader) -> Self {
+        Self {
+            string_count: header.string_count,
+            style_count: header.style_count,
+            flags: header.flags,
+            string_start: header.string_start,
+            style_start: header.style_start,
+            is_utf8: header.is_utf8(),
+        }
+    }
+
+    ///Returns the number of strings.
+    #[must_use]
+    pub fn get_string_count(&self) -> u32 {
+        self.string_count
+    }
+
+    ///Returns the number of styled strings.
+    #[must_use]
+    pub fn get_style_count(&self) -> u32 {
+        self.style_count
+    }
+
+    ///Returns the raw flags. Unknown flags are kept.
+    #[must_use]
+    pub fn get_flags(&self) -> u32 {
+        self.flags
+    }
+
+    ///Returns the offset of the string data from the start of the chunk.
+    #[must_use]
+    pub fn get_string_start(&self) -> u32 {
+        self.string_start
+    }
+
+    ///Returns the offset of the style data from the start of the chunk.
+    #[must_use]
+    pub fn get_style_start(&self) -> u32 {
+        self.style_start
+    }
+
+    ///Returns whether the strings are encoded as UTF-8 instead of UTF-16.
+    #[must_use]
+    pub fn is_utf8(&self) -> bool {
+        self.is_utf8
+    }
+}
+
+///Lists the chunks of a binary XML document in file order. The first entry
+///is the document chunk, followed by the chunks it contains. Chunks of
+///unknown types are included.
+///
+///Bytes after the size declared in the document header are not part of
+///any chunk. A document without a document header has no document chunk,
+///and the list starts with its string pool.
+///
+/// # Errors
+///
+/// Will return `ParseError` if a chunk header is truncated or declares a
+/// size that is smaller than the header or larger than the remaining input.
+pub fn parse_chunks(input: &[u8]) -> Result<Vec<ChunkInfo>, ParseError> {
+    if binaryxml::is_headerless(input) {
+        // The chunks of the document follow each other without an XML
+        // chunk around them.
+        return list_chunks(input, 0).map(|(chunks, _)| chunks);
+    }
+
+    let (document, _) = binaryxml::next_chunk(input)?;
+    let header_size = usize::from(document.header_size);
+    let (mut chunks, end) = list_chunks(&document.data[header_size..], header_size)?;
+
+    // The contained chunks are the contents of the document chunk.
+    chunks.insert(0, ChunkInfo::new(0, &document, Some(end)));
+    Ok(chunks)
+}
+
+// Lists the chunks that fill `input`, which starts at `offset` in the file.
+// Returns the chunks and the offset of the end of the last one.
+fn list_chunks(mut input: &[u8], mut offset: usize) -> Result<(Vec<ChunkInfo>, usize), ParseError> {
+    let mut chunks = Vec::new();
+    while !input.is_empty() {
+        let (chunk, next) = binaryxml::next_chunk(input)?;
+        chunks.push(ChunkInfo::new(offset, &chunk, content_size(&chunk)));
+        offset += chunk.data.len();
+        input = next;
+    }
+    Ok((chunks, offset))
+}
+
+// Returns the number of bytes used by the header and contents of `chunk`.
+fn content_size(chunk: &RawChunk) -> Option<usize> {
+    let header_size = usize::from(chunk.header_size);
+    let body_size = match chunk.resource_type()? {
+        ResourceType::StringPool => return stringpool::content_size(chunk.data).ok(),
+        ResourceType::XmlResourceMap => (chunk.data.len().checked_sub(header_size)?) / 4 * 4,
+        ResourceType::XmlStartNameSpace
+        | ResourceType::XmlEndNameSpace
+        | ResourceType::XmlEndElement => 8,
+        ResourceType::XmlCdata => 12,
+        ResourceType::XmlStartElement => {
+            const ATTR_EXT_SIZE: usize = 20;
+
+            let (_, ext) = XmlAttrExt::from_bytes((chunk.data.get(header_size..)?, 0)).ok()?;
+            let attributes = usize::from(ext.attribute_start)
+                + usize::from(ext.attribute_size) * usize::from(ext.attribute_count);
+            attributes.max(ATTR_EXT_SIZE)
+        }
+        _ => return None,
+    };
+    Some(header_size + body_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chunks() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        let chunks = parse_chunks(data).unwrap();
+
+        let document = &chunks[0];
+        assert_eq!(document.get_type_name(), Some("RES_XML_TYPE"));
+        assert_eq!(usize::try_from(document.get_size()).unwrap(), data.len());
+
+        let pool = chunks[1].get_string_pool().unwrap();
+        assert_eq!(chunks[1].get_offset(), 8);
+        assert!(!pool.is_utf8());
+        for (chunk, next) in chunks[1..].iter().zip(&chunks[2..]) {
+            assert_eq!(
+                chunk.get_offset() + usize::try_from(chunk.get_size()).unwrap(),
+                next.get_offset()
+            );
+        }
+        // Nothing is hidden in a file produced by aapt.
+        for chunk in &chunks {
+            assert_eq!(chunk.get_content_size(), Some(chunk.get_size()));
+        }
+    }
+
+    #[test]
+    fn test_chunk_slack() {
+        let data = include_bytes!("../examples/AndroidManifestChunkSlack.xml");
+        let chunks = parse_chunks(data).unwrap();
+
+        let slack: Vec<(&str, u32)> = chunks
+            .iter()
+            .filter_map(|c| Some((c.get_type_name()?, c.get_size() - c.get_content_size()?)))
+            .filter(|(_, slack)| *slack != 0)
+            .collect();
+        assert_eq!(
+            slack,
+            [
+                ("RES_STRING_POOL_TYPE", 16),
+                ("RES_XML_START_ELEMENT_TYPE", 8)
+            ]
+        );
+
+        // The trailing bytes are outside of the document chunk.
+        assert_eq!(
+            usize::try_from(chunks[0].get_size()).unwrap(),
+            data.len() - 4
+        );
+        assert_eq!(chunks[1].get_string_pool().unwrap().get_string_count(), 7);
+    }
+
+    #[test]
+    fn test_unknown_chunk() {
+        let data = include_bytes!("../examples/AndroidManifestReorderedChunks.xml");
+        let unknown = parse_chunks(data)
+            .unwrap()
+            .into_iter()
+            .find(|c| c.get_type_name().is_none())
+            .unwrap();
+        assert_eq!(unknown.get_type(), 0x7777);
+        assert_eq!(unknown.get_content_size(), None);
+        assert_eq!(unknown.get_size(), 16);
+    }
+
+    #[test]
+    fn test_no_document_header() {
+        let data = include_bytes!("../examples/AndroidManifestNoDocumentHeader.xml");
+        let chunks = parse_chunks(data).unwrap();
+
+        assert_eq!(chunks[0].get_type_name(), Some("RES_STRING_POOL_TYPE"));
+        assert_eq!(chunks[0].get_offset(), 0);
+        assert!(chunks
+            .iter()
+            .all(|c| c.get_type_name() != Some("RES_XML_TYPE")));
+
+        let last = chunks.last().unwrap();
+        assert_eq!(
+            last.get_offset() + usize::try_from(last.get_size()).unwrap(),
+            data.len()
+        );
+    }
+}
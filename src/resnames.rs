@@ -0,0 +1,170 @@
+//!Names of the public resources of the Android framework.
+//!
+//!Without resources.arsc, references are shown by their resource ID, such
+//!as `@ref/0x01030006`. With the `android-res-names` feature, references to
+//!the framework resources listed here are shown by name instead, such as
+//!`@android:style/Theme.NoTitleBar`. References to the resources of the APK
+//!itself are always shown by ID.
+//!
+//!Every public attribute is known. Only the commonly used styles, strings,
+//!IDs and colors are.
+//!```rust
+//!use axmldecoder::resnames::lookup;
+//!assert_eq!(lookup(0x0101_0003), Some("name"));
+//!assert_eq!(lookup(0x0103_0006), Some("Theme.NoTitleBar"));
+//!assert_eq!(lookup(0x7f03_0000), None);
+//!```
+
+use crate::xml::get_resource_name;
+
+const ATTR_TYPE: u32 = 0x0101;
+
+// Sorted by resource ID
+const NAMES: &[(u32, &str)] = &[
+    (0x0102_0000, "background"),
+    (0x0102_0001, "checkbox"),
+    (0x0102_0002, "content"),
+    (0x0102_0003, "edit"),
+    (0x0102_0004, "empty"),
+    (0x0102_0005, "hint"),
+    (0x0102_0006, "icon"),
+    (0x0102_0007, "icon1"),
+    (0x0102_0008, "icon2"),
+    (0x0102_0009, "input"),
+    (0x0102_000a, "list"),
+    (0x0102_000b, "message"),
+    (0x0102_000c, "primary"),
+    (0x0102_000d, "progress"),
+    (0x0102_000e, "selectedIcon"),
+    (0x0102_000f, "secondaryProgress"),
+    (0x0102_0010, "summary"),
+    (0x0102_0011, "tabcontent"),
+    (0x0102_0012, "tabhost"),
+    (0x0102_0013, "tabs"),
+    (0x0102_0014, "text1"),
+    (0x0102_0015, "text2"),
+    (0x0102_0016, "title"),
+    (0x0102_0017, "toggle"),
+    (0x0102_0018, "widget_frame"),
+    (0x0102_0019, "button1"),
+    (0x0102_001a, "button2"),
+    (0x0102_001b, "button3"),
+    (0x0102_002c, "home"),
+    (0x0103_0000, "Animation"),
+    (0x0103_0001, "Animation.Activity"),
+    (0x0103_0002, "Animation.Dialog"),
+    (0x0103_0003, "Animation.Translucent"),
+    (0x0103_0004, "Animation.Toast"),
+    (0x0103_0005, "Theme"),
+    (0x0103_0006, "Theme.NoTitleBar"),
+    (0x0103_0007, "Theme.NoTitleBar.Fullscreen"),
+    (0x0103_0008, "Theme.Black"),
+    (0x0103_0009, "Theme.Black.NoTitleBar"),
+    (0x0103_000a, "Theme.Black.NoTitleBar.Fullscreen"),
+    (0x0103_000b, "Theme.Dialog"),
+    (0x0103_000c, "Theme.Light"),
+    (0x0103_000d, "Theme.Light.NoTitleBar"),
+    (0x0103_000e, "Theme.Light.NoTitleBar.Fullscreen"),
+    (0x0103_000f, "Theme.Translucent"),
+    (0x0103_0010, "Theme.Translucent.NoTitleBar"),
+    (0x0103_0011, "Theme.Translucent.NoTitleBar.Fullscreen"),
+    (0x0103_006b, "Theme.Holo"),
+    (0x0103_006c, "Theme.Holo.NoActionBar"),
+    (0x0103_006d, "Theme.Holo.NoActionBar.Fullscreen"),
+    (0x0103_006e, "Theme.Holo.Light"),
+    (0x0103_0119, "Theme.Holo.Light.DarkActionBar"),
+    (0x0103_0128, "Theme.DeviceDefault"),
+    (0x0103_012b, "Theme.DeviceDefault.Light"),
+    (0x0103_013f, "Theme.DeviceDefault.Light.DarkActionBar"),
+    (0x0103_0224, "Theme.Material"),
+    (0x0103_0237, "Theme.Material.Light"),
+    (0x0103_0238, "Theme.Material.Light.DarkActionBar"),
+    (0x0104_0000, "cancel"),
+    (0x0104_0001, "copy"),
+    (0x0104_0002, "copyUrl"),
+    (0x0104_0003, "cut"),
+    (0x0104_0006, "emptyPhoneNumber"),
+    (0x0104_0007, "httpErrorBadUrl"),
+    (0x0104_0008, "httpErrorUnsupportedScheme"),
+    (0x0104_0009, "no"),
+    (0x0104_000a, "ok"),
+    (0x0104_000b, "paste"),
+    (0x0104_000c, "search_go"),
+    (0x0104_000d, "selectAll"),
+    (0x0104_000e, "unknownName"),
+    (0x0104_000f, "untitled"),
+    (0x0104_0010, "VideoView_error_button"),
+    (0x0104_0011, "VideoView_error_text_unknown"),
+    (0x0104_0012, "VideoView_error_title"),
+    (0x0104_0013, "yes"),
+    (0x0104_0014, "dialog_alert_title"),
+    (
+        0x0104_0015,
+        "VideoView_error_text_invalid_progressive_playback",
+    ),
+    (0x0106_000b, "white"),
+    (0x0106_000c, "black"),
+    (0x0106_000d, "transparent"),
+];
+
+///Returns the name of the Android framework resource with the given ID,
+///without its package and type. Returns `None` for resources that are not
+///known, including every resource of an APK.
+///
+///The table is not generated from the framework's `public.xml`. Apart
+///from the attributes, it only covers the common public styles, strings,
+///IDs and colors, so `None` does not mean that the ID is not a framework
+///resource.
+#[must_use]
+pub fn lookup(id: u32) -> Option<&'static str> {
+    if id >> 16 == ATTR_TYPE {
+        return get_resource_name(id).filter(|name| *name != "UNKNOWN");
+    }
+    NAMES
+        .binary_search_by_key(&id, |(i, _)| *i)
+        .ok()
+        .map(|i| NAMES[i].1)
+}
+
+// Returns the type and name of the Android framework resource with the
+// given ID, as used in `@android:type/name` references.
+pub(crate) fn lookup_with_type(id: u32) -> Option<(&'static str, &'static str)> {
+    let typ = match id >> 16 {
+        0x0101 => "attr",
+        0x0102 => "id",
+        0x0103 => "style",
+        0x0104 => "string",
+        0x0106 => "color",
+        _ => return None,
+    };
+    Some((typ, lookup(id)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_sorted() {
+        assert!(NAMES.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup(0x0101_0000), Some("theme"));
+        assert_eq!(lookup(0x0101_0267), None);
+        assert_eq!(lookup(0x0104_000a), Some("ok"));
+        assert_eq!(lookup(0x0104_0004), None);
+        assert_eq!(lookup(0x7f01_0000), None);
+
+        assert_eq!(lookup_with_type(0x0101_0003), Some(("attr", "name")));
+        assert_eq!(
+            lookup_with_type(0x0103_0010),
+            Some(("style", "Theme.Translucent.NoTitleBar"))
+        );
+        assert_eq!(
+            lookup_with_type(0x0106_000d),
+            Some(("color", "transparent"))
+        );
+    }
+}
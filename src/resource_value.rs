@@ -0,0 +1,772 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use deku::prelude::*;
+
+use crate::stringpool::StringPool;
+use crate::ParseError;
+
+#[derive(Debug, DekuRead, DekuWrite)]
+pub(crate) struct ResourceValue {
+    pub(crate) size: u16,
+    pub(crate) res: u8,
+    pub(crate) data_type: ResourceValueType,
+    pub(crate) data: u32,
+}
+
+impl ResourceValue {
+    pub(crate) fn get_value(&self, string_pool: &StringPool) -> Result<AttributeValue, ParseError> {
+        let value = match &self.data_type {
+            ResourceValueType::Null => AttributeValue::Null,
+            ResourceValueType::Reference => AttributeValue::Reference(self.data),
+            ResourceValueType::Attribute => AttributeValue::Attribute(self.data),
+            ResourceValueType::String => AttributeValue::String(
+                string_pool
+                    .get(usize::try_from(self.data).unwrap())
+                    .ok_or(ParseError::StringNotFound(self.data))?
+                    .to_string(),
+            ),
+            ResourceValueType::Float => AttributeValue::Float(f32::from_bits(self.data)),
+            ResourceValueType::Dimension => {
+                let (value, unit) = decode_complex(self.data);
+                AttributeValue::Dimension { value, unit }
+            }
+            ResourceValueType::Fraction => AttributeValue::Fraction {
+                value: complex_to_float(self.data),
+                typ: FractionType::from_complex(self.data),
+            },
+            #[allow(clippy::cast_possible_wrap)]
+            ResourceValueType::Dec => AttributeValue::Int(self.data as i32),
+            ResourceValueType::Hex => AttributeValue::Hex(self.data),
+            ResourceValueType::Boolean => AttributeValue::Boolean(self.data != 0),
+            ResourceValueType::ColorArgb8 => AttributeValue::ColorArgb8(self.data),
+            ResourceValueType::ColorRgb8 => AttributeValue::ColorRgb8(self.data),
+            ResourceValueType::ColorArgb4 => AttributeValue::ColorArgb4(self.data),
+            ResourceValueType::ColorRgb4 => AttributeValue::ColorRgb4(self.data),
+            ResourceValueType::DynamicReference => AttributeValue::DynamicReference(self.data),
+            ResourceValueType::DynamicAttribute => AttributeValue::DynamicAttribute(self.data),
+            ResourceValueType::Unknown(data_type) => AttributeValue::Unknown {
+                data_type: *data_type,
+                data: self.data,
+            },
+        };
+
+        Ok(value)
+    }
+}
+
+impl ResourceValue {
+    pub(crate) fn new(data_type: ResourceValueType, data: u32) -> Self {
+        Self {
+            size: 8,
+            res: 0,
+            data_type,
+            data,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(type = "u8")]
+pub(crate) enum ResourceValueType {
+    #[deku(id = "0x00")]
+    Null,
+    #[deku(id = "0x01")]
+    Reference,
+    #[deku(id = "0x02")]
+    Attribute,
+    #[deku(id = "0x03")]
+    String,
+    #[deku(id = "0x04")]
+    Float,
+    #[deku(id = "0x05")]
+    Dimension,
+    #[deku(id = "0x06")]
+    Fraction,
+    #[deku(id = "0x07")]
+    DynamicReference,
+    #[deku(id = "0x08")]
+    DynamicAttribute,
+    #[deku(id = "0x10")]
+    Dec,
+    #[deku(id = "0x11")]
+    Hex,
+    #[deku(id = "0x12")]
+    Boolean,
+    #[deku(id = "0x1c")]
+    ColorArgb8,
+    #[deku(id = "0x1d")]
+    ColorRgb8,
+    #[deku(id = "0x1e")]
+    ColorArgb4,
+    #[deku(id = "0x1f")]
+    ColorRgb4,
+    // Types that are not decoded
+    #[deku(id_pat = "_")]
+    Unknown(u8),
+}
+
+///Enum representing the typed value of an attribute.
+///
+///The [`Display`](fmt::Display) implementation produces the same string
+///found in [`Element::get_attributes`](crate::Element::get_attributes).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum AttributeValue {
+    Null,
+    Reference(u32),
+    Attribute(u32),
+    ///A reference to a resource of a shared library, whose package ID is
+    ///only assigned at runtime.
+    DynamicReference(u32),
+    ///An attribute of a shared library, whose package ID is only assigned
+    ///at runtime.
+    DynamicAttribute(u32),
+    String(String),
+    Float(f32),
+    Dimension {
+        value: f32,
+        unit: DimensionUnit,
+    },
+    Fraction {
+        value: f32,
+        typ: FractionType,
+    },
+    Int(i32),
+    Hex(u32),
+    Boolean(bool),
+    ColorArgb8(u32),
+    ColorRgb8(u32),
+    ColorArgb4(u32),
+    ColorRgb4(u32),
+    ///A value of a type that is not decoded. `data_type` is the type found
+    ///in the file and `data` its raw data. It is shown as aapt shows it,
+    ///e.g. `type0x20/1234`.
+    Unknown {
+        data_type: u8,
+        data: u32,
+    },
+}
+
+impl AttributeValue {
+    pub(crate) fn to_resource_value(
+        &self,
+        mut string_index: impl FnMut(&str) -> u32,
+    ) -> ResourceValue {
+        let (data_type, data) = match self {
+            AttributeValue::Null => (ResourceValueType::Null, 0),
+            AttributeValue::Reference(data) => (ResourceValueType::Reference, *data),
+            AttributeValue::Attribute(data) => (ResourceValueType::Attribute, *data),
+            AttributeValue::DynamicReference(data) => (ResourceValueType::DynamicReference, *data),
+            AttributeValue::DynamicAttribute(data) => (ResourceValueType::DynamicAttribute, *data),
+            AttributeValue::String(s) => (ResourceValueType::String, string_index(s)),
+            AttributeValue::Float(value) => (ResourceValueType::Float, value.to_bits()),
+            AttributeValue::Dimension { value, unit } => (
+                ResourceValueType::Dimension,
+                float_to_complex(*value) | (u32::from(unit.to_complex()) & COMPLEX_UNIT_MASK),
+            ),
+            AttributeValue::Fraction { value, typ } => (
+                ResourceValueType::Fraction,
+                float_to_complex(*value) | (u32::from(typ.to_complex()) & COMPLEX_UNIT_MASK),
+            ),
+            AttributeValue::Int(value) => (
+                ResourceValueType::Dec,
+                u32::from_ne_bytes(value.to_ne_bytes()),
+            ),
+            AttributeValue::Hex(data) => (ResourceValueType::Hex, *data),
+            // Android encodes true as -1
+            AttributeValue::Boolean(value) => (
+                ResourceValueType::Boolean,
+                if *value { u32::MAX } else { 0 },
+            ),
+            AttributeValue::ColorArgb8(data) => (ResourceValueType::ColorArgb8, *data),
+            AttributeValue::ColorRgb8(data) => (ResourceValueType::ColorRgb8, *data),
+            AttributeValue::ColorArgb4(data) => (ResourceValueType::ColorArgb4, *data),
+            AttributeValue::ColorRgb4(data) => (ResourceValueType::ColorRgb4, *data),
+            AttributeValue::Unknown { data_type, data } => {
+                (ResourceValueType::Unknown(*data_type), *data)
+            }
+        };
+
+        ResourceValue::new(data_type, data)
+    }
+}
+
+// Floats are compared by their bit pattern so that values decoded from the
+// same data always compare equal, including NaN.
+impl PartialEq for AttributeValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (AttributeValue::Null, AttributeValue::Null) => true,
+            (AttributeValue::Reference(a), AttributeValue::Reference(b))
+            | (AttributeValue::Attribute(a), AttributeValue::Attribute(b))
+            | (AttributeValue::DynamicReference(a), AttributeValue::DynamicReference(b))
+            | (AttributeValue::DynamicAttribute(a), AttributeValue::DynamicAttribute(b))
+            | (AttributeValue::Hex(a), AttributeValue::Hex(b))
+            | (AttributeValue::ColorArgb8(a), AttributeValue::ColorArgb8(b))
+            | (AttributeValue::ColorRgb8(a), AttributeValue::ColorRgb8(b))
+            | (AttributeValue::ColorArgb4(a), AttributeValue::ColorArgb4(b))
+            | (AttributeValue::ColorRgb4(a), AttributeValue::ColorRgb4(b)) => a == b,
+            (AttributeValue::String(a), AttributeValue::String(b)) => a == b,
+            (AttributeValue::Float(a), AttributeValue::Float(b)) => a.to_bits() == b.to_bits(),
+            (
+                AttributeValue::Dimension { value: a, unit: ua },
+                AttributeValue::Dimension { value: b, unit: ub },
+            ) => a.to_bits() == b.to_bits() && ua == ub,
+            (
+                AttributeValue::Fraction { value: a, typ: ta },
+                AttributeValue::Fraction { value: b, typ: tb },
+            ) => a.to_bits() == b.to_bits() && ta == tb,
+            (AttributeValue::Int(a), AttributeValue::Int(b)) => a == b,
+            (AttributeValue::Boolean(a), AttributeValue::Boolean(b)) => a == b,
+            (
+                AttributeValue::Unknown {
+                    data_type: ta,
+                    data: a,
+                },
+                AttributeValue::Unknown {
+                    data_type: tb,
+                    data: b,
+                },
+            ) => ta == tb && a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for AttributeValue {}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        AttributeValue::String(value)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        AttributeValue::String(value.to_string())
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Boolean(value)
+    }
+}
+
+impl From<i32> for AttributeValue {
+    fn from(value: i32) -> Self {
+        AttributeValue::Int(value)
+    }
+}
+
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeValue::Null => Ok(()),
+            AttributeValue::Reference(data) => {
+                #[cfg(feature = "android-res-names")]
+                if let Some((typ, name)) = crate::resnames::lookup_with_type(*data) {
+                    return write!(f, "@android:{}/{}", typ, name);
+                }
+                write!(f, "@ref/0x{:08x}", data)
+            }
+            AttributeValue::Attribute(data) => {
+                #[cfg(feature = "android-res-names")]
+                if let Some((typ, name)) = crate::resnames::lookup_with_type(*data) {
+                    return write!(f, "?android:{}/{}", typ, name);
+                }
+                write!(f, "?attr/0x{:08x}", data)
+            }
+            AttributeValue::DynamicReference(data) => write!(f, "@dyn/0x{:08x}", data),
+            AttributeValue::DynamicAttribute(data) => write!(f, "?dyn/0x{:08x}", data),
+            AttributeValue::String(s) => write!(f, "{}", s),
+            AttributeValue::Float(value) => write!(f, "{}", format_float(*value)),
+            AttributeValue::Dimension { value, unit } => {
+                write!(f, "{}{}", format_dimension(*value), unit)
+            }
+            AttributeValue::Fraction { value, typ } => {
+                write!(f, "{}{}", format_float(value * 100.0), typ)
+            }
+            AttributeValue::Int(value) => write!(f, "{}", value),
+            AttributeValue::Hex(data) => write!(f, "0x{:08x}", data),
+            AttributeValue::Boolean(value) => write!(f, "{}", value),
+            AttributeValue::ColorArgb8(data) => write!(f, "#{:08x}", data),
+            AttributeValue::ColorRgb8(data) => write!(f, "#{:06x}", data & 0x00ff_ffff),
+            AttributeValue::ColorArgb4(data) => write!(f, "#{:08x}", expand_nibbles(*data)),
+            AttributeValue::ColorRgb4(data) => {
+                write!(f, "#{:06x}", expand_nibbles(*data) & 0x00ff_ffff)
+            }
+            // As printed by aapt
+            AttributeValue::Unknown { data_type, data } => {
+                write!(f, "type0x{:02x}/{}", data_type, data)
+            }
+        }
+    }
+}
+
+///Unit of a [`AttributeValue::Dimension`] value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum DimensionUnit {
+    Px,
+    ///Density-independent pixels, written as `dp`.
+    Dip,
+    Sp,
+    Pt,
+    In,
+    Mm,
+    Unknown(u8),
+}
+
+impl DimensionUnit {
+    fn from_complex(data: u32) -> Self {
+        match complex_unit(data) {
+            0 => DimensionUnit::Px,
+            1 => DimensionUnit::Dip,
+            2 => DimensionUnit::Sp,
+            3 => DimensionUnit::Pt,
+            4 => DimensionUnit::In,
+            5 => DimensionUnit::Mm,
+            n => DimensionUnit::Unknown(n),
+        }
+    }
+
+    fn to_complex(self) -> u8 {
+        match self {
+            DimensionUnit::Px => 0,
+            DimensionUnit::Dip => 1,
+            DimensionUnit::Sp => 2,
+            DimensionUnit::Pt => 3,
+            DimensionUnit::In => 4,
+            DimensionUnit::Mm => 5,
+            DimensionUnit::Unknown(n) => n,
+        }
+    }
+}
+
+impl fmt::Display for DimensionUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DimensionUnit::Px => write!(f, "px"),
+            DimensionUnit::Dip => write!(f, "dp"),
+            DimensionUnit::Sp => write!(f, "sp"),
+            DimensionUnit::Pt => write!(f, "pt"),
+            DimensionUnit::In => write!(f, "in"),
+            DimensionUnit::Mm => write!(f, "mm"),
+            DimensionUnit::Unknown(_) => write!(f, " (unknown unit)"),
+        }
+    }
+}
+
+///Type of a [`AttributeValue::Fraction`] value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum FractionType {
+    ///A fraction of the element itself, i.e. `%`.
+    Percent,
+    ///A fraction of the parent container, i.e. `%p`.
+    PercentParent,
+    Unknown(u8),
+}
+
+impl FractionType {
+    fn from_complex(data: u32) -> Self {
+        match complex_unit(data) {
+            0 => FractionType::Percent,
+            1 => FractionType::PercentParent,
+            n => FractionType::Unknown(n),
+        }
+    }
+
+    fn to_complex(self) -> u8 {
+        match self {
+            FractionType::Percent => 0,
+            FractionType::PercentParent => 1,
+            FractionType::Unknown(n) => n,
+        }
+    }
+}
+
+impl fmt::Display for FractionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FractionType::Percent => write!(f, "%"),
+            FractionType::PercentParent => write!(f, "%p"),
+            FractionType::Unknown(_) => write!(f, " (unknown unit)"),
+        }
+    }
+}
+
+// 4-bit colors only use the high nibble of each component. The nibble is
+// duplicated to give the equivalent 8-bit component, e.g. #f0a is written as
+// #ff00aa.
+fn expand_nibbles(data: u32) -> u32 {
+    let high = data & 0xf0f0_f0f0;
+    high | (high >> 4)
+}
+
+// Formats a float the same way as printf's `%g`, which is what aapt uses
+// when dumping float, dimension and fraction values.
+fn format_float(value: f32) -> String {
+    fn trim_zeros(s: &str) -> &str {
+        if s.contains('.') {
+            s.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            s
+        }
+    }
+
+    if value.is_nan() {
+        return "nan".to_string();
+    }
+    if value.is_infinite() {
+        return if value < 0.0 { "-inf" } else { "inf" }.to_string();
+    }
+    if value == 0.0 {
+        return value.to_string();
+    }
+
+    // Round to 6 significant digits first, as the rounding can change the
+    // exponent.
+    let scientific = format!("{:.5e}", value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+
+    if (-4..6).contains(&exponent) {
+        let precision = usize::try_from(5 - exponent).unwrap();
+        trim_zeros(&format!("{:.*}", precision, value)).to_string()
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", trim_zeros(mantissa), sign, exponent.abs())
+    }
+}
+
+// Formats a dimension value like aapt, which always includes the fractional
+// part, e.g. `16.0dp`.
+fn format_dimension(value: f32) -> String {
+    let s = format_float(value);
+    if value.is_finite() && !s.contains(['.', 'e']) {
+        format!("{}.0", s)
+    } else {
+        s
+    }
+}
+
+// Complex values (dimensions and fractions) are encoded as a 24-bit
+// mantissa, a 2-bit radix and a 4-bit unit.
+//
+// https://android.googlesource.com/platform/frameworks/base/+/master/libs/androidfw/include/androidfw/ResourceTypes.h
+const COMPLEX_UNIT_MASK: u32 = 0xf;
+const COMPLEX_RADIX_SHIFT: u32 = 4;
+const COMPLEX_RADIX_MASK: u32 = 0x3;
+const COMPLEX_MANTISSA_SHIFT: u32 = 8;
+const COMPLEX_MANTISSA_MASK: u32 = 0x00ff_ffff;
+
+fn complex_unit(data: u32) -> u8 {
+    u8::try_from(data & COMPLEX_UNIT_MASK).unwrap()
+}
+
+// Decodes a complex dimension value into its value and unit.
+fn decode_complex(data: u32) -> (f32, DimensionUnit) {
+    (complex_to_float(data), DimensionUnit::from_complex(data))
+}
+
+#[allow(clippy::cast_possible_wrap, clippy::cast_precision_loss)]
+fn complex_to_float(data: u32) -> f32 {
+    const MANTISSA_MULT: f32 = 1.0 / (1 << COMPLEX_MANTISSA_SHIFT) as f32;
+    const RADIX_MULTS: [f32; 4] = [
+        MANTISSA_MULT,
+        1.0 / (1 << 7) as f32 * MANTISSA_MULT,
+        1.0 / (1 << 15) as f32 * MANTISSA_MULT,
+        1.0 / (1 << 23) as f32 * MANTISSA_MULT,
+    ];
+
+    let mantissa = (data & (COMPLEX_MANTISSA_MASK << COMPLEX_MANTISSA_SHIFT)) as i32;
+    let radix = (data >> COMPLEX_RADIX_SHIFT) & COMPLEX_RADIX_MASK;
+
+    mantissa as f32 * RADIX_MULTS[usize::try_from(radix).unwrap()]
+}
+
+// Port of floatToComplex() from aapt2's ResourceUtils.cpp
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_wrap
+)]
+fn float_to_complex(value: f32) -> u32 {
+    const RADIX_23P0: u32 = 0;
+    const RADIX_16P7: u32 = 1;
+    const RADIX_8P15: u32 = 2;
+    const RADIX_0P23: u32 = 3;
+
+    let neg = value < 0.0;
+    let value = value.abs();
+
+    let bits = (value * (1 << 23) as f32 + 0.5) as u64;
+
+    let (radix, shift) = if bits & 0x7f_ffff == 0 {
+        (RADIX_23P0, 23)
+    } else if bits & 0xffff_ffff_ff80_0000 == 0 {
+        (RADIX_0P23, 0)
+    } else if bits & 0xffff_ffff_8000_0000 == 0 {
+        (RADIX_8P15, 8)
+    } else if bits & 0xffff_ff80_0000_0000 == 0 {
+        (RADIX_16P7, 16)
+    } else {
+        (RADIX_23P0, 23)
+    };
+
+    let mut mantissa = ((bits >> shift) & u64::from(COMPLEX_MANTISSA_MASK)) as u32;
+    if neg {
+        mantissa = (mantissa as i32).wrapping_neg() as u32 & COMPLEX_MANTISSA_MASK;
+    }
+
+    (radix << COMPLEX_RADIX_SHIFT) | (mantissa << COMPLEX_MANTISSA_SHIFT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complex_to_float() {
+        // 16dip
+        assert_eq!(complex_to_float(0x0000_1001), 16.0);
+        assert_eq!(DimensionUnit::from_complex(0x0000_1001), DimensionUnit::Dip);
+
+        // 1.5sp, radix 16p7
+        assert_eq!(complex_to_float(0x0000_c012), 1.5);
+        assert_eq!(DimensionUnit::from_complex(0x0000_c012), DimensionUnit::Sp);
+
+        // -1px
+        assert_eq!(complex_to_float(0xffff_ff00), -1.0);
+        assert_eq!(DimensionUnit::from_complex(0xffff_ff00), DimensionUnit::Px);
+    }
+
+    #[test]
+    fn test_unknown_type() {
+        let data = [0x08, 0x00, 0x00, 0x20, 0x01, 0x00, 0x0b, 0x7f];
+        let (_, value) = ResourceValue::from_bytes((&data, 0)).unwrap();
+        let string_pool = StringPool::encode::<&str>(&[]).unwrap();
+        let string_pool = StringPool::try_from(string_pool.as_slice()).unwrap();
+        let value = value.get_value(&string_pool).unwrap();
+        assert_eq!(
+            value,
+            AttributeValue::Unknown {
+                data_type: 0x20,
+                data: 0x7f0b_0001
+            }
+        );
+        assert_eq!(value.to_string(), "type0x20/2131427329");
+
+        let encoded = value.to_resource_value(|_| unreachable!());
+        assert_eq!(encoded.to_bytes().unwrap(), data);
+    }
+
+    #[test]
+    fn test_dynamic_types() {
+        let string_pool = StringPool::encode::<&str>(&[]).unwrap();
+        let string_pool = StringPool::try_from(string_pool.as_slice()).unwrap();
+        for (data, expected, text) in [
+            (
+                [0x08, 0x00, 0x00, 0x07, 0x01, 0x00, 0x0b, 0x00],
+                AttributeValue::DynamicReference(0x000b_0001),
+                "@dyn/0x000b0001",
+            ),
+            (
+                [0x08, 0x00, 0x00, 0x08, 0x02, 0x00, 0x01, 0x00],
+                AttributeValue::DynamicAttribute(0x0001_0002),
+                "?dyn/0x00010002",
+            ),
+        ] {
+            let (_, value) = ResourceValue::from_bytes((&data, 0)).unwrap();
+            let value = value.get_value(&string_pool).unwrap();
+            assert_eq!(value, expected);
+            assert_eq!(value.to_string(), text);
+
+            let encoded = value.to_resource_value(|_| unreachable!());
+            assert_eq!(encoded.to_bytes().unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(AttributeValue::Hex(255).to_string(), "0x000000ff");
+        assert_eq!(AttributeValue::Hex(0x1184).to_string(), "0x00001184");
+        assert_eq!(
+            AttributeValue::Reference(0x7f01_0001).to_string(),
+            "@ref/0x7f010001"
+        );
+        // android:background="@drawable/..."
+        assert_eq!(
+            AttributeValue::Reference(0x7f08_0000).to_string(),
+            "@ref/0x7f080000"
+        );
+        #[cfg(not(feature = "android-res-names"))]
+        assert_eq!(
+            AttributeValue::Attribute(0x0101_0000).to_string(),
+            "?attr/0x01010000"
+        );
+        #[cfg(feature = "android-res-names")]
+        assert_eq!(
+            AttributeValue::Attribute(0x0101_0000).to_string(),
+            "?android:attr/theme"
+        );
+        assert_eq!(
+            AttributeValue::Reference(0x0103_0400).to_string(),
+            "@ref/0x01030400"
+        );
+        assert_eq!(AttributeValue::Int(-1).to_string(), "-1");
+        assert_eq!(AttributeValue::Boolean(true).to_string(), "true");
+        assert_eq!(AttributeValue::Float(1.5).to_string(), "1.5");
+        assert_eq!(
+            AttributeValue::Float(f32::from_bits(0x3f80_0000)).to_string(),
+            "1"
+        );
+        assert_eq!(
+            AttributeValue::Float(f32::from_bits(0x7fc0_0000)).to_string(),
+            "nan"
+        );
+        assert_eq!(
+            AttributeValue::Float(f32::from_bits(0xff80_0000)).to_string(),
+            "-inf"
+        );
+        assert_eq!(AttributeValue::Null.to_string(), "");
+    }
+
+    #[test]
+    fn test_colors() {
+        assert_eq!(
+            AttributeValue::ColorArgb8(0xffff_0000).to_string(),
+            "#ffff0000"
+        );
+        assert_eq!(
+            AttributeValue::ColorArgb8(0x0000_00ff).to_string(),
+            "#000000ff"
+        );
+        assert_eq!(
+            AttributeValue::ColorRgb8(0xff00_00ff).to_string(),
+            "#0000ff"
+        );
+        assert_eq!(
+            AttributeValue::ColorRgb8(0xff12_3456).to_string(),
+            "#123456"
+        );
+        assert_eq!(
+            AttributeValue::ColorRgb8(0x00ff_0000).to_string(),
+            "#ff0000"
+        );
+        assert_eq!(
+            AttributeValue::ColorArgb4(0x88ff_00aa).to_string(),
+            "#88ff00aa"
+        );
+        assert_eq!(
+            AttributeValue::ColorArgb4(0x0000_000f).to_string(),
+            "#00000000"
+        );
+        assert_eq!(
+            AttributeValue::ColorArgb4(0x8f0a_3c5e).to_string(),
+            "#88003355"
+        );
+        assert_eq!(
+            AttributeValue::ColorRgb4(0xffff_00aa).to_string(),
+            "#ff00aa"
+        );
+        assert_eq!(
+            AttributeValue::ColorRgb4(0xff00_0000).to_string(),
+            "#000000"
+        );
+    }
+
+    #[test]
+    fn test_dimension_units_and_radixes() {
+        let units = [
+            (0, "px"),
+            (1, "dp"),
+            (2, "sp"),
+            (3, "pt"),
+            (4, "in"),
+            (5, "mm"),
+        ];
+        // (mantissa, radix, formatted value) for each radix
+        let radixes = [
+            (0x00_0003, 0, "3.0"),
+            (0x00_00c0, 1, "1.5"),
+            (0x00_c000, 2, "1.5"),
+            (0x60_0000, 3, "0.75"),
+        ];
+
+        for (unit, suffix) in units {
+            for (mantissa, radix, expected) in radixes {
+                let data = (mantissa << 8) | (radix << 4) | unit;
+                let (value, unit) = decode_complex(data);
+                let value = AttributeValue::Dimension { value, unit };
+                assert_eq!(value.to_string(), format!("{}{}", expected, suffix));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_complex() {
+        // android:minHeight="48dp"
+        assert_eq!(decode_complex(0x0000_3001), (48.0, DimensionUnit::Dip));
+        // android:textSize="14sp"
+        assert_eq!(decode_complex(0x0000_0e02), (14.0, DimensionUnit::Sp));
+        // android:layout_margin="-8px"
+        assert_eq!(decode_complex(0xffff_f800), (-8.0, DimensionUnit::Px));
+
+        let (value, unit) = decode_complex(0x0000_3001);
+        assert_eq!(
+            AttributeValue::Dimension { value, unit }.to_string(),
+            "48.0dp"
+        );
+        let (value, unit) = decode_complex(0x0000_0a07);
+        assert_eq!(
+            AttributeValue::Dimension { value, unit }.to_string(),
+            "10.0 (unknown unit)"
+        );
+    }
+
+    #[test]
+    fn test_fraction() {
+        // 50%, radix 0p23
+        let value = AttributeValue::Fraction {
+            value: complex_to_float(0x4000_0030),
+            typ: FractionType::from_complex(0x4000_0030),
+        };
+        assert_eq!(value.to_string(), "50%");
+
+        // 12.5%p, radix 0p23
+        let value = AttributeValue::Fraction {
+            value: complex_to_float(0x1000_0031),
+            typ: FractionType::from_complex(0x1000_0031),
+        };
+        assert_eq!(value.to_string(), "12.5%p");
+    }
+
+    #[test]
+    fn test_format_float() {
+        assert_eq!(format_float(1.0), "1");
+        assert_eq!(format_float(0.0), "0");
+        assert_eq!(format_float(-2.5), "-2.5");
+        assert_eq!(format_float(16.0 / 9.0), "1.77778");
+        assert_eq!(format_float(0.0001), "0.0001");
+        assert_eq!(format_float(0.00001), "1e-05");
+        assert_eq!(format_float(123_456.0), "123456");
+        assert_eq!(format_float(1_234_567.0), "1.23457e+06");
+        assert_eq!(format_float(999_999.9), "1e+06");
+        assert_eq!(format_float(f32::NAN), "nan");
+        assert_eq!(format_float(f32::INFINITY), "inf");
+        assert_eq!(format_float(f32::NEG_INFINITY), "-inf");
+    }
+
+    #[test]
+    fn test_float_to_complex() {
+        for value in [0.0, 16.0, 1.5, -1.0, 0.25, 123.456, -0.001] {
+            let complex = float_to_complex(value);
+            assert!((complex_to_float(complex) - value).abs() < 0.0001);
+        }
+    }
+}
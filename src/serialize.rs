@@ -0,0 +1,50 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::{Cdata, Element, Node, XmlDocument};
+
+impl Serialize for XmlDocument {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("XmlDocument", 2)?;
+        s.serialize_field("namespaces", self.get_namespaces())?;
+        s.serialize_field("root", self.root())?;
+        s.end()
+    }
+}
+
+// Nodes are written without a tag as elements and cdata can already be told
+// apart by their fields.
+impl Serialize for Node {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Node::Element(e) => e.serialize(serializer),
+            Node::Cdata(c) => c.serialize(serializer),
+        }
+    }
+}
+
+impl Serialize for Element {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Element", 3)?;
+        s.serialize_field("tag", self.get_tag())?;
+        s.serialize_field("attributes", &Attributes(self))?;
+        s.serialize_field("children", self.get_children())?;
+        s.end()
+    }
+}
+
+// The attributes of an element, serialized as a map from name to value.
+struct Attributes<'a>(&'a Element);
+
+impl Serialize for Attributes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.get_attributes())
+    }
+}
+
+impl Serialize for Cdata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Cdata", 1)?;
+        s.serialize_field("cdata", self.get_data())?;
+        s.end()
+    }
+}
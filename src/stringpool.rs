@@ -1,12 +1,13 @@
-use deku::bitvec::{BitSlice, Msb0};
+use deku::bitvec::{BitField, BitSlice, Msb0};
 use deku::prelude::*;
 
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 use byteorder::ByteOrder;
 use byteorder::LittleEndian;
-use std::io::Read;
-use std::rc::Rc;
 
-use crate::binaryxml::ChunkHeader;
+use crate::binaryxml::{ChunkHeader, ResourceType};
 use crate::ParseError;
 
 #[derive(Debug, DekuRead, DekuWrite)]
@@ -19,48 +20,78 @@ pub(crate) struct StringPoolHeader {
     pub(crate) style_start: u32,
 }
 
+impl StringPoolHeader {
+    pub(crate) fn is_utf8(&self) -> bool {
+        (self.flags & (1 << 8)) != 0
+    }
+}
+
 #[derive(Debug, DekuRead)]
 pub(crate) struct StringPool {
     pub(crate) header: StringPoolHeader,
     #[deku(reader = "StringPool::read_strings(header, deku::rest)")]
-    pub(crate) strings: Vec<Rc<String>>,
+    pub(crate) strings: Vec<Arc<String>>,
 }
 
 type DekuRest = BitSlice<u8, Msb0>;
 impl StringPool {
     fn read_strings<'a>(
         header: &StringPoolHeader,
-        mut rest: &'a DekuRest,
-    ) -> Result<(&'a DekuRest, Vec<Rc<String>>), DekuError> {
-        const STRINGPOOL_HEADER_SIZE: usize = std::mem::size_of::<StringPoolHeader>();
-
-        assert_eq!(header.style_count, 0);
-
-        let flag_is_utf8 = (header.flags & (1 << 8)) != 0;
+        rest: &'a DekuRest,
+    ) -> Result<(&'a DekuRest, Vec<Arc<String>>), DekuError> {
+        const STRINGPOOL_HEADER_SIZE: usize = core::mem::size_of::<StringPoolHeader>();
 
-        let s = usize::try_from(header.chunk_header.size).unwrap() - STRINGPOOL_HEADER_SIZE;
+        let s = usize::try_from(header.chunk_header.size)
+            .unwrap()
+            .checked_sub(STRINGPOOL_HEADER_SIZE)
+            .ok_or_else(|| DekuError::Parse("Invalid string pool size".to_string()))?;
 
-        let mut string_pool_data = vec![0; s];
-        rest.read_exact(&mut string_pool_data).unwrap();
+        if rest.len() < s * 8 {
+            return Err(DekuError::Parse("String pool is truncated".to_string()));
+        }
+        let (string_pool_bits, rest) = rest.split_at(s * 8);
+        let string_pool_data: Vec<u8> = string_pool_bits
+            .chunks(8)
+            .map(BitField::load_be::<u8>)
+            .collect();
 
         // Parse string offsets
         let num_offsets = usize::try_from(header.string_count).unwrap();
-        let offsets = parse_offsets(&string_pool_data, num_offsets);
+        let offsets = parse_offsets(&string_pool_data, num_offsets)
+            .map_err(|e| DekuError::Parse(e.to_string()))?;
+
+        // Offsets in the header are relative to the start of the chunk.
+        let data_offset = |offset: u32| {
+            usize::try_from(offset)
+                .ok()
+                .and_then(|offset| offset.checked_sub(STRINGPOOL_HEADER_SIZE))
+                .filter(|offset| *offset <= string_pool_data.len())
+                .ok_or_else(|| DekuError::Parse("Invalid string pool offset".to_string()))
+        };
 
-        let string_data_start =
-            usize::try_from(header.string_start).unwrap() - STRINGPOOL_HEADER_SIZE;
-        let string_data = &string_pool_data[string_data_start..];
+        let string_data_start = data_offset(header.string_start)?;
+
+        // Style spans are stored after the string data. They are not needed
+        // to decode the strings so the style data is skipped over.
+        let string_data_end = if header.style_count == 0 {
+            string_pool_data.len()
+        } else {
+            data_offset(header.style_start)?
+        };
+        let string_data = string_pool_data
+            .get(string_data_start..string_data_end)
+            .ok_or_else(|| DekuError::Parse("Invalid string pool offset".to_string()))?;
 
         let mut strings = Vec::with_capacity(usize::try_from(header.string_count).unwrap());
 
-        let parse_fn = if flag_is_utf8 {
+        let parse_fn = if header.is_utf8() {
             parse_utf8_string
         } else {
             parse_utf16_string
         };
 
         for offset in offsets {
-            strings.push(Rc::new(
+            strings.push(Arc::new(
                 parse_fn(string_data, usize::try_from(offset).unwrap())
                     .map_err(|e| DekuError::Parse(e.to_string()))?,
             ));
@@ -69,7 +100,44 @@ impl StringPool {
         Ok((rest, strings))
     }
 
-    pub(crate) fn get(&self, i: usize) -> Option<Rc<String>> {
+    pub(crate) fn encode<S: AsRef<str>>(strings: &[S]) -> Result<Vec<u8>, DekuError> {
+        const STRINGPOOL_HEADER_SIZE: u16 = 28;
+
+        let mut offsets = Vec::with_capacity(strings.len() * 4);
+        let mut string_data = Vec::new();
+        for s in strings {
+            offsets.extend_from_slice(&u32::try_from(string_data.len()).unwrap().to_le_bytes());
+            encode_utf16_string(s.as_ref(), &mut string_data);
+        }
+
+        // Chunks are aligned to 4 bytes
+        while string_data.len() % 4 != 0 {
+            string_data.push(0);
+        }
+
+        let string_count = u32::try_from(strings.len()).unwrap();
+        let string_start = u32::from(STRINGPOOL_HEADER_SIZE) + string_count * 4;
+
+        let header = StringPoolHeader {
+            chunk_header: ChunkHeader {
+                typ: ResourceType::StringPool,
+                header_size: STRINGPOOL_HEADER_SIZE,
+                size: string_start + u32::try_from(string_data.len()).unwrap(),
+            },
+            string_count,
+            style_count: 0,
+            flags: 0,
+            string_start,
+            style_start: 0,
+        };
+
+        let mut data = header.to_bytes()?;
+        data.extend_from_slice(&offsets);
+        data.extend_from_slice(&string_data);
+        Ok(data)
+    }
+
+    pub(crate) fn get(&self, i: usize) -> Option<Arc<String>> {
         if u32::try_from(i).unwrap() == u32::MAX {
             return None;
         }
@@ -78,38 +146,120 @@ impl StringPool {
     }
 }
 
-fn parse_offsets(string_data: &[u8], count: usize) -> Vec<u32> {
-    let mut offsets = Vec::with_capacity(count);
+// Returns the number of bytes of the string pool chunk `data` that are used
+// by its header, offsets, strings and styles, rounded up to a multiple of
+// four. Anything after that up to the chunk size is unused. Offsets are
+// resolved the way Android does, relative to the declared header size.
+#[cfg(feature = "raw")]
+pub(crate) fn content_size(data: &[u8]) -> Result<usize, ParseError> {
+    let (_, header) = StringPoolHeader::from_bytes((data, 0)).map_err(ParseError::DekuError)?;
+    let string_count = usize::try_from(header.string_count).unwrap();
+    let style_count = usize::try_from(header.style_count).unwrap();
+    let string_start = usize::try_from(header.string_start).unwrap();
+    let style_start = usize::try_from(header.style_start).unwrap();
+
+    let offsets_start = usize::from(header.chunk_header.header_size);
+    let offsets = parse_offsets(
+        data.get(offsets_start..).unwrap_or_default(),
+        string_count.saturating_add(style_count),
+    )?;
+    let mut end = offsets_start + offsets.len() * 4;
+
+    for (i, offset) in offsets.iter().enumerate() {
+        let offset = usize::try_from(*offset).unwrap();
+        if i < string_count {
+            let start = string_start.saturating_add(offset);
+            let (len, size) = if header.is_utf8() {
+                let (_, char_len_size) = parse_utf8_length(data, start)?;
+                let (len, byte_len_size) = parse_utf8_length(data, start + char_len_size)?;
+                (len + 1, char_len_size + byte_len_size)
+            } else {
+                let (len, len_size) = parse_utf16_length(data, start)?;
+                ((len + 1) * 2, len_size)
+            };
+            get_bytes(data, start + size, len)?;
+            end = end.max(start + size + len);
+        } else {
+            // Each style is a list of 12 byte spans terminated by 0xFFFFFFFF.
+            let mut pos = style_start.saturating_add(offset);
+            while LittleEndian::read_u32(get_bytes(data, pos, 4)?) != u32::MAX {
+                pos += 12;
+            }
+            end = end.max(pos + 4);
+        }
+    }
 
-    for i in 0..count {
-        let index = i * 4;
-        let offset = LittleEndian::read_u32(&string_data[index..index + 4]);
-        offsets.push(offset);
+    // aapt ends the style data with extra terminators.
+    if style_count > 0 {
+        while get_bytes(data, end, 4).is_ok_and(|b| LittleEndian::read_u32(b) == u32::MAX) {
+            end += 4;
+        }
     }
 
-    offsets
+    Ok(end.next_multiple_of(4))
+}
+
+// Returns `len` bytes of `data` starting at `offset`. The offsets come from
+// the file, so they are checked instead of trusted.
+fn get_bytes(data: &[u8], offset: usize, len: usize) -> Result<&[u8], ParseError> {
+    offset
+        .checked_add(len)
+        .and_then(|end| data.get(offset..end))
+        .ok_or(ParseError::StringOffsetOutOfBounds(offset))
+}
+
+fn parse_offsets(string_data: &[u8], count: usize) -> Result<Vec<u32>, ParseError> {
+    let offsets = get_bytes(string_data, 0, count.saturating_mul(4))?;
+    Ok(offsets
+        .chunks_exact(4)
+        .map(LittleEndian::read_u32)
+        .collect())
 }
 
 fn parse_utf16_string(string_data: &[u8], offset: usize) -> Result<String, ParseError> {
-    let len = LittleEndian::read_u16(&string_data[offset..offset + 2]);
+    let (len, len_size) = parse_utf16_length(string_data, offset)?;
+    let string_start = offset + len_size;
+
+    let s: Vec<u16> = get_bytes(string_data, string_start, len * 2)?
+        .chunks_exact(2)
+        .map(LittleEndian::read_u16)
+        .collect();
+
+    let s = String::from_utf16(&s).map_err(ParseError::Utf16StringParseError)?;
+    Ok(s)
+}
 
-    // Handles the case where the string is > 32767 characters
+// Returns the decoded length and the number of bytes it was encoded in.
+fn parse_utf16_length(string_data: &[u8], offset: usize) -> Result<(usize, usize), ParseError> {
+    let len = LittleEndian::read_u16(get_bytes(string_data, offset, 2)?);
+
+    // Handles the case where the string is > 32767 characters. The length
+    // is then spread across two u16 values, with the high bit of the first
+    // one set.
     if is_high_bit_set_16(len) {
-        unimplemented!()
+        let low = LittleEndian::read_u16(get_bytes(string_data, offset + 2, 2)?);
+        let len = (u32::from(len & 0x7fff) << 16) | u32::from(low);
+        Ok((usize::try_from(len).unwrap(), 4))
+    } else {
+        Ok((usize::from(len), 2))
     }
+}
 
-    // This needs to change if we ever implement support for long strings
-    let string_start = offset + 2;
+fn encode_utf16_string(s: &str, output: &mut Vec<u8>) {
+    let units: Vec<u16> = s.encode_utf16().collect();
+    let len = u32::try_from(units.len()).unwrap();
 
-    let mut s = Vec::with_capacity(len.into());
-    for i in 0..len {
-        let index = string_start + usize::try_from(i * 2).unwrap();
-        let char = LittleEndian::read_u16(&string_data[index..index + 2]);
-        s.push(char);
+    if len > 0x7fff {
+        output.extend_from_slice(&(0x8000 | u16::try_from(len >> 16).unwrap()).to_le_bytes());
     }
+    output.extend_from_slice(&u16::try_from(len & 0xffff).unwrap().to_le_bytes());
 
-    let s = String::from_utf16(&s).map_err(ParseError::Utf16StringParseError)?;
-    Ok(s)
+    for unit in units {
+        output.extend_from_slice(&unit.to_le_bytes());
+    }
+
+    // Null terminator
+    output.extend_from_slice(&[0, 0]);
 }
 
 fn is_high_bit_set_16(input: u16) -> bool {
@@ -117,29 +267,100 @@ fn is_high_bit_set_16(input: u16) -> bool {
 }
 
 fn parse_utf8_string(string_data: &[u8], offset: usize) -> Result<String, ParseError> {
-    let len = string_data[offset + 1];
+    // UTF-8 strings are prefixed by their length in characters followed by
+    // their length in bytes. Only the byte length is needed to decode the
+    // string.
+    let (_, char_len_size) = parse_utf8_length(string_data, offset)?;
+    let (len, byte_len_size) = parse_utf8_length(string_data, offset + char_len_size)?;
 
-    // Handles the case where the length value has high bit set
-    // Not quite clear if the UTF-8 encoding actually has this but
-    // perform the check anyway...
-    if is_high_bit_set_8(len) {
-        unimplemented!()
-    }
-
-    // This needs to change if we ever implement support for long strings
-    let string_start = offset + 2;
-
-    let mut s = Vec::with_capacity(len.into());
-    for i in 0..len {
-        let index = string_start + usize::try_from(i).unwrap();
-        let char = string_data[index];
-        s.push(char);
-    }
+    let string_start = offset + char_len_size + byte_len_size;
+    let s = get_bytes(string_data, string_start, len)?.to_vec();
 
     let s = String::from_utf8(s).map_err(ParseError::Utf8StringParseError)?;
     Ok(s)
 }
 
+// Returns the decoded length and the number of bytes it was encoded in.
+fn parse_utf8_length(string_data: &[u8], offset: usize) -> Result<(usize, usize), ParseError> {
+    let len = get_bytes(string_data, offset, 1)?[0];
+
+    // Handles the case where the length is > 127. The length is then
+    // spread across two bytes, with the high bit of the first one set.
+    if is_high_bit_set_8(len) {
+        let low = get_bytes(string_data, offset + 1, 1)?[0];
+        Ok(((usize::from(len & 0x7f) << 8) | usize::from(low), 2))
+    } else {
+        Ok((usize::from(len), 1))
+    }
+}
+
 fn is_high_bit_set_8(input: u8) -> bool {
     input & (1 << 7) != 0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+
+    #[test]
+    fn test_parse_long_utf16_string() {
+        let len: u32 = 40000;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&(0x8000 | u16::try_from(len >> 16).unwrap()).to_le_bytes());
+        data.extend_from_slice(&u16::try_from(len & 0xffff).unwrap().to_le_bytes());
+        for _ in 0..len {
+            data.extend_from_slice(&u16::from(b'a').to_le_bytes());
+        }
+        data.extend_from_slice(&[0, 0]);
+
+        let s = parse_utf16_string(&data, 0).unwrap();
+        assert_eq!(s.len(), 40000);
+        assert!(s.chars().all(|c| c == 'a'));
+    }
+
+    #[test]
+    fn test_invalid_style_start() {
+        let mut data = StringPool::encode(&["a", "b"]).unwrap();
+        // Set style_count to 1 and point style_start before string_start.
+        data[12..16].copy_from_slice(&1u32.to_le_bytes());
+        data[24..28].copy_from_slice(&30u32.to_le_bytes());
+        assert!(StringPool::from_bytes((&data, 0)).is_err());
+
+        data[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(StringPool::from_bytes((&data, 0)).is_err());
+    }
+
+    #[test]
+    fn test_parse_long_utf8_string() {
+        let class_name = format!("com.example.{}", "a".repeat(188));
+        assert_eq!(class_name.len(), 200);
+
+        let mut data = vec![0x80, 200, 0x80, 200];
+        data.extend_from_slice(class_name.as_bytes());
+        data.push(0);
+
+        assert_eq!(parse_utf8_string(&data, 0).unwrap(), class_name);
+    }
+
+    #[test]
+    fn test_string_pool_long_utf16_string() {
+        let long = "x".repeat(40000);
+        let data = StringPool::encode(&["manifest", &long]).unwrap();
+
+        let string_pool = StringPool::try_from(data.as_slice()).unwrap();
+        assert_eq!(string_pool.get(0).unwrap().as_str(), "manifest");
+        assert_eq!(string_pool.get(1).unwrap().as_str(), long);
+    }
+
+    #[test]
+    fn test_parse_utf8_string_uses_byte_length() {
+        // 2 characters, 6 bytes
+        let mut data = vec![2, 6];
+        data.extend_from_slice("应用".as_bytes());
+        data.push(0);
+
+        assert_eq!(parse_utf8_string(&data, 0).unwrap(), "应用");
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Element, XmlDocument};
+
+// Components with an intent filter must set `android:exported` when
+// targeting Android 12 or later.
+const EXPORTED_REQUIRED_SDK: u32 = 31;
+
+///A common problem found in an `AndroidManifest.xml` by
+///[`validate_manifest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestWarning {
+    ///A `<uses-permission>` or `<uses-permission-sdk-23>` element has no
+    ///`android:name`.
+    MissingPermissionName { line: u32 },
+    ///A component with an intent filter does not set `android:exported`,
+    ///which is required when targeting API 31 or later.
+    MissingExported {
+        tag: String,
+        name: Option<String>,
+        line: u32,
+    },
+    ///The application sets `android:debuggable="true"`.
+    Debuggable { line: u32 },
+}
+
+impl fmt::Display for ManifestWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestWarning::MissingPermissionName { line } => {
+                write!(f, "Permission without android:name on line {}", line)
+            }
+            ManifestWarning::MissingExported {
+                tag,
+                name: Some(name),
+                line,
+            } => write!(
+                f,
+                "<{} android:name=\"{}\"> has an intent filter but no android:exported on line {}",
+                tag, name, line
+            ),
+            ManifestWarning::MissingExported {
+                tag,
+                name: None,
+                line,
+            } => write!(
+                f,
+                "<{}> has an intent filter but no android:exported on line {}",
+                tag, line
+            ),
+            ManifestWarning::Debuggable { line } => {
+                write!(f, "Application is debuggable on line {}", line)
+            }
+        }
+    }
+}
+
+///Checks a parsed `AndroidManifest.xml` for common problems that security
+///scanners look for. All problems are returned, in document order. A
+///document whose root element is not `<manifest>` has no warnings.
+///```rust
+///use axmldecoder::{parse, validate_manifest, ManifestWarning};
+///# use axmldecoder::ParseError;
+///let data = include_bytes!("../examples/AndroidManifest.xml");
+///let warnings = validate_manifest(&parse(data)?);
+///assert_eq!(warnings, [ManifestWarning::Debuggable { line: 6 }]);
+///# Ok::<(), ParseError>(())
+///```
+#[must_use]
+pub fn validate_manifest(doc: &XmlDocument) -> Vec<ManifestWarning> {
+    let mut warnings = Vec::new();
+    let Some(root) = doc
+        .root()
+        .as_element()
+        .filter(|e| e.get_tag() == "manifest")
+    else {
+        return warnings;
+    };
+
+    let target_sdk = root
+        .find_child_by_tag("uses-sdk")
+        .and_then(|e| e.get_attribute_as_u32("android:targetSdkVersion"));
+
+    for e in root.iter_children_elements() {
+        match e.get_tag() {
+            "uses-permission" | "uses-permission-sdk-23"
+                if e.get_attribute("android:name").is_none() =>
+            {
+                warnings.push(ManifestWarning::MissingPermissionName {
+                    line: e.get_line_number(),
+                });
+            }
+            "application" => check_application(e, target_sdk, &mut warnings),
+            _ => {}
+        }
+    }
+
+    warnings
+}
+
+fn check_application(e: &Element, target_sdk: Option<u32>, warnings: &mut Vec<ManifestWarning>) {
+    if e.get_attribute_as_bool("android:debuggable") == Some(true) {
+        warnings.push(ManifestWarning::Debuggable {
+            line: e.get_line_number(),
+        });
+    }
+
+    if !matches!(target_sdk, Some(sdk) if sdk >= EXPORTED_REQUIRED_SDK) {
+        return;
+    }
+    for component in e.iter_children_elements() {
+        if !matches!(
+            component.get_tag(),
+            "activity" | "activity-alias" | "service" | "receiver"
+        ) {
+            continue;
+        }
+        if component.find_child_by_tag("intent-filter").is_some()
+            && component.get_attribute("android:exported").is_none()
+        {
+            warnings.push(ManifestWarning::MissingExported {
+                tag: component.get_tag().to_string(),
+                name: component.get_attribute("android:name").map(str::to_string),
+                line: component.get_line_number(),
+            });
+        }
+    }
+}
@@ -1,189 +1,1164 @@
-use indexmap::IndexMap;
-use std::rc::Rc;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
 
-use crate::binaryxml::{
-    BinaryXmlDocument, XmlCdata, XmlNodeType, XmlStartElement, XmlStartNameSpace,
-};
-use crate::stringpool::StringPool;
-use crate::ParseError;
+use crate::encoder;
+use crate::event::EventSource;
+use crate::printer;
+use crate::resource_value::AttributeValue;
+use crate::{EncodeError, Event, IndexMap, IndexSet, ParseError, ParseOptions, ANDROID_NAMESPACE};
 
 ///Struct representing a parsed XML document.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct XmlDocument {
-    root: Option<Node>,
+    root: Node,
+    extra_roots: Vec<Node>,
+    namespaces: IndexMap<String, String>,
+    warnings: Vec<String>,
+    strings: SharedStrings,
 }
 
 impl XmlDocument {
-    pub(crate) fn new(binaryxml: BinaryXmlDocument) -> Result<Self, ParseError> {
-        let string_pool = binaryxml.string_pool;
-        let resource_map = binaryxml.resource_map;
-
-        let mut namespaces = IndexMap::new();
-
-        // There are some files without the XmlStartNameSpace element.
-        // We should assume that the android namespace is always present even
-        // if not explicitly defined in the document.
-        //
-        // examples/AndroidManifestNoNamespace.xml
-        namespaces.insert(
-            Rc::new("http://schemas.android.com/apk/res/android".to_string()),
-            Rc::new("android".to_string()),
-        );
+    ///Creates a document with `root` as its root element. Only the
+    ///`android` namespace is declared.
+    ///```rust
+    ///use axmldecoder::{encode, parse, Element, Node, XmlDocument};
+    ///let mut manifest = Element::new("manifest");
+    ///manifest.set_attribute("package", "com.example");
+    ///manifest.add_child(Node::Element(Element::new("application")));
+    ///
+    ///let doc = XmlDocument::new(manifest);
+    ///let decoded = parse(&encode(&doc)?)?;
+    ///assert!(decoded.find_first_element_by_tag("application").is_some());
+    ///# Ok::<(), Box<dyn std::error::Error>>(())
+    ///```
+    #[must_use]
+    pub fn new(mut root: Element) -> Self {
+        let mut namespaces = IndexMap::default();
+        namespaces.insert("android".to_string(), ANDROID_NAMESPACE.to_string());
 
-        let mut element_tracker: Vec<Element> = Vec::new();
-        for node in binaryxml.elements {
-            match node.element {
-                XmlNodeType::XmlStartNameSpace(e) => {
-                    let (uri, prefix) = Self::process_start_namespace(&e, &string_pool)?;
-                    namespaces.insert(uri.clone(), prefix.clone());
-                }
-                XmlNodeType::XmlEndNameSpace(_) => {}
-                XmlNodeType::XmlStartElement(e) => {
-                    element_tracker.push(Self::process_start_element(
-                        &e,
-                        &string_pool,
-                        &namespaces,
-                        &resource_map.resource_ids,
-                    )?);
-                }
-                XmlNodeType::XmlEndElement(_) => {
-                    let e = element_tracker.pop().unwrap();
+        let mut interner = Interner::default();
+        for e in core::iter::once(&root).chain(root.iter_descendants()) {
+            interner.intern(e.tag.clone());
+            for (name, value) in &e.attributes {
+                interner.intern(name.clone());
+                interner.intern(value.clone());
+            }
+        }
+        let strings = SharedStrings(Some(Arc::new(interner)));
+        root.adopt(&strings);
 
-                    if element_tracker.is_empty() {
-                        return Ok(XmlDocument {
-                            root: Some(Node::Element(e)),
-                        });
-                    }
+        Self {
+            root: Node::Element(root),
+            extra_roots: Vec::new(),
+            namespaces,
+            warnings: Vec::new(),
+            strings,
+        }
+    }
 
-                    element_tracker
-                        .last_mut()
-                        .unwrap()
-                        .insert_children(Node::Element(e));
-                }
-                XmlNodeType::XmlCdata(e) => {
-                    let cdata = Self::process_cdata(&e, &string_pool)?;
-                    element_tracker
-                        .last_mut()
-                        .unwrap()
-                        .insert_children(Node::Cdata(cdata));
+    pub(crate) fn from_reader(
+        reader: &mut impl EventSource,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseError> {
+        let mut builder = TreeBuilder::new(options.max_depth);
+        // Warnings from the reader are copied as they are found, so that all
+        // warnings are in document order.
+        let mut reported = 0;
+        while let Some(event) = reader.next() {
+            builder
+                .warnings
+                .extend_from_slice(&reader.warnings()[reported..]);
+            reported = reader.warnings().len();
+
+            if let Err(e) = event.and_then(|event| builder.push(event)) {
+                // Some packers append junk nodes after the root element.
+                // The document itself is complete at that point, so the rest
+                // is ignored. Input that ends early, cannot be read or
+                // breaks a limit is still an error.
+                if builder.roots.is_empty() || !is_node_error(&e) {
+                    return Err(e);
                 }
-            };
+                builder.warnings.push(format!(
+                    "Ignored invalid data after the root element: {}",
+                    e
+                ));
+                break;
+            }
         }
+        builder
+            .warnings
+            .extend_from_slice(&reader.warnings()[reported..]);
 
-        Ok(Self { root: None })
+        builder.finish(options.lenient)
     }
 
-    ///Returns the root [Element] of the XML document.
+    // Builds a document from events that are known to be balanced, such as
+    // those written by `DocumentBuilder`.
+    pub(crate) fn from_events(events: Vec<Event>) -> Result<Self, ParseError> {
+        let mut builder = TreeBuilder::new(None);
+        for event in events {
+            builder.push(event)?;
+        }
+        builder.finish(false)
+    }
+
+    ///Returns the root node of the XML document. Parsing fails with
+    ///[`ParseError::NoRootElement`] if the document has no elements, so
+    ///there is always a root.
     #[must_use]
-    pub fn get_root(&self) -> &Option<Node> {
+    pub fn root(&self) -> &Node {
         &self.root
     }
 
-    fn process_cdata(e: &XmlCdata, string_pool: &StringPool) -> Result<Cdata, ParseError> {
-        Ok(Cdata {
-            data: string_pool
-                .get(usize::try_from(e.data).unwrap())
-                .ok_or(ParseError::StringNotFound(e.data))?
-                .to_string(),
+    ///Returns the root node of the XML document, allowing it to be
+    ///modified.
+    #[must_use]
+    pub fn root_mut(&mut self) -> &mut Node {
+        &mut self.root
+    }
+
+    ///Replaces the root node of the XML document, returning the previous
+    ///one. The declared namespaces and any nodes after the root are kept.
+    pub fn set_root(&mut self, mut root: Node) -> Node {
+        if let Node::Element(e) = &mut root {
+            e.adopt(&self.strings);
+        }
+        core::mem::replace(&mut self.root, root)
+    }
+
+    ///Returns the root node of the XML document, which is always present.
+    #[deprecated(since = "0.7.0", note = "use `root`, which is always present")]
+    #[must_use]
+    pub fn get_root(&self) -> Option<&Node> {
+        Some(&self.root)
+    }
+
+    ///Returns the top-level nodes that follow the root element. Some packers
+    ///append a decoy second `<manifest>` after the real one. Android only
+    ///reads the first root, so these are kept separate.
+    #[must_use]
+    pub fn get_extra_roots(&self) -> &[Node] {
+        &self.extra_roots
+    }
+
+    ///Returns the first [Element] with the given tag, searching the whole
+    ///document depth-first starting from the root.
+    #[deprecated(since = "0.7.0", note = "use `find_first_element_by_tag`")]
+    #[must_use]
+    pub fn find_element_by_tag_recursive(&self, tag: &str) -> Option<&Element> {
+        self.find_first_element_by_tag(tag)
+    }
+
+    ///Returns every [Element] in the document with the given tag, in
+    ///depth-first document order.
+    #[must_use]
+    pub fn find_elements_by_tag(&self, tag: &str) -> Vec<&Element> {
+        self.iter_elements()
+            .filter(|e| e.get_tag() == tag)
+            .collect()
+    }
+
+    ///Returns the first [Element] in the document with the given tag,
+    ///stopping the traversal as soon as it is found.
+    #[must_use]
+    pub fn find_first_element_by_tag(&self, tag: &str) -> Option<&Element> {
+        self.iter_elements().find(|e| e.get_tag() == tag)
+    }
+
+    ///Returns an iterator over every [Element] in the document, visiting
+    ///the root first and then its descendants depth-first in document
+    ///order.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let doc = parse(data)?;
+    ///let tags: Vec<&str> = doc.iter_elements().map(|e| e.get_tag()).collect();
+    ///assert_eq!(tags[..3], ["manifest", "application", "activity"]);
+    ///# Ok::<(), ParseError>(())
+    ///```
+    pub fn iter_elements(&self) -> impl Iterator<Item = &Element> {
+        self.root()
+            .as_element()
+            .into_iter()
+            .flat_map(|root| core::iter::once(root).chain(root.iter_descendants()))
+    }
+
+    ///Returns an iterator over every [Element] in the document with the
+    ///given tag, in depth-first document order.
+    pub fn iter_elements_by_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.iter_elements().filter(move |e| e.get_tag() == tag)
+    }
+
+    ///Returns an iterator over the elements matching a `/` separated path
+    ///of tags, starting with the tag of the root element. Each further tag
+    ///must match a direct child, so `manifest/application/activity` yields
+    ///every activity of every application.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let doc = parse(data)?;
+    ///let activity = doc.find_element_by_path("manifest/application/activity").unwrap();
+    ///assert_eq!(activity.get_attribute("android:name"), Some("TCActivity"));
+    ///assert!(doc.find_element_by_path("application/activity").is_none());
+    ///# Ok::<(), ParseError>(())
+    ///```
+    pub fn iter_elements_by_path<'a, 'b>(
+        &'a self,
+        path: &'b str,
+    ) -> impl Iterator<Item = &'a Element> + 'b
+    where
+        'a: 'b,
+    {
+        let mut tags = path.split('/');
+        let first = tags.next().unwrap_or_default();
+        self.root()
+            .as_element()
+            .filter(|root| root.get_tag() == first)
+            .into_iter()
+            .flat_map(move |root| root.iter_tag_path(tags.clone()))
+    }
+
+    ///Returns the first element matching a `/` separated path of tags. See
+    ///[`iter_elements_by_path`](Self::iter_elements_by_path).
+    #[must_use]
+    pub fn find_element_by_path(&self, path: &str) -> Option<&Element> {
+        self.iter_elements_by_path(path).next()
+    }
+
+    ///Returns the first element reached by following `path` from the root
+    ///element, one tag per level. Unlike
+    ///[`find_element_by_path`](Self::find_element_by_path), the path does
+    ///not include the tag of the root itself.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let doc = parse(data)?;
+    ///let action = doc.find_by_path(&["application", "activity", "intent-filter", "action"]);
+    ///assert_eq!(
+    ///    action.and_then(|e| e.get_attribute("android:name")),
+    ///    Some("android.intent.action.MAIN")
+    ///);
+    ///# Ok::<(), ParseError>(())
+    ///```
+    #[must_use]
+    pub fn find_by_path(&self, path: &[&str]) -> Option<&Element> {
+        self.root().as_element()?.find_descendant_by_tag_path(path)
+    }
+
+    ///Returns the number of elements in the document, including the root.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let doc = parse(data)?;
+    ///assert_eq!(doc.get_element_count(), 6);
+    ///assert_eq!(doc.get_depth(), 5);
+    ///# Ok::<(), ParseError>(())
+    ///```
+    #[must_use]
+    pub fn get_element_count(&self) -> usize {
+        self.iter_elements().count()
+    }
+
+    ///Returns the maximum nesting depth of the elements in the document.
+    ///The root element has a depth of 1, its children 2, and so on.
+    #[must_use]
+    pub fn get_depth(&self) -> usize {
+        let Some(root) = self.root().as_element() else {
+            return 0;
+        };
+        let mut depth = 0;
+        let mut stack = vec![(root, 1)];
+        while let Some((e, d)) = stack.pop() {
+            depth = depth.max(d);
+            stack.extend(e.iter_children_elements().map(|c| (c, d + 1)));
+        }
+        depth
+    }
+
+    ///Returns the number of character data nodes in the document.
+    #[must_use]
+    pub fn get_cdata_count(&self) -> usize {
+        self.root().as_element().map_or(0, |root| {
+            root.iter_descendant_nodes()
+                .filter(|n| n.as_cdata().is_some())
+                .count()
         })
     }
 
-    fn process_start_namespace(
-        e: &XmlStartNameSpace,
-        string_pool: &StringPool,
-    ) -> Result<(Rc<String>, Rc<String>), ParseError> {
-        let uri = string_pool
-            .get(usize::try_from(e.uri).unwrap())
-            .ok_or(ParseError::StringNotFound(e.uri))?;
-        let prefix = string_pool
-            .get(usize::try_from(e.prefix).unwrap())
-            .ok_or(ParseError::StringNotFound(e.prefix))?;
-
-        Ok((uri, prefix))
-    }
-
-    fn process_start_element(
-        e: &XmlStartElement,
-        string_pool: &StringPool,
-        namespaces: &IndexMap<Rc<String>, Rc<String>>,
-        resource_map: &[u32],
-    ) -> Result<Element, ParseError> {
-        let name = string_pool
-            .get(usize::try_from(e.attr_ext.name).unwrap())
-            .ok_or(ParseError::StringNotFound(e.attr_ext.name))?;
-        let name = (*name).clone();
-
-        let mut attributes: IndexMap<String, String> = IndexMap::new();
-
-        // Specially handle the <manifest> element by adding the namespace
-        // attributes to it.
-        if name == "manifest" {
-            for (url, name) in namespaces.iter() {
-                attributes.insert(format!("xmlns:{}", name), url.to_string());
-            }
+    ///Returns the number of distinct tags, attribute names and attribute
+    ///values held by the document. Each is stored once, however many
+    ///elements use it. Strings set on elements afterwards that the document
+    ///did not already hold are not counted.
+    #[must_use]
+    pub fn get_distinct_string_count(&self) -> usize {
+        self.strings.len()
+    }
+
+    ///Returns the total number of attributes across every element in the
+    ///document. The `xmlns:` attributes added to a `<manifest>` root are
+    ///namespace declarations and are not counted.
+    #[must_use]
+    pub fn get_attribute_count(&self) -> usize {
+        self.iter_elements()
+            .map(|e| {
+                e.attributes
+                    .keys()
+                    .filter(|k| !k.starts_with("xmlns:"))
+                    .count()
+            })
+            .sum()
+    }
+
+    ///Returns every namespace declared in the document, mapping prefix to
+    ///URI.
+    ///
+    ///The `android` prefix is always present, even if the document does not
+    ///declare it. Use [`Element::get_declared_namespaces`] to find where each
+    ///namespace was declared.
+    #[must_use]
+    pub fn get_namespaces(&self) -> &IndexMap<String, String> {
+        &self.namespaces
+    }
+
+    ///Returns a description of each problem that was worked around while
+    ///parsing the document, such as chunks of unknown types that were
+    ///skipped.
+    #[must_use]
+    pub fn get_warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    ///Returns the document as indented text XML. This is the same as the
+    ///[`Display`](std::fmt::Display) output.
+    #[must_use]
+    pub fn to_xml_string(&self) -> String {
+        self.to_string()
+    }
+
+    ///Returns the document encoded in Android's binary XML format. This is
+    ///the same as [`encode`](crate::encode).
+    ///
+    /// # Errors
+    ///
+    /// Will return `EncodeError` if the document uses a namespace prefix
+    /// that is never declared.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let mut doc = parse(data)?;
+    ///let manifest = doc.root_mut().as_element_mut().unwrap();
+    ///manifest.set_attribute("android:versionCode", 2);
+    ///
+    ///let patched = parse(&doc.to_binary()?)?;
+    ///assert_eq!(patched, doc);
+    ///# Ok::<(), Box<dyn std::error::Error>>(())
+    ///```
+    pub fn to_binary(&self) -> Result<Vec<u8>, EncodeError> {
+        encoder::encode(self)
+    }
+
+    ///Returns the document as a JSON object of the form
+    ///`{"namespaces": {...}, "root": {...}}`.
+    ///
+    ///`namespaces` maps each prefix to its URI as returned by
+    ///[`XmlDocument::get_namespaces`]. Elements are written as
+    ///`{"tag": "...", "attributes": {...}, "children": [...]}`, where
+    ///`attributes` maps each name to its string value in document order,
+    ///and character data as `{"cdata": "..."}`. This is the same as the
+    ///`serde::Serialize` output.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let json = parse(data)?.to_json_string();
+    ///assert!(json.starts_with(r#"{"namespaces":{"android":"#));
+    ///# Ok::<(), ParseError>(())
+    ///```
+    #[cfg(feature = "serde_json")]
+    #[must_use]
+    pub fn to_json_string(&self) -> String {
+        // Every map key is a string, so serializing cannot fail.
+        serde_json::to_string(self).unwrap()
+    }
+
+    ///Returns the document as text XML without any whitespace between
+    ///elements.
+    #[must_use]
+    pub fn to_compact_xml(&self) -> String {
+        let mut s = String::new();
+        printer::write_document(self, None, &mut s).unwrap();
+        s
+    }
+
+    ///Writes the document as text XML to `w`, with each element on its own
+    ///line and indented by `indent` spaces per level.
+    ///
+    /// # Errors
+    ///
+    /// Will return `fmt::Error` if writing to `w` fails.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let mut xml = String::new();
+    ///parse(data)?.write_xml(&mut xml, 4).unwrap();
+    ///assert!(xml.contains("\n    <application"));
+    ///# Ok::<(), ParseError>(())
+    ///```
+    pub fn write_xml<W: fmt::Write>(&self, w: &mut W, indent: usize) -> fmt::Result {
+        printer::write_document(self, Some(indent), w)
+    }
+}
+
+impl fmt::Display for XmlDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        printer::write_document(self, Some(printer::INDENT), f)
+    }
+}
+
+// Parses a `0x` prefixed hexadecimal value, as written for
+// `AttributeValue::Hex`.
+// Returns whether `e` comes from decoding a node or from a node that does
+// not fit the tree, as opposed to reading the input.
+fn is_node_error(e: &ParseError) -> bool {
+    matches!(
+        e,
+        ParseError::ChunkDecode { .. }
+            | ParseError::StringNotFound(_)
+            | ParseError::StringOffsetOutOfBounds(_)
+            | ParseError::ResourceIdNotFound(_)
+            | ParseError::UnknownResourceString(_)
+            | ParseError::UnexpectedEndElement
+            | ParseError::CdataOutsideElement
+    )
+}
+
+fn parse_hex(value: &str) -> Option<u32> {
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))?;
+    u32::from_str_radix(digits, 16).ok()
+}
+
+// Tags and attribute names and values repeat many times in a document, so
+// each distinct string is only allocated once.
+#[derive(Default)]
+struct Interner {
+    strings: IndexSet<Arc<str>>,
+}
+
+impl Interner {
+    // Returns the shared copy of `s`. A string that has not been seen yet
+    // is added, taking ownership of it where the caller has it.
+    fn intern<S: AsRef<str> + Into<Arc<str>>>(&mut self, s: S) -> Arc<str> {
+        if let Some(interned) = self.strings.get(s.as_ref()) {
+            return interned.clone();
         }
+        let interned: Arc<str> = s.into();
+        self.strings.insert(interned.clone());
+        interned
+    }
+}
 
-        for attr in &e.attributes {
-            let ns = string_pool.get(usize::try_from(attr.ns).unwrap());
-            let name = string_pool
-                .get(usize::try_from(attr.name).unwrap())
-                .ok_or(ParseError::StringNotFound(attr.name))?;
-            let value = attr.typed_value.get_value(string_pool);
-
-            let mut final_name = String::new();
-            if name.is_empty() {
-                let resource_id = resource_map
-                    .get(usize::try_from(attr.name).unwrap())
-                    .ok_or(ParseError::ResourceIdNotFound(attr.name))?;
-                let resource_str = get_resource_string(*resource_id)
-                    .ok_or(ParseError::UnknownResourceString(*resource_id))?;
-                final_name.push_str(&resource_str);
-            } else {
-                if let Some(n) = ns {
-                    // There are samples where the namespace value is the
-                    // raw string instead of a URI found in a namespace chunk.
-                    // For now, skip appending the namespace for those cases.
-                    //
-                    // examples/AndroidManifestUnknownNamespace
-                    if let Some(n) = namespaces.get(&n) {
-                        final_name.push_str(n);
-                        final_name.push(':');
-                    };
+// The interned strings of the document an element belongs to, kept once the
+// tree is built so that strings set on the element later can reuse them.
+// Elements that are not part of a document have none. The strings are not
+// part of the value of an element or document, so comparisons ignore them.
+#[derive(Clone, Default)]
+struct SharedStrings(Option<Arc<Interner>>);
+
+impl SharedStrings {
+    // Returns the document's copy of `s`, or `s` itself if the document
+    // does not hold it.
+    fn share<S: AsRef<str> + Into<Arc<str>>>(&self, s: S) -> Arc<str> {
+        match self
+            .0
+            .as_ref()
+            .and_then(|interner| interner.strings.get(s.as_ref()))
+        {
+            Some(shared) => shared.clone(),
+            None => s.into(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.0.as_ref().map_or(0, |interner| interner.strings.len())
+    }
+}
+
+impl PartialEq for SharedStrings {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SharedStrings {}
+
+impl fmt::Debug for SharedStrings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SharedStrings({})", self.len())
+    }
+}
+
+// Builds the element tree from the events of an `EventReader`.
+struct TreeBuilder {
+    max_depth: Option<usize>,
+    // Every prefix declared in the document, mapping prefix to URI.
+    declared: IndexMap<String, String>,
+    // `(prefix, uri)` pairs of the namespaces in scope, innermost last.
+    scopes: Vec<(String, String)>,
+    // Namespaces declared since the last start element. These are attached
+    // to the next element so that they can be re-emitted at the same
+    // position.
+    pending_namespaces: Vec<(String, String)>,
+    element_tracker: Vec<Element>,
+    // Completed top-level elements. Anything after the first is a decoy
+    // added by a packer.
+    roots: Vec<Node>,
+    warnings: Vec<String>,
+    interner: Interner,
+}
+
+impl TreeBuilder {
+    fn new(max_depth: Option<usize>) -> Self {
+        let mut declared = IndexMap::default();
+        declared.insert("android".to_string(), ANDROID_NAMESPACE.to_string());
+
+        Self {
+            max_depth,
+            declared,
+            scopes: vec![("android".to_string(), ANDROID_NAMESPACE.to_string())],
+            pending_namespaces: Vec::new(),
+            element_tracker: Vec::new(),
+            roots: Vec::new(),
+            warnings: Vec::new(),
+            interner: Interner::default(),
+        }
+    }
+
+    fn push(&mut self, event: Event) -> Result<(), ParseError> {
+        match event {
+            Event::StartNamespace { prefix, uri } => {
+                self.declared.insert(prefix.clone(), uri.clone());
+                self.scopes.push((prefix.clone(), uri.clone()));
+                self.pending_namespaces.push((prefix, uri));
+            }
+            Event::EndNamespace { prefix, uri } => {
+                if let Some(i) = self
+                    .scopes
+                    .iter()
+                    .rposition(|(p, u)| *p == prefix && *u == uri)
+                {
+                    self.scopes.remove(i);
                 }
-                final_name.push_str(&name);
             }
+            Event::StartElement {
+                tag,
+                namespace,
+                attributes,
+                raw_values,
+                resource_ids,
+                id_attribute,
+                class_attribute,
+                style_attribute,
+                line,
+                comment,
+            } => {
+                if let Some(max_depth) = self.max_depth {
+                    if self.element_tracker.len() >= max_depth {
+                        return Err(ParseError::MaxDepthExceeded(max_depth));
+                    }
+                }
+
+                let mut element =
+                    Element::from_parts(&mut self.interner, tag, namespace, attributes, raw_values);
+                element.resource_ids = resource_ids
+                    .into_iter()
+                    .map(|(name, id)| (self.interner.intern(name), id))
+                    .collect();
+                element.id_attribute = id_attribute;
+                element.class_attribute = class_attribute;
+                element.style_attribute = style_attribute;
+                element.line = line;
+                element.comment = comment;
+                element.declared_namespaces = core::mem::take(&mut self.pending_namespaces);
 
-            attributes.insert(final_name, value.to_string());
+                // Remember the URI behind each attribute prefix, as a prefix
+                // may be declared again later with a different URI.
+                for key in element.attributes.keys() {
+                    if let Some((prefix, _)) = key.split_once(':') {
+                        let scoped = self.scopes.iter().rev().find(|(p, _)| p == prefix);
+                        if let Some((_, uri)) = scoped {
+                            element
+                                .attribute_namespaces
+                                .insert(prefix.to_string(), uri.clone());
+                        }
+                    }
+                }
+                self.element_tracker.push(element);
+            }
+            Event::EndElement { .. } => {
+                let e = self
+                    .element_tracker
+                    .pop()
+                    .ok_or(ParseError::UnexpectedEndElement)?;
+                self.close_element(e);
+            }
+            Event::Cdata {
+                data,
+                line,
+                comment,
+            } => {
+                self.element_tracker
+                    .last_mut()
+                    .ok_or(ParseError::CdataOutsideElement)?
+                    .insert_children(Node::Cdata(Cdata {
+                        data,
+                        line,
+                        comment,
+                    }));
+            }
+            Event::UnknownChunk { typ, data } => {
+                self.warnings.push(format!(
+                    "Skipped unknown chunk of type 0x{:04x} ({} bytes)",
+                    typ,
+                    data.len()
+                ));
+            }
+        };
+
+        Ok(())
+    }
+
+    fn close_element(&mut self, e: Element) {
+        match self.element_tracker.last_mut() {
+            Some(parent) => parent.insert_children(Node::Element(e)),
+            None => self.roots.push(Node::Element(e)),
         }
+    }
 
-        Ok(Element {
-            attributes,
-            tag: name,
-            children: Vec::new(),
+    // Returns the document once all events have been pushed. Elements that
+    // are still open are an error unless `lenient` is set, in which case
+    // they are closed.
+    fn finish(mut self, lenient: bool) -> Result<XmlDocument, ParseError> {
+        if !self.element_tracker.is_empty() {
+            if !lenient {
+                return Err(ParseError::UnexpectedEof);
+            }
+
+            self.warnings.push(format!(
+                "Document ended with {} unclosed element(s)",
+                self.element_tracker.len()
+            ));
+            while let Some(e) = self.element_tracker.pop() {
+                self.close_element(e);
+            }
+        }
+
+        if self.roots.len() > 1 {
+            self.warnings.push(format!(
+                "Found {} top-level element(s) after the root element",
+                self.roots.len() - 1
+            ));
+        }
+
+        let strings = SharedStrings(Some(Arc::new(self.interner)));
+        for e in self.roots.iter_mut().filter_map(Node::as_element_mut) {
+            e.set_strings(&strings);
+        }
+
+        let mut roots = self.roots.into_iter();
+        let root = roots.next().ok_or(ParseError::NoRootElement)?;
+        Ok(XmlDocument {
+            root,
+            extra_roots: roots.collect(),
+            namespaces: self.declared,
+            warnings: self.warnings,
+            strings,
         })
     }
 }
 
 ///Enum representing possible nodes within the parsed XML document.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
 pub enum Node {
     Element(Element),
     Cdata(Cdata),
 }
 
+impl Node {
+    ///Returns the [`Element`] if the node is an element.
+    #[must_use]
+    pub fn as_element(&self) -> Option<&Element> {
+        match self {
+            Node::Element(e) => Some(e),
+            Node::Cdata(_) => None,
+        }
+    }
+
+    ///Returns the [`Cdata`] if the node is a Cdata element.
+    #[must_use]
+    pub fn as_cdata(&self) -> Option<&Cdata> {
+        match self {
+            Node::Element(_) => None,
+            Node::Cdata(c) => Some(c),
+        }
+    }
+
+    ///Returns the [`Element`] if the node is an element, allowing it to be
+    ///modified.
+    #[must_use]
+    pub fn as_element_mut(&mut self) -> Option<&mut Element> {
+        match self {
+            Node::Element(e) => Some(e),
+            Node::Cdata(_) => None,
+        }
+    }
+
+    ///Converts the node into an [`Element`] if the node is an element.
+    #[must_use]
+    pub fn into_element(self) -> Option<Element> {
+        match self {
+            Node::Element(e) => Some(e),
+            Node::Cdata(_) => None,
+        }
+    }
+
+    ///Converts the node into a [`Cdata`] if the node is a Cdata element.
+    #[must_use]
+    pub fn into_cdata(self) -> Option<Cdata> {
+        match self {
+            Node::Element(_) => None,
+            Node::Cdata(c) => Some(c),
+        }
+    }
+}
+
 ///Struct representing an element within the parsed XML document.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Element {
-    attributes: IndexMap<String, String>,
-    tag: String,
+    // Tags and attribute names and values are shared with the other
+    // elements of the document that use the same string
+    attributes: IndexMap<Arc<str>, Arc<str>>,
+    typed_attributes: IndexMap<Arc<str>, AttributeValue>,
+    raw_values: IndexMap<Arc<str>, String>,
+    // Android resource IDs of the attribute names, from the resource map
+    resource_ids: IndexMap<Arc<str>, u32>,
+    // Names of the attributes marked as the ID, class and style
+    id_attribute: Option<String>,
+    class_attribute: Option<String>,
+    style_attribute: Option<String>,
+    tag: Arc<str>,
+    namespace: Option<String>,
+    declared_namespaces: Vec<(String, String)>,
+    // The URI of each prefix used by the attributes
+    attribute_namespaces: IndexMap<String, String>,
+    line: u32,
+    comment: Option<String>,
     children: Vec<Node>,
+    strings: SharedStrings,
 }
 
 impl Element {
-    ///Returns a map of attributes associated with the element.
+    ///Creates an element with the given tag and no attributes or children.
+    ///
+    ///Prefixed tags such as `amazon:activity` are written as is. The
+    ///element is not placed in a namespace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` is empty.
+    #[must_use]
+    pub fn new(tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        assert!(!tag.is_empty(), "element tag must not be empty");
+        Self::from_parts(
+            &mut Interner::default(),
+            tag,
+            None,
+            IndexMap::default(),
+            IndexMap::default(),
+        )
+    }
+
+    fn from_parts(
+        interner: &mut Interner,
+        tag: String,
+        namespace: Option<String>,
+        typed: IndexMap<String, AttributeValue>,
+        raw: IndexMap<String, String>,
+    ) -> Self {
+        let mut attributes = IndexMap::default();
+        let mut typed_attributes = IndexMap::default();
+        let mut raw_values = IndexMap::default();
+        for (name, value) in typed {
+            let name = interner.intern(name);
+            // A reference or null value is shown as its raw string if there
+            // is one, as the raw string is what Android's XML parser returns
+            // too.
+            let raw = raw.get(&*name);
+            let text = match (&value, raw) {
+                (
+                    AttributeValue::Reference(_)
+                    | AttributeValue::DynamicReference(_)
+                    | AttributeValue::Null,
+                    Some(raw),
+                ) => interner.intern(raw.as_str()),
+                _ => interner.intern(value.to_string()),
+            };
+            if let Some(raw) = raw {
+                raw_values.insert(name.clone(), raw.clone());
+            }
+            attributes.insert(name.clone(), text);
+            typed_attributes.insert(name, value);
+        }
+
+        Self {
+            attributes,
+            typed_attributes,
+            raw_values,
+            resource_ids: IndexMap::default(),
+            id_attribute: None,
+            class_attribute: None,
+            style_attribute: None,
+            tag: interner.intern(tag),
+            namespace,
+            declared_namespaces: Vec::new(),
+            attribute_namespaces: IndexMap::default(),
+            line: 0,
+            comment: None,
+            children: Vec::new(),
+            strings: SharedStrings::default(),
+        }
+    }
+
+    // Gives the element and its descendants the strings of the document
+    // they were built in.
+    fn set_strings(&mut self, strings: &SharedStrings) {
+        self.strings = strings.clone();
+        for child in self.children.iter_mut().filter_map(Node::as_element_mut) {
+            child.set_strings(strings);
+        }
+    }
+
+    // Moves the element and its descendants into a document, replacing
+    // their strings with the document's copies where it has them.
+    fn adopt(&mut self, strings: &SharedStrings) {
+        fn share_keys<V>(map: &mut IndexMap<Arc<str>, V>, strings: &SharedStrings) {
+            *map = core::mem::take(map)
+                .into_iter()
+                .map(|(name, value)| (strings.share(name), value))
+                .collect();
+        }
+
+        self.tag = strings.share(self.tag.clone());
+        self.attributes = core::mem::take(&mut self.attributes)
+            .into_iter()
+            .map(|(name, value)| (strings.share(name), strings.share(value)))
+            .collect();
+        share_keys(&mut self.typed_attributes, strings);
+        share_keys(&mut self.raw_values, strings);
+        share_keys(&mut self.resource_ids, strings);
+        self.strings = strings.clone();
+        for child in self.children.iter_mut().filter_map(Node::as_element_mut) {
+            child.adopt(strings);
+        }
+    }
+
+    ///Returns the `(name, value)` pairs of the attributes associated with
+    ///the element, in document order.
+    pub fn get_attributes(&self) -> impl ExactSizeIterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .map(|(name, value)| (&**name, &**value))
+    }
+
+    ///Returns the `(name, value)` pairs of the attributes associated with
+    ///the element, preserving the type information of each value.
+    pub fn get_typed_attributes(&self) -> impl ExactSizeIterator<Item = (&str, &AttributeValue)> {
+        self.typed_attributes
+            .iter()
+            .map(|(name, value)| (&**name, value))
+    }
+
+    ///Returns the value of the attribute with the given name, e.g.
+    ///`android:name`. The name is matched as written in the document, so a
+    ///document that binds the Android namespace to another prefix needs
+    ///[`Element::get_attribute_ns`] instead.
+    #[must_use]
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(|value| &**value)
+    }
+
+    ///Returns the typed value of the attribute with the given name.
+    ///```rust
+    ///use axmldecoder::{parse, AttributeValue};
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let doc = parse(data)?;
+    ///let manifest = doc.root().as_element().unwrap();
+    ///assert_eq!(
+    ///    manifest.get_typed_attribute("android:versionCode"),
+    ///    Some(&AttributeValue::Int(1))
+    ///);
+    ///# Ok::<(), ParseError>(())
+    ///```
+    #[must_use]
+    pub fn get_typed_attribute(&self, name: &str) -> Option<&AttributeValue> {
+        self.typed_attributes.get(name)
+    }
+
+    ///Returns the raw string stored alongside the typed value of the
+    ///attribute with the given name. Some toolchains keep the original text
+    ///here when the value is compiled to another type, e.g. the
+    ///`android:versionName` of a reference. Returns `None` for string
+    ///values and for attributes without a raw string.
+    ///
+    ///[`Element::get_attribute`] returns the raw string instead of the
+    ///typed value for references and null values.
+    ///```rust
+    ///use axmldecoder::{parse, AttributeValue};
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifestRawValues.xml");
+    ///let doc = parse(data)?;
+    ///let manifest = doc.root().as_element().unwrap();
+    ///assert_eq!(
+    ///    manifest.get_typed_attribute("android:versionName"),
+    ///    Some(&AttributeValue::Reference(0x7f0b_0001))
+    ///);
+    ///assert_eq!(manifest.get_raw_attribute("android:versionName"), Some("1.2.3"));
+    ///assert_eq!(manifest.get_attribute("android:versionName"), Some("1.2.3"));
+    ///# Ok::<(), ParseError>(())
+    ///```
+    #[must_use]
+    pub fn get_raw_attribute(&self, name: &str) -> Option<&str> {
+        self.raw_values.get(name).map(String::as_str)
+    }
+
+    ///Returns the Android resource ID of the attribute with the given name,
+    ///taken from the resource map of the binary XML. Android identifies
+    ///framework attributes by this ID rather than by name. Returns `None`
+    ///for attributes without a resource ID, such as `package`.
+    ///
+    ///Attributes in the Android namespace that are added with
+    ///[`Element::set_attribute`] are given the ID of the framework attribute
+    ///with the same name.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let doc = parse(data)?;
+    ///let manifest = doc.root().as_element().unwrap();
+    ///assert_eq!(
+    ///    manifest.get_attribute_resource_id("android:versionCode"),
+    ///    Some(0x0101_021b)
+    ///);
+    ///assert_eq!(manifest.get_attribute_resource_id("package"), None);
+    ///# Ok::<(), ParseError>(())
+    ///```
     #[must_use]
-    pub fn get_attributes(&self) -> &IndexMap<String, String> {
-        &self.attributes
+    pub fn get_attribute_resource_id(&self, name: &str) -> Option<u32> {
+        self.resource_ids.get(name).copied()
+    }
+
+    ///Returns the name and value of the attribute that the binary XML marks
+    ///as the ID of the element, usually `android:id`.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/LayoutSpecialAttributes.xml");
+    ///let doc = parse(data)?;
+    ///let view = doc.root().as_element().unwrap();
+    ///assert_eq!(view.get_id_attribute(), Some(("android:id", "@ref/0x7f080001")));
+    ///assert_eq!(view.get_class_attribute(), Some(("class", "com.example.CustomView")));
+    ///# Ok::<(), ParseError>(())
+    ///```
+    #[must_use]
+    pub fn get_id_attribute(&self) -> Option<(&str, &str)> {
+        self.special_attribute(self.id_attribute.as_deref())
+    }
+
+    ///Returns the name and value of the attribute that the binary XML marks
+    ///as the class of the element. In compiled layouts this is the `class`
+    ///attribute of `<view>` and `<fragment>`, which has no namespace.
+    #[must_use]
+    pub fn get_class_attribute(&self) -> Option<(&str, &str)> {
+        self.special_attribute(self.class_attribute.as_deref())
+    }
+
+    ///Returns the name and value of the attribute that the binary XML marks
+    ///as the style of the element, usually `style`.
+    #[must_use]
+    pub fn get_style_attribute(&self) -> Option<(&str, &str)> {
+        self.special_attribute(self.style_attribute.as_deref())
+    }
+
+    fn special_attribute(&self, name: Option<&str>) -> Option<(&str, &str)> {
+        let (name, value) = self.attributes.get_key_value(name?)?;
+        Some((name, value))
+    }
+
+    ///Returns the value of the attribute with the given name, or `default`
+    ///if the element does not have it.
+    #[must_use]
+    pub fn get_attribute_or<'a>(&'a self, name: &str, default: &'a str) -> &'a str {
+        self.get_attribute(name).unwrap_or(default)
+    }
+
+    ///Returns the value of the attribute with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Will return `ParseError::AttributeNotFound` if the element does not
+    /// have the attribute.
+    pub fn require_attribute(&self, name: &str) -> Result<&str, ParseError> {
+        self.get_attribute(name)
+            .ok_or_else(|| ParseError::AttributeNotFound(name.to_string()))
+    }
+
+    ///Returns the value of the attribute with the given local name in the
+    ///namespace `namespace_uri`, whatever prefix the document uses for it.
+    ///Attributes without a prefix are found by passing an empty URI.
+    ///
+    ///As when parsing, the `android` prefix refers to the Android namespace
+    ///unless the document declares it otherwise.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let doc = parse(data)?;
+    ///let activity = doc.find_first_element_by_tag("activity").unwrap();
+    ///let android = "http://schemas.android.com/apk/res/android";
+    ///assert_eq!(activity.get_attribute_ns(android, "name"), Some("TCActivity"));
+    ///# Ok::<(), ParseError>(())
+    ///```
+    #[must_use]
+    pub fn get_attribute_ns(&self, namespace_uri: &str, local_name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| match key.split_once(':') {
+                Some((prefix, name)) => {
+                    name == local_name && self.attribute_namespace(prefix) == Some(namespace_uri)
+                }
+                None => namespace_uri.is_empty() && &***key == local_name,
+            })
+            .map(|(_, value)| &**value)
+    }
+
+    ///Returns the value of the first attribute with the given local name,
+    ///ignoring any namespace prefix.
+    #[must_use]
+    pub fn get_attribute_local(&self, local_name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key.split_once(':').map_or(&***key, |(_, name)| name) == local_name)
+            .map(|(_, value)| &**value)
+    }
+
+    // Returns the URI that `prefix` was bound to where the element was
+    // parsed, if any of its attributes use it.
+    pub(crate) fn scoped_namespace(&self, prefix: &str) -> Option<&str> {
+        self.attribute_namespaces.get(prefix).map(String::as_str)
+    }
+
+    fn attribute_namespace(&self, prefix: &str) -> Option<&str> {
+        match self.scoped_namespace(prefix) {
+            Some(uri) => Some(uri),
+            None if prefix == "android" => Some(ANDROID_NAMESPACE),
+            None => None,
+        }
+    }
+
+    ///Sets the attribute with the given name, e.g. `android:debuggable`. An
+    ///existing attribute keeps its position, new attributes are added at
+    ///the end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is empty.
+    ///```rust
+    ///use axmldecoder::{parse, AttributeValue};
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let mut doc = parse(data)?;
+    ///let manifest = doc.root_mut().as_element_mut().unwrap();
+    ///manifest.set_attribute("android:versionName", "2.0");
+    ///assert_eq!(manifest.get_attribute("android:versionName"), Some("2.0"));
+    ///# Ok::<(), ParseError>(())
+    ///```
+    pub fn set_attribute(&mut self, name: impl Into<String>, value: impl Into<AttributeValue>) {
+        let name = self.strings.share(name.into());
+        assert!(!name.is_empty(), "attribute name must not be empty");
+
+        if !self.attributes.contains_key(&name) {
+            let id = name.split_once(':').and_then(|(prefix, local)| {
+                match self.attribute_namespace(prefix) {
+                    Some(ANDROID_NAMESPACE) => get_resource_id(local),
+                    _ => None,
+                }
+            });
+            if let Some(id) = id {
+                self.resource_ids.insert(name.clone(), id);
+            }
+        }
+
+        let value = value.into();
+        self.raw_values.shift_remove(&name);
+        self.attributes
+            .insert(name.clone(), self.strings.share(value.to_string()));
+        self.typed_attributes.insert(name, value);
+    }
+
+    ///Removes the attribute with the given name, returning its value if it
+    ///was present. The order of the remaining attributes is kept.
+    pub fn remove_attribute(&mut self, name: &str) -> Option<String> {
+        self.typed_attributes.shift_remove(name);
+        self.raw_values.shift_remove(name);
+        self.resource_ids.shift_remove(name);
+        self.attributes
+            .shift_remove(name)
+            .map(|value| value.to_string())
+    }
+
+    ///Returns the value of the attribute with the given name as an `i32`.
+    ///Both decimal and `0x` prefixed hexadecimal values are accepted, with
+    ///hexadecimal values taken as the raw bits. Returns `None` if the
+    ///attribute is missing or is not an integer.
+    #[must_use]
+    pub fn get_attribute_as_int(&self, name: &str) -> Option<i32> {
+        let value = self.get_attribute(name)?;
+        match value.parse() {
+            Ok(value) => Some(value),
+            Err(_) => parse_hex(value).map(|value| value as i32),
+        }
+    }
+
+    ///Returns the value of the attribute with the given name as a `u32`.
+    ///Both decimal and `0x` prefixed hexadecimal values are accepted.
+    ///Returns `None` if the attribute is missing or is not an unsigned
+    ///integer.
+    #[must_use]
+    pub fn get_attribute_as_u32(&self, name: &str) -> Option<u32> {
+        let value = self.get_attribute(name)?;
+        value.parse().ok().or_else(|| parse_hex(value))
+    }
+
+    ///Returns the value of the attribute with the given name as a `bool`.
+    ///Returns `None` if the attribute is missing or is not `true` or
+    ///`false`.
+    #[must_use]
+    pub fn get_attribute_as_bool(&self, name: &str) -> Option<bool> {
+        self.get_attribute(name)?.parse().ok()
     }
 
     ///Returns the element tag.
@@ -192,1366 +1167,1970 @@ impl Element {
         &self.tag
     }
 
+    ///Renames the element. The namespace of the element is not changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` is empty.
+    pub fn set_tag(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        assert!(!tag.is_empty(), "element tag must not be empty");
+        self.tag = self.strings.share(tag);
+    }
+
+    ///Returns the namespace URI the element is qualified with, if any.
+    #[must_use]
+    pub fn get_namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    ///Returns the `(prefix, URI)` pairs of the namespaces declared on this
+    ///element, in declaration order.
+    #[must_use]
+    pub fn get_declared_namespaces(&self) -> &[(String, String)] {
+        &self.declared_namespaces
+    }
+
+    ///Returns the line number of the element in the original source file.
+    ///Nodes created with `new` have no source line and return 0.
+    #[must_use]
+    pub fn get_line_number(&self) -> u32 {
+        self.line
+    }
+
+    ///Returns the comment attached to the element in the original source
+    ///file, if any.
+    #[must_use]
+    pub fn get_comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
     ///Returns a list of child nodes.
     #[must_use]
     pub fn get_children(&self) -> &Vec<Node> {
         &self.children
     }
 
+    ///Returns the first direct child element with the given tag.
+    #[must_use]
+    pub fn find_child_by_tag(&self, tag: &str) -> Option<&Element> {
+        self.iter_children_elements().find(|e| e.get_tag() == tag)
+    }
+
+    ///Returns the first direct child element with the given tag, allowing
+    ///it to be modified.
+    #[must_use]
+    pub fn find_child_by_tag_mut(&mut self, tag: &str) -> Option<&mut Element> {
+        self.children
+            .iter_mut()
+            .filter_map(Node::as_element_mut)
+            .find(|e| e.get_tag() == tag)
+    }
+
+    ///Appends `child` to the children of the element. The strings of a
+    ///child element are shared with the document where it holds them.
+    pub fn add_child(&mut self, mut child: Node) {
+        if let Node::Element(e) = &mut child {
+            e.adopt(&self.strings);
+        }
+        self.children.push(child);
+    }
+
+    ///Removes and returns the child at `index`, or returns `None` if there
+    ///is no such child.
+    pub fn remove_child(&mut self, index: usize) -> Option<Node> {
+        (index < self.children.len()).then(|| self.children.remove(index))
+    }
+
+    ///Returns all direct child elements with the given tag.
+    #[must_use]
+    pub fn find_children_by_tag(&self, tag: &str) -> Vec<&Element> {
+        self.iter_children_elements()
+            .filter(|e| e.get_tag() == tag)
+            .collect()
+    }
+
+    ///Returns an iterator over the direct children that are elements,
+    ///skipping Cdata nodes.
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# use axmldecoder::ParseError;
+    ///let data = include_bytes!("../examples/AndroidManifest.xml");
+    ///let doc = parse(data)?;
+    ///let root = doc.root().as_element().unwrap();
+    ///let tags: Vec<&str> = root.iter_children_elements().map(|e| e.get_tag()).collect();
+    ///assert_eq!(tags, ["application"]);
+    ///# Ok::<(), ParseError>(())
+    ///```
+    pub fn iter_children_elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(Node::as_element)
+    }
+
+    ///Returns an iterator over the direct children that are Cdata nodes,
+    ///skipping elements.
+    pub fn iter_children_cdatas(&self) -> impl Iterator<Item = &Cdata> {
+        self.children.iter().filter_map(Node::as_cdata)
+    }
+
+    ///Returns an iterator over every element below this one, depth-first
+    ///in document order. The element itself is not included.
+    pub fn iter_descendants(&self) -> impl Iterator<Item = &Element> {
+        self.iter_descendant_nodes().filter_map(Node::as_element)
+    }
+
+    ///Returns an iterator over every node below this element, including
+    ///Cdata nodes, depth-first in document order. The element itself is
+    ///not included.
+    pub fn iter_descendant_nodes(&self) -> impl Iterator<Item = &Node> {
+        // Children are pushed in reverse so that they are popped in order.
+        let mut stack: Vec<&Node> = self.children.iter().rev().collect();
+        core::iter::from_fn(move || {
+            let node = stack.pop()?;
+            if let Node::Element(e) = node {
+                stack.extend(e.children.iter().rev());
+            }
+            Some(node)
+        })
+    }
+
+    ///Returns an iterator over the elements below this element with the
+    ///given tag, depth-first in document order. Unlike
+    ///[`find_children_by_tag`](Self::find_children_by_tag), nested elements
+    ///are included and nothing is collected up front.
+    pub fn iter_descendants_by_tag<'a>(
+        &'a self,
+        tag: &'a str,
+    ) -> impl Iterator<Item = &'a Element> {
+        self.iter_descendants().filter(move |e| e.get_tag() == tag)
+    }
+
+    ///Returns the first element reached by following `path` down from this
+    ///element, matching one direct child tag per level. If several children
+    ///match a step, the later ones are tried when the rest of the path
+    ///cannot be found below the first. An empty path returns this element.
+    #[must_use]
+    pub fn find_descendant_by_tag_path(&self, path: &[&str]) -> Option<&Element> {
+        self.iter_tag_path(path.iter().copied()).next()
+    }
+
+    // Returns the elements reached by following `tags` down from this
+    // element, one direct child per tag, in document order. This is the one
+    // implementation behind the path lookups of `Element` and `XmlDocument`.
+    fn iter_tag_path<'a, 'b>(
+        &'a self,
+        tags: impl IntoIterator<Item = &'b str>,
+    ) -> Box<dyn Iterator<Item = &'a Element> + 'b>
+    where
+        'a: 'b,
+    {
+        let mut matches: Box<dyn Iterator<Item = &'a Element> + 'b> =
+            Box::new(core::iter::once(self));
+        for tag in tags {
+            matches = Box::new(matches.flat_map(move |e| {
+                e.iter_children_elements()
+                    .filter(move |child| child.get_tag() == tag)
+            }));
+        }
+        matches
+    }
+
     fn insert_children(&mut self, child: Node) {
         self.children.push(child);
     }
 }
 
 ///Struct representing a Cdata element within the parsed XML document.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Cdata {
     data: String,
+    line: u32,
+    comment: Option<String>,
 }
 
 impl Cdata {
+    ///Creates a Cdata node containing `data`.
+    #[must_use]
+    pub fn new(data: impl Into<String>) -> Self {
+        Self {
+            data: data.into(),
+            line: 0,
+            comment: None,
+        }
+    }
+
     #[must_use]
     pub fn get_data(&self) -> &str {
         &self.data
     }
+
+    ///Returns the line number of the Cdata in the original source file.
+    ///Nodes created with `new` have no source line and return 0.
+    #[must_use]
+    pub fn get_line_number(&self) -> u32 {
+        self.line
+    }
+
+    ///Returns the comment attached to the Cdata in the original source
+    ///file, if any.
+    #[must_use]
+    pub fn get_comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
 }
 
 // Logic borrowed from:
 // https://github.com/ytsutano/axmldec/blob/master/lib/jitana/util/axml_parser.cpp#L504
-fn get_resource_string(resource_id: u32) -> Option<String> {
-    const RESOURCE_STRINGS: &[&str] = &[
-        "theme",
-        "label",
-        "icon",
-        "name",
-        "manageSpaceActivity",
-        "allowClearUserData",
-        "permission",
-        "readPermission",
-        "writePermission",
-        "protectionLevel",
-        "permissionGroup",
-        "sharedUserId",
-        "hasCode",
-        "persistent",
-        "enabled",
-        "debuggable",
-        "exported",
-        "process",
-        "taskAffinity",
-        "multiprocess",
-        "finishOnTaskLaunch",
-        "clearTaskOnLaunch",
-        "stateNotNeeded",
-        "excludeFromRecents",
-        "authorities",
-        "syncable",
-        "initOrder",
-        "grantUriPermissions",
-        "priority",
-        "launchMode",
-        "screenOrientation",
-        "configChanges",
-        "description",
-        "targetPackage",
-        "handleProfiling",
-        "functionalTest",
-        "value",
-        "resource",
-        "mimeType",
-        "scheme",
-        "host",
-        "port",
-        "path",
-        "pathPrefix",
-        "pathPattern",
-        "action",
-        "data",
-        "targetClass",
-        "colorForeground",
-        "colorBackground",
-        "backgroundDimAmount",
-        "disabledAlpha",
-        "textAppearance",
-        "textAppearanceInverse",
-        "textColorPrimary",
-        "textColorPrimaryDisableOnly",
-        "textColorSecondary",
-        "textColorPrimaryInverse",
-        "textColorSecondaryInverse",
-        "textColorPrimaryNoDisable",
-        "textColorSecondaryNoDisable",
-        "textColorPrimaryInverseNoDisable",
-        "textColorSecondaryInverseNoDisable",
-        "textColorHintInverse",
-        "textAppearanceLarge",
-        "textAppearanceMedium",
-        "textAppearanceSmall",
-        "textAppearanceLargeInverse",
-        "textAppearanceMediumInverse",
-        "textAppearanceSmallInverse",
-        "textCheckMark",
-        "textCheckMarkInverse",
-        "buttonStyle",
-        "buttonStyleSmall",
-        "buttonStyleInset",
-        "buttonStyleToggle",
-        "galleryItemBackground",
-        "listPreferredItemHeight",
-        "expandableListPreferredItemPaddingLeft",
-        "expandableListPreferredChildPaddingLeft",
-        "expandableListPreferredItemIndicatorLeft",
-        "expandableListPreferredItemIndicatorRight",
-        "expandableListPreferredChildIndicatorLeft",
-        "expandableListPreferredChildIndicatorRight",
-        "windowBackground",
-        "windowFrame",
-        "windowNoTitle",
-        "windowIsFloating",
-        "windowIsTranslucent",
-        "windowContentOverlay",
-        "windowTitleSize",
-        "windowTitleStyle",
-        "windowTitleBackgroundStyle",
-        "alertDialogStyle",
-        "panelBackground",
-        "panelFullBackground",
-        "panelColorForeground",
-        "panelColorBackground",
-        "panelTextAppearance",
-        "scrollbarSize",
-        "scrollbarThumbHorizontal",
-        "scrollbarThumbVertical",
-        "scrollbarTrackHorizontal",
-        "scrollbarTrackVertical",
-        "scrollbarAlwaysDrawHorizontalTrack",
-        "scrollbarAlwaysDrawVerticalTrack",
-        "absListViewStyle",
-        "autoCompleteTextViewStyle",
-        "checkboxStyle",
-        "dropDownListViewStyle",
-        "editTextStyle",
-        "expandableListViewStyle",
-        "galleryStyle",
-        "gridViewStyle",
-        "imageButtonStyle",
-        "imageWellStyle",
-        "listViewStyle",
-        "listViewWhiteStyle",
-        "popupWindowStyle",
-        "progressBarStyle",
-        "progressBarStyleHorizontal",
-        "progressBarStyleSmall",
-        "progressBarStyleLarge",
-        "seekBarStyle",
-        "ratingBarStyle",
-        "ratingBarStyleSmall",
-        "radioButtonStyle",
-        "scrollbarStyle",
-        "scrollViewStyle",
-        "spinnerStyle",
-        "starStyle",
-        "tabWidgetStyle",
-        "textViewStyle",
-        "webViewStyle",
-        "dropDownItemStyle",
-        "spinnerDropDownItemStyle",
-        "dropDownHintAppearance",
-        "spinnerItemStyle",
-        "mapViewStyle",
-        "preferenceScreenStyle",
-        "preferenceCategoryStyle",
-        "preferenceInformationStyle",
-        "preferenceStyle",
-        "checkBoxPreferenceStyle",
-        "yesNoPreferenceStyle",
-        "dialogPreferenceStyle",
-        "editTextPreferenceStyle",
-        "ringtonePreferenceStyle",
-        "preferenceLayoutChild",
-        "textSize",
-        "typeface",
-        "textStyle",
-        "textColor",
-        "textColorHighlight",
-        "textColorHint",
-        "textColorLink",
-        "state_focused",
-        "state_window_focused",
-        "state_enabled",
-        "state_checkable",
-        "state_checked",
-        "state_selected",
-        "state_active",
-        "state_single",
-        "state_first",
-        "state_middle",
-        "state_last",
-        "state_pressed",
-        "state_expanded",
-        "state_empty",
-        "state_above_anchor",
-        "ellipsize",
-        "x",
-        "y",
-        "windowAnimationStyle",
-        "gravity",
-        "autoLink",
-        "linksClickable",
-        "entries",
-        "layout_gravity",
-        "windowEnterAnimation",
-        "windowExitAnimation",
-        "windowShowAnimation",
-        "windowHideAnimation",
-        "activityOpenEnterAnimation",
-        "activityOpenExitAnimation",
-        "activityCloseEnterAnimation",
-        "activityCloseExitAnimation",
-        "taskOpenEnterAnimation",
-        "taskOpenExitAnimation",
-        "taskCloseEnterAnimation",
-        "taskCloseExitAnimation",
-        "taskToFrontEnterAnimation",
-        "taskToFrontExitAnimation",
-        "taskToBackEnterAnimation",
-        "taskToBackExitAnimation",
-        "orientation",
-        "keycode",
-        "fullDark",
-        "topDark",
-        "centerDark",
-        "bottomDark",
-        "fullBright",
-        "topBright",
-        "centerBright",
-        "bottomBright",
-        "bottomMedium",
-        "centerMedium",
-        "id",
-        "tag",
-        "scrollX",
-        "scrollY",
-        "background",
-        "padding",
-        "paddingLeft",
-        "paddingTop",
-        "paddingRight",
-        "paddingBottom",
-        "focusable",
-        "focusableInTouchMode",
-        "visibility",
-        "fitsSystemWindows",
-        "scrollbars",
-        "fadingEdge",
-        "fadingEdgeLength",
-        "nextFocusLeft",
-        "nextFocusRight",
-        "nextFocusUp",
-        "nextFocusDown",
-        "clickable",
-        "longClickable",
-        "saveEnabled",
-        "drawingCacheQuality",
-        "duplicateParentState",
-        "clipChildren",
-        "clipToPadding",
-        "layoutAnimation",
-        "animationCache",
-        "persistentDrawingCache",
-        "alwaysDrawnWithCache",
-        "addStatesFromChildren",
-        "descendantFocusability",
-        "layout",
-        "inflatedId",
-        "layout_width",
-        "layout_height",
-        "layout_margin",
-        "layout_marginLeft",
-        "layout_marginTop",
-        "layout_marginRight",
-        "layout_marginBottom",
-        "listSelector",
-        "drawSelectorOnTop",
-        "stackFromBottom",
-        "scrollingCache",
-        "textFilterEnabled",
-        "transcriptMode",
-        "cacheColorHint",
-        "dial",
-        "hand_hour",
-        "hand_minute",
-        "format",
-        "checked",
-        "button",
-        "checkMark",
-        "foreground",
-        "measureAllChildren",
-        "groupIndicator",
-        "childIndicator",
-        "indicatorLeft",
-        "indicatorRight",
-        "childIndicatorLeft",
-        "childIndicatorRight",
-        "childDivider",
-        "animationDuration",
-        "spacing",
-        "horizontalSpacing",
-        "verticalSpacing",
-        "stretchMode",
-        "columnWidth",
-        "numColumns",
-        "src",
-        "antialias",
-        "filter",
-        "dither",
-        "scaleType",
-        "adjustViewBounds",
-        "maxWidth",
-        "maxHeight",
-        "tint",
-        "baselineAlignBottom",
-        "cropToPadding",
-        "textOn",
-        "textOff",
-        "baselineAligned",
-        "baselineAlignedChildIndex",
-        "weightSum",
-        "divider",
-        "dividerHeight",
-        "choiceMode",
-        "itemTextAppearance",
-        "horizontalDivider",
-        "verticalDivider",
-        "headerBackground",
-        "itemBackground",
-        "itemIconDisabledAlpha",
-        "rowHeight",
-        "maxRows",
-        "maxItemsPerRow",
-        "moreIcon",
-        "max",
-        "progress",
-        "secondaryProgress",
-        "indeterminate",
-        "indeterminateOnly",
-        "indeterminateDrawable",
-        "progressDrawable",
-        "indeterminateDuration",
-        "indeterminateBehavior",
-        "minWidth",
-        "minHeight",
-        "interpolator",
-        "thumb",
-        "thumbOffset",
-        "numStars",
-        "rating",
-        "stepSize",
-        "isIndicator",
-        "checkedButton",
-        "stretchColumns",
-        "shrinkColumns",
-        "collapseColumns",
-        "layout_column",
-        "layout_span",
-        "bufferType",
-        "text",
-        "hint",
-        "textScaleX",
-        "cursorVisible",
-        "maxLines",
-        "lines",
-        "height",
-        "minLines",
-        "maxEms",
-        "ems",
-        "width",
-        "minEms",
-        "scrollHorizontally",
-        "password",
-        "singleLine",
-        "selectAllOnFocus",
-        "includeFontPadding",
-        "maxLength",
-        "shadowColor",
-        "shadowDx",
-        "shadowDy",
-        "shadowRadius",
-        "numeric",
-        "digits",
-        "phoneNumber",
-        "inputMethod",
-        "capitalize",
-        "autoText",
-        "editable",
-        "freezesText",
-        "drawableTop",
-        "drawableBottom",
-        "drawableLeft",
-        "drawableRight",
-        "drawablePadding",
-        "completionHint",
-        "completionHintView",
-        "completionThreshold",
-        "dropDownSelector",
-        "popupBackground",
-        "inAnimation",
-        "outAnimation",
-        "flipInterval",
-        "fillViewport",
-        "prompt",
-        "startYear",
-        "endYear",
-        "mode",
-        "layout_x",
-        "layout_y",
-        "layout_weight",
-        "layout_toLeftOf",
-        "layout_toRightOf",
-        "layout_above",
-        "layout_below",
-        "layout_alignBaseline",
-        "layout_alignLeft",
-        "layout_alignTop",
-        "layout_alignRight",
-        "layout_alignBottom",
-        "layout_alignParentLeft",
-        "layout_alignParentTop",
-        "layout_alignParentRight",
-        "layout_alignParentBottom",
-        "layout_centerInParent",
-        "layout_centerHorizontal",
-        "layout_centerVertical",
-        "layout_alignWithParentIfMissing",
-        "layout_scale",
-        "visible",
-        "variablePadding",
-        "constantSize",
-        "oneshot",
-        "duration",
-        "drawable",
-        "shape",
-        "innerRadiusRatio",
-        "thicknessRatio",
-        "startColor",
-        "endColor",
-        "useLevel",
-        "angle",
-        "type",
-        "centerX",
-        "centerY",
-        "gradientRadius",
-        "color",
-        "dashWidth",
-        "dashGap",
-        "radius",
-        "topLeftRadius",
-        "topRightRadius",
-        "bottomLeftRadius",
-        "bottomRightRadius",
-        "left",
-        "top",
-        "right",
-        "bottom",
-        "minLevel",
-        "maxLevel",
-        "fromDegrees",
-        "toDegrees",
-        "pivotX",
-        "pivotY",
-        "insetLeft",
-        "insetRight",
-        "insetTop",
-        "insetBottom",
-        "shareInterpolator",
-        "fillBefore",
-        "fillAfter",
-        "startOffset",
-        "repeatCount",
-        "repeatMode",
-        "zAdjustment",
-        "fromXScale",
-        "toXScale",
-        "fromYScale",
-        "toYScale",
-        "fromXDelta",
-        "toXDelta",
-        "fromYDelta",
-        "toYDelta",
-        "fromAlpha",
-        "toAlpha",
-        "delay",
-        "animation",
-        "animationOrder",
-        "columnDelay",
-        "rowDelay",
-        "direction",
-        "directionPriority",
-        "factor",
-        "cycles",
-        "searchMode",
-        "searchSuggestAuthority",
-        "searchSuggestPath",
-        "searchSuggestSelection",
-        "searchSuggestIntentAction",
-        "searchSuggestIntentData",
-        "queryActionMsg",
-        "suggestActionMsg",
-        "suggestActionMsgColumn",
-        "menuCategory",
-        "orderInCategory",
-        "checkableBehavior",
-        "title",
-        "titleCondensed",
-        "alphabeticShortcut",
-        "numericShortcut",
-        "checkable",
-        "selectable",
-        "orderingFromXml",
-        "key",
-        "summary",
-        "order",
-        "widgetLayout",
-        "dependency",
-        "defaultValue",
-        "shouldDisableView",
-        "summaryOn",
-        "summaryOff",
-        "disableDependentsState",
-        "dialogTitle",
-        "dialogMessage",
-        "dialogIcon",
-        "positiveButtonText",
-        "negativeButtonText",
-        "dialogLayout",
-        "entryValues",
-        "ringtoneType",
-        "showDefault",
-        "showSilent",
-        "scaleWidth",
-        "scaleHeight",
-        "scaleGravity",
-        "ignoreGravity",
-        "foregroundGravity",
-        "tileMode",
-        "targetActivity",
-        "alwaysRetainTaskState",
-        "allowTaskReparenting",
-        "searchButtonText",
-        "colorForegroundInverse",
-        "textAppearanceButton",
-        "listSeparatorTextViewStyle",
-        "streamType",
-        "clipOrientation",
-        "centerColor",
-        "minSdkVersion",
-        "windowFullscreen",
-        "unselectedAlpha",
-        "progressBarStyleSmallTitle",
-        "ratingBarStyleIndicator",
-        "apiKey",
-        "textColorTertiary",
-        "textColorTertiaryInverse",
-        "listDivider",
-        "soundEffectsEnabled",
-        "keepScreenOn",
-        "lineSpacingExtra",
-        "lineSpacingMultiplier",
-        "listChoiceIndicatorSingle",
-        "listChoiceIndicatorMultiple",
-        "versionCode",
-        "versionName",
-        "marqueeRepeatLimit",
-        "windowNoDisplay",
-        "backgroundDimEnabled",
-        "inputType",
-        "isDefault",
-        "windowDisablePreview",
-        "privateImeOptions",
-        "editorExtras",
-        "settingsActivity",
-        "fastScrollEnabled",
-        "reqTouchScreen",
-        "reqKeyboardType",
-        "reqHardKeyboard",
-        "reqNavigation",
-        "windowSoftInputMode",
-        "imeFullscreenBackground",
-        "noHistory",
-        "headerDividersEnabled",
-        "footerDividersEnabled",
-        "candidatesTextStyleSpans",
-        "smoothScrollbar",
-        "reqFiveWayNav",
-        "keyBackground",
-        "keyTextSize",
-        "labelTextSize",
-        "keyTextColor",
-        "keyPreviewLayout",
-        "keyPreviewOffset",
-        "keyPreviewHeight",
-        "verticalCorrection",
-        "popupLayout",
-        "state_long_pressable",
-        "keyWidth",
-        "keyHeight",
-        "horizontalGap",
-        "verticalGap",
-        "rowEdgeFlags",
-        "codes",
-        "popupKeyboard",
-        "popupCharacters",
-        "keyEdgeFlags",
-        "isModifier",
-        "isSticky",
-        "isRepeatable",
-        "iconPreview",
-        "keyOutputText",
-        "keyLabel",
-        "keyIcon",
-        "keyboardMode",
-        "isScrollContainer",
-        "fillEnabled",
-        "updatePeriodMillis",
-        "initialLayout",
-        "voiceSearchMode",
-        "voiceLanguageModel",
-        "voicePromptText",
-        "voiceLanguage",
-        "voiceMaxResults",
-        "bottomOffset",
-        "topOffset",
-        "allowSingleTap",
-        "handle",
-        "content",
-        "animateOnClick",
-        "configure",
-        "hapticFeedbackEnabled",
-        "innerRadius",
-        "thickness",
-        "sharedUserLabel",
-        "dropDownWidth",
-        "dropDownAnchor",
-        "imeOptions",
-        "imeActionLabel",
-        "imeActionId",
-        "UNKNOWN",
-        "imeExtractEnterAnimation",
-        "imeExtractExitAnimation",
-        "tension",
-        "extraTension",
-        "anyDensity",
-        "searchSuggestThreshold",
-        "includeInGlobalSearch",
-        "onClick",
-        "targetSdkVersion",
-        "maxSdkVersion",
-        "testOnly",
-        "contentDescription",
-        "gestureStrokeWidth",
-        "gestureColor",
-        "uncertainGestureColor",
-        "fadeOffset",
-        "fadeDuration",
-        "gestureStrokeType",
-        "gestureStrokeLengthThreshold",
-        "gestureStrokeSquarenessThreshold",
-        "gestureStrokeAngleThreshold",
-        "eventsInterceptionEnabled",
-        "fadeEnabled",
-        "backupAgent",
-        "allowBackup",
-        "glEsVersion",
-        "queryAfterZeroResults",
-        "dropDownHeight",
-        "smallScreens",
-        "normalScreens",
-        "largeScreens",
-        "progressBarStyleInverse",
-        "progressBarStyleSmallInverse",
-        "progressBarStyleLargeInverse",
-        "searchSettingsDescription",
-        "textColorPrimaryInverseDisableOnly",
-        "autoUrlDetect",
-        "resizeable",
-        "required",
-        "accountType",
-        "contentAuthority",
-        "userVisible",
-        "windowShowWallpaper",
-        "wallpaperOpenEnterAnimation",
-        "wallpaperOpenExitAnimation",
-        "wallpaperCloseEnterAnimation",
-        "wallpaperCloseExitAnimation",
-        "wallpaperIntraOpenEnterAnimation",
-        "wallpaperIntraOpenExitAnimation",
-        "wallpaperIntraCloseEnterAnimation",
-        "wallpaperIntraCloseExitAnimation",
-        "supportsUploading",
-        "killAfterRestore",
-        "restoreNeedsApplication",
-        "smallIcon",
-        "accountPreferences",
-        "textAppearanceSearchResultSubtitle",
-        "textAppearanceSearchResultTitle",
-        "summaryColumn",
-        "detailColumn",
-        "detailSocialSummary",
-        "thumbnail",
-        "detachWallpaper",
-        "finishOnCloseSystemDialogs",
-        "scrollbarFadeDuration",
-        "scrollbarDefaultDelayBeforeFade",
-        "fadeScrollbars",
-        "colorBackgroundCacheHint",
-        "dropDownHorizontalOffset",
-        "dropDownVerticalOffset",
-        "quickContactBadgeStyleWindowSmall",
-        "quickContactBadgeStyleWindowMedium",
-        "quickContactBadgeStyleWindowLarge",
-        "quickContactBadgeStyleSmallWindowSmall",
-        "quickContactBadgeStyleSmallWindowMedium",
-        "quickContactBadgeStyleSmallWindowLarge",
-        "author",
-        "autoStart",
-        "expandableListViewWhiteStyle",
-        "installLocation",
-        "vmSafeMode",
-        "webTextViewStyle",
-        "restoreAnyVersion",
-        "tabStripLeft",
-        "tabStripRight",
-        "tabStripEnabled",
-        "logo",
-        "xlargeScreens",
-        "immersive",
-        "overScrollMode",
-        "overScrollHeader",
-        "overScrollFooter",
-        "filterTouchesWhenObscured",
-        "textSelectHandleLeft",
-        "textSelectHandleRight",
-        "textSelectHandle",
-        "textSelectHandleWindowStyle",
-        "popupAnimationStyle",
-        "screenSize",
-        "screenDensity",
-        "allContactsName",
-        "windowActionBar",
-        "actionBarStyle",
-        "navigationMode",
-        "displayOptions",
-        "subtitle",
-        "customNavigationLayout",
-        "hardwareAccelerated",
-        "measureWithLargestChild",
-        "animateFirstView",
-        "dropDownSpinnerStyle",
-        "actionDropDownStyle",
-        "actionButtonStyle",
-        "showAsAction",
-        "previewImage",
-        "actionModeBackground",
-        "actionModeCloseDrawable",
-        "windowActionModeOverlay",
-        "valueFrom",
-        "valueTo",
-        "valueType",
-        "propertyName",
-        "ordering",
-        "fragment",
-        "windowActionBarOverlay",
-        "fragmentOpenEnterAnimation",
-        "fragmentOpenExitAnimation",
-        "fragmentCloseEnterAnimation",
-        "fragmentCloseExitAnimation",
-        "fragmentFadeEnterAnimation",
-        "fragmentFadeExitAnimation",
-        "actionBarSize",
-        "imeSubtypeLocale",
-        "imeSubtypeMode",
-        "imeSubtypeExtraValue",
-        "splitMotionEvents",
-        "listChoiceBackgroundIndicator",
-        "spinnerMode",
-        "animateLayoutChanges",
-        "actionBarTabStyle",
-        "actionBarTabBarStyle",
-        "actionBarTabTextStyle",
-        "actionOverflowButtonStyle",
-        "actionModeCloseButtonStyle",
-        "titleTextStyle",
-        "subtitleTextStyle",
-        "iconifiedByDefault",
-        "actionLayout",
-        "actionViewClass",
-        "activatedBackgroundIndicator",
-        "state_activated",
-        "listPopupWindowStyle",
-        "popupMenuStyle",
-        "textAppearanceLargePopupMenu",
-        "textAppearanceSmallPopupMenu",
-        "breadCrumbTitle",
-        "breadCrumbShortTitle",
-        "listDividerAlertDialog",
-        "textColorAlertDialogListItem",
-        "loopViews",
-        "dialogTheme",
-        "alertDialogTheme",
-        "dividerVertical",
-        "homeAsUpIndicator",
-        "enterFadeDuration",
-        "exitFadeDuration",
-        "selectableItemBackground",
-        "autoAdvanceViewId",
-        "useIntrinsicSizeAsMinimum",
-        "actionModeCutDrawable",
-        "actionModeCopyDrawable",
-        "actionModePasteDrawable",
-        "textEditPasteWindowLayout",
-        "textEditNoPasteWindowLayout",
-        "textIsSelectable",
-        "windowEnableSplitTouch",
-        "indeterminateProgressStyle",
-        "progressBarPadding",
-        "animationResolution",
-        "state_accelerated",
-        "baseline",
-        "homeLayout",
-        "opacity",
-        "alpha",
-        "transformPivotX",
-        "transformPivotY",
-        "translationX",
-        "translationY",
-        "scaleX",
-        "scaleY",
-        "rotation",
-        "rotationX",
-        "rotationY",
-        "showDividers",
-        "dividerPadding",
-        "borderlessButtonStyle",
-        "dividerHorizontal",
-        "itemPadding",
-        "buttonBarStyle",
-        "buttonBarButtonStyle",
-        "segmentedButtonStyle",
-        "staticWallpaperPreview",
-        "allowParallelSyncs",
-        "isAlwaysSyncable",
-        "verticalScrollbarPosition",
-        "fastScrollAlwaysVisible",
-        "fastScrollThumbDrawable",
-        "fastScrollPreviewBackgroundLeft",
-        "fastScrollPreviewBackgroundRight",
-        "fastScrollTrackDrawable",
-        "fastScrollOverlayPosition",
-        "customTokens",
-        "nextFocusForward",
-        "firstDayOfWeek",
-        "showWeekNumber",
-        "minDate",
-        "maxDate",
-        "shownWeekCount",
-        "selectedWeekBackgroundColor",
-        "focusedMonthDateColor",
-        "unfocusedMonthDateColor",
-        "weekNumberColor",
-        "weekSeparatorLineColor",
-        "selectedDateVerticalBar",
-        "weekDayTextAppearance",
-        "dateTextAppearance",
-        "UNKNOWN",
-        "spinnersShown",
-        "calendarViewShown",
-        "state_multiline",
-        "detailsElementBackground",
-        "textColorHighlightInverse",
-        "textColorLinkInverse",
-        "editTextColor",
-        "editTextBackground",
-        "horizontalScrollViewStyle",
-        "layerType",
-        "alertDialogIcon",
-        "windowMinWidthMajor",
-        "windowMinWidthMinor",
-        "queryHint",
-        "fastScrollTextColor",
-        "largeHeap",
-        "windowCloseOnTouchOutside",
-        "datePickerStyle",
-        "calendarViewStyle",
-        "textEditSidePasteWindowLayout",
-        "textEditSideNoPasteWindowLayout",
-        "actionMenuTextAppearance",
-        "actionMenuTextColor",
-        "textCursorDrawable",
-        "resizeMode",
-        "requiresSmallestWidthDp",
-        "compatibleWidthLimitDp",
-        "largestWidthLimitDp",
-        "state_hovered",
-        "state_drag_can_accept",
-        "state_drag_hovered",
-        "stopWithTask",
-        "switchTextOn",
-        "switchTextOff",
-        "switchPreferenceStyle",
-        "switchTextAppearance",
-        "track",
-        "switchMinWidth",
-        "switchPadding",
-        "thumbTextPadding",
-        "textSuggestionsWindowStyle",
-        "textEditSuggestionItemLayout",
-        "rowCount",
-        "rowOrderPreserved",
-        "columnCount",
-        "columnOrderPreserved",
-        "useDefaultMargins",
-        "alignmentMode",
-        "layout_row",
-        "layout_rowSpan",
-        "layout_columnSpan",
-        "actionModeSelectAllDrawable",
-        "isAuxiliary",
-        "accessibilityEventTypes",
-        "packageNames",
-        "accessibilityFeedbackType",
-        "notificationTimeout",
-        "accessibilityFlags",
-        "canRetrieveWindowContent",
-        "listPreferredItemHeightLarge",
-        "listPreferredItemHeightSmall",
-        "actionBarSplitStyle",
-        "actionProviderClass",
-        "backgroundStacked",
-        "backgroundSplit",
-        "textAllCaps",
-        "colorPressedHighlight",
-        "colorLongPressedHighlight",
-        "colorFocusedHighlight",
-        "colorActivatedHighlight",
-        "colorMultiSelectHighlight",
-        "drawableStart",
-        "drawableEnd",
-        "actionModeStyle",
-        "minResizeWidth",
-        "minResizeHeight",
-        "actionBarWidgetTheme",
-        "uiOptions",
-        "subtypeLocale",
-        "subtypeExtraValue",
-        "actionBarDivider",
-        "actionBarItemBackground",
-        "actionModeSplitBackground",
-        "textAppearanceListItem",
-        "textAppearanceListItemSmall",
-        "targetDescriptions",
-        "directionDescriptions",
-        "overridesImplicitlyEnabledSubtype",
-        "listPreferredItemPaddingLeft",
-        "listPreferredItemPaddingRight",
-        "requiresFadingEdge",
-        "publicKey",
-        "parentActivityName",
-        "UNKNOWN",
-        "isolatedProcess",
-        "importantForAccessibility",
-        "keyboardLayout",
-        "fontFamily",
-        "mediaRouteButtonStyle",
-        "mediaRouteTypes",
-        "supportsRtl",
-        "textDirection",
-        "textAlignment",
-        "layoutDirection",
-        "paddingStart",
-        "paddingEnd",
-        "layout_marginStart",
-        "layout_marginEnd",
-        "layout_toStartOf",
-        "layout_toEndOf",
-        "layout_alignStart",
-        "layout_alignEnd",
-        "layout_alignParentStart",
-        "layout_alignParentEnd",
-        "listPreferredItemPaddingStart",
-        "listPreferredItemPaddingEnd",
-        "singleUser",
-        "presentationTheme",
-        "subtypeId",
-        "initialKeyguardLayout",
-        "UNKNOWN",
-        "widgetCategory",
-        "permissionGroupFlags",
-        "labelFor",
-        "permissionFlags",
-        "checkedTextViewStyle",
-        "showOnLockScreen",
-        "format12Hour",
-        "format24Hour",
-        "timeZone",
-        "mipMap",
-        "mirrorForRtl",
-        "windowOverscan",
-        "requiredForAllUsers",
-        "indicatorStart",
-        "indicatorEnd",
-        "childIndicatorStart",
-        "childIndicatorEnd",
-        "restrictedAccountType",
-        "requiredAccountType",
-        "canRequestTouchExplorationMode",
-        "canRequestEnhancedWebAccessibility",
-        "canRequestFilterKeyEvents",
-        "layoutMode",
-        "keySet",
-        "targetId",
-        "fromScene",
-        "toScene",
-        "transition",
-        "transitionOrdering",
-        "fadingMode",
-        "startDelay",
-        "ssp",
-        "sspPrefix",
-        "sspPattern",
-        "addPrintersActivity",
-        "vendor",
-        "category",
-        "isAsciiCapable",
-        "autoMirrored",
-        "supportsSwitchingToNextInputMethod",
-        "requireDeviceUnlock",
-        "apduServiceBanner",
-        "accessibilityLiveRegion",
-        "windowTranslucentStatus",
-        "windowTranslucentNavigation",
-        "advancedPrintOptionsActivity",
-        "banner",
-        "windowSwipeToDismiss",
-        "isGame",
-        "allowEmbedded",
-        "setupActivity",
-        "fastScrollStyle",
-        "windowContentTransitions",
-        "windowContentTransitionManager",
-        "translationZ",
-        "tintMode",
-        "controlX1",
-        "controlY1",
-        "controlX2",
-        "controlY2",
-        "transitionName",
-        "transitionGroup",
-        "viewportWidth",
-        "viewportHeight",
-        "fillColor",
-        "pathData",
-        "strokeColor",
-        "strokeWidth",
-        "trimPathStart",
-        "trimPathEnd",
-        "trimPathOffset",
-        "strokeLineCap",
-        "strokeLineJoin",
-        "strokeMiterLimit",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "colorControlNormal",
-        "colorControlActivated",
-        "colorButtonNormal",
-        "colorControlHighlight",
-        "persistableMode",
-        "titleTextAppearance",
-        "subtitleTextAppearance",
-        "slideEdge",
-        "actionBarTheme",
-        "textAppearanceListItemSecondary",
-        "colorPrimary",
-        "colorPrimaryDark",
-        "colorAccent",
-        "nestedScrollingEnabled",
-        "windowEnterTransition",
-        "windowExitTransition",
-        "windowSharedElementEnterTransition",
-        "windowSharedElementExitTransition",
-        "windowAllowReturnTransitionOverlap",
-        "windowAllowEnterTransitionOverlap",
-        "sessionService",
-        "stackViewStyle",
-        "switchStyle",
-        "elevation",
-        "excludeId",
-        "excludeClass",
-        "hideOnContentScroll",
-        "actionOverflowMenuStyle",
-        "documentLaunchMode",
-        "maxRecents",
-        "autoRemoveFromRecents",
-        "stateListAnimator",
-        "toId",
-        "fromId",
-        "reversible",
-        "splitTrack",
-        "targetName",
-        "excludeName",
-        "matchOrder",
-        "windowDrawsSystemBarBackgrounds",
-        "statusBarColor",
-        "navigationBarColor",
-        "contentInsetStart",
-        "contentInsetEnd",
-        "contentInsetLeft",
-        "contentInsetRight",
-        "paddingMode",
-        "layout_rowWeight",
-        "layout_columnWeight",
-        "translateX",
-        "translateY",
-        "selectableItemBackgroundBorderless",
-        "elegantTextHeight",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "windowTransitionBackgroundFadeDuration",
-        "overlapAnchor",
-        "progressTint",
-        "progressTintMode",
-        "progressBackgroundTint",
-        "progressBackgroundTintMode",
-        "secondaryProgressTint",
-        "secondaryProgressTintMode",
-        "indeterminateTint",
-        "indeterminateTintMode",
-        "backgroundTint",
-        "backgroundTintMode",
-        "foregroundTint",
-        "foregroundTintMode",
-        "buttonTint",
-        "buttonTintMode",
-        "thumbTint",
-        "thumbTintMode",
-        "fullBackupOnly",
-        "propertyXName",
-        "propertyYName",
-        "relinquishTaskIdentity",
-        "tileModeX",
-        "tileModeY",
-        "actionModeShareDrawable",
-        "actionModeFindDrawable",
-        "actionModeWebSearchDrawable",
-        "transitionVisibilityMode",
-        "minimumHorizontalAngle",
-        "minimumVerticalAngle",
-        "maximumAngle",
-        "searchViewStyle",
-        "closeIcon",
-        "goIcon",
-        "searchIcon",
-        "voiceIcon",
-        "commitIcon",
-        "suggestionRowLayout",
-        "queryBackground",
-        "submitBackground",
-        "buttonBarPositiveButtonStyle",
-        "buttonBarNeutralButtonStyle",
-        "buttonBarNegativeButtonStyle",
-        "popupElevation",
-        "actionBarPopupTheme",
-        "multiArch",
-        "touchscreenBlocksFocus",
-        "windowElevation",
-        "launchTaskBehindTargetAnimation",
-        "launchTaskBehindSourceAnimation",
-        "restrictionType",
-        "dayOfWeekBackground",
-        "dayOfWeekTextAppearance",
-        "headerMonthTextAppearance",
-        "headerDayOfMonthTextAppearance",
-        "headerYearTextAppearance",
-        "yearListItemTextAppearance",
-        "yearListSelectorColor",
-        "calendarTextColor",
-        "recognitionService",
-        "timePickerStyle",
-        "timePickerDialogTheme",
-        "headerTimeTextAppearance",
-        "headerAmPmTextAppearance",
-        "numbersTextColor",
-        "numbersBackgroundColor",
-        "numbersSelectorColor",
-        "amPmTextColor",
-        "amPmBackgroundColor",
-        "UNKNOWN",
-        "checkMarkTint",
-        "checkMarkTintMode",
-        "popupTheme",
-        "toolbarStyle",
-        "windowClipToOutline",
-        "datePickerDialogTheme",
-        "showText",
-        "windowReturnTransition",
-        "windowReenterTransition",
-        "windowSharedElementReturnTransition",
-        "windowSharedElementReenterTransition",
-        "resumeWhilePausing",
-        "datePickerMode",
-        "timePickerMode",
-        "inset",
-        "letterSpacing",
-        "fontFeatureSettings",
-        "outlineProvider",
-        "contentAgeHint",
-        "country",
-        "windowSharedElementsUseOverlay",
-        "reparent",
-        "reparentWithOverlay",
-        "ambientShadowAlpha",
-        "spotShadowAlpha",
-        "navigationIcon",
-        "navigationContentDescription",
-        "fragmentExitTransition",
-        "fragmentEnterTransition",
-        "fragmentSharedElementEnterTransition",
-        "fragmentReturnTransition",
-        "fragmentSharedElementReturnTransition",
-        "fragmentReenterTransition",
-        "fragmentAllowEnterTransitionOverlap",
-        "fragmentAllowReturnTransitionOverlap",
-        "patternPathData",
-        "strokeAlpha",
-        "fillAlpha",
-        "windowActivityTransitions",
-        "colorEdgeEffect",
-        "resizeClip",
-        "collapseContentDescription",
-        "accessibilityTraversalBefore",
-        "accessibilityTraversalAfter",
-        "dialogPreferredPadding",
-        "searchHintIcon",
-        "revisionCode",
-        "drawableTint",
-        "drawableTintMode",
-        "fraction",
-        "trackTint",
-        "trackTintMode",
-        "start",
-        "end",
-        "breakStrategy",
-        "hyphenationFrequency",
-        "allowUndo",
-        "windowLightStatusBar",
-        "numbersInnerTextColor",
-        "colorBackgroundFloating",
-        "titleTextColor",
-        "subtitleTextColor",
-        "thumbPosition",
-        "scrollIndicators",
-        "contextClickable",
-        "fingerprintAuthDrawable",
-        "logoDescription",
-        "extractNativeLibs",
-        "fullBackupContent",
-        "usesCleartextTraffic",
-        "lockTaskMode",
-        "autoVerify",
-        "showForAllUsers",
-        "supportsAssist",
-        "supportsLaunchVoiceAssistFromKeyguard",
-        "listMenuViewStyle",
-        "subMenuArrow",
-        "defaultWidth",
-        "defaultHeight",
-        "resizeableActivity",
-        "supportsPictureInPicture",
-        "titleMargin",
-        "titleMarginStart",
-        "titleMarginEnd",
-        "titleMarginTop",
-        "titleMarginBottom",
-        "maxButtonHeight",
-        "buttonGravity",
-        "collapseIcon",
-        "level",
-        "contextPopupMenuStyle",
-        "textAppearancePopupMenuHeader",
-        "windowBackgroundFallback",
-        "defaultToDeviceProtectedStorage",
-        "directBootAware",
-        "preferenceFragmentStyle",
-        "canControlMagnification",
-        "languageTag",
-        "pointerIcon",
-        "tickMark",
-        "tickMarkTint",
-        "tickMarkTintMode",
-        "canPerformGestures",
-        "externalService",
-        "supportsLocalInteraction",
-        "startX",
-        "startY",
-        "endX",
-        "endY",
-        "offset",
-        "use32bitAbi",
-        "bitmap",
-        "hotSpotX",
-        "hotSpotY",
-        "version",
-        "backupInForeground",
-        "countDown",
-        "canRecord",
-        "tunerCount",
-        "fillType",
-        "popupEnterTransition",
-        "popupExitTransition",
-        "forceHasOverlappingRendering",
-        "contentInsetStartWithNavigation",
-        "contentInsetEndWithActions",
-        "numberPickerStyle",
-        "enableVrMode",
-        "UNKNOWN",
-        "networkSecurityConfig",
-        "shortcutId",
-        "shortcutShortLabel",
-        "shortcutLongLabel",
-        "shortcutDisabledMessage",
-        "roundIcon",
-        "contextUri",
-        "contextDescription",
-        "showMetadataInPreview",
-        "colorSecondary",
-    ];
-
-    let i = resource_id - 0x0101_0000;
-
-    Some((*RESOURCE_STRINGS.get(usize::try_from(i).unwrap())?).to_string())
+const RESOURCE_STRINGS: &[&str] = &[
+    "theme",
+    "label",
+    "icon",
+    "name",
+    "manageSpaceActivity",
+    "allowClearUserData",
+    "permission",
+    "readPermission",
+    "writePermission",
+    "protectionLevel",
+    "permissionGroup",
+    "sharedUserId",
+    "hasCode",
+    "persistent",
+    "enabled",
+    "debuggable",
+    "exported",
+    "process",
+    "taskAffinity",
+    "multiprocess",
+    "finishOnTaskLaunch",
+    "clearTaskOnLaunch",
+    "stateNotNeeded",
+    "excludeFromRecents",
+    "authorities",
+    "syncable",
+    "initOrder",
+    "grantUriPermissions",
+    "priority",
+    "launchMode",
+    "screenOrientation",
+    "configChanges",
+    "description",
+    "targetPackage",
+    "handleProfiling",
+    "functionalTest",
+    "value",
+    "resource",
+    "mimeType",
+    "scheme",
+    "host",
+    "port",
+    "path",
+    "pathPrefix",
+    "pathPattern",
+    "action",
+    "data",
+    "targetClass",
+    "colorForeground",
+    "colorBackground",
+    "backgroundDimAmount",
+    "disabledAlpha",
+    "textAppearance",
+    "textAppearanceInverse",
+    "textColorPrimary",
+    "textColorPrimaryDisableOnly",
+    "textColorSecondary",
+    "textColorPrimaryInverse",
+    "textColorSecondaryInverse",
+    "textColorPrimaryNoDisable",
+    "textColorSecondaryNoDisable",
+    "textColorPrimaryInverseNoDisable",
+    "textColorSecondaryInverseNoDisable",
+    "textColorHintInverse",
+    "textAppearanceLarge",
+    "textAppearanceMedium",
+    "textAppearanceSmall",
+    "textAppearanceLargeInverse",
+    "textAppearanceMediumInverse",
+    "textAppearanceSmallInverse",
+    "textCheckMark",
+    "textCheckMarkInverse",
+    "buttonStyle",
+    "buttonStyleSmall",
+    "buttonStyleInset",
+    "buttonStyleToggle",
+    "galleryItemBackground",
+    "listPreferredItemHeight",
+    "expandableListPreferredItemPaddingLeft",
+    "expandableListPreferredChildPaddingLeft",
+    "expandableListPreferredItemIndicatorLeft",
+    "expandableListPreferredItemIndicatorRight",
+    "expandableListPreferredChildIndicatorLeft",
+    "expandableListPreferredChildIndicatorRight",
+    "windowBackground",
+    "windowFrame",
+    "windowNoTitle",
+    "windowIsFloating",
+    "windowIsTranslucent",
+    "windowContentOverlay",
+    "windowTitleSize",
+    "windowTitleStyle",
+    "windowTitleBackgroundStyle",
+    "alertDialogStyle",
+    "panelBackground",
+    "panelFullBackground",
+    "panelColorForeground",
+    "panelColorBackground",
+    "panelTextAppearance",
+    "scrollbarSize",
+    "scrollbarThumbHorizontal",
+    "scrollbarThumbVertical",
+    "scrollbarTrackHorizontal",
+    "scrollbarTrackVertical",
+    "scrollbarAlwaysDrawHorizontalTrack",
+    "scrollbarAlwaysDrawVerticalTrack",
+    "absListViewStyle",
+    "autoCompleteTextViewStyle",
+    "checkboxStyle",
+    "dropDownListViewStyle",
+    "editTextStyle",
+    "expandableListViewStyle",
+    "galleryStyle",
+    "gridViewStyle",
+    "imageButtonStyle",
+    "imageWellStyle",
+    "listViewStyle",
+    "listViewWhiteStyle",
+    "popupWindowStyle",
+    "progressBarStyle",
+    "progressBarStyleHorizontal",
+    "progressBarStyleSmall",
+    "progressBarStyleLarge",
+    "seekBarStyle",
+    "ratingBarStyle",
+    "ratingBarStyleSmall",
+    "radioButtonStyle",
+    "scrollbarStyle",
+    "scrollViewStyle",
+    "spinnerStyle",
+    "starStyle",
+    "tabWidgetStyle",
+    "textViewStyle",
+    "webViewStyle",
+    "dropDownItemStyle",
+    "spinnerDropDownItemStyle",
+    "dropDownHintAppearance",
+    "spinnerItemStyle",
+    "mapViewStyle",
+    "preferenceScreenStyle",
+    "preferenceCategoryStyle",
+    "preferenceInformationStyle",
+    "preferenceStyle",
+    "checkBoxPreferenceStyle",
+    "yesNoPreferenceStyle",
+    "dialogPreferenceStyle",
+    "editTextPreferenceStyle",
+    "ringtonePreferenceStyle",
+    "preferenceLayoutChild",
+    "textSize",
+    "typeface",
+    "textStyle",
+    "textColor",
+    "textColorHighlight",
+    "textColorHint",
+    "textColorLink",
+    "state_focused",
+    "state_window_focused",
+    "state_enabled",
+    "state_checkable",
+    "state_checked",
+    "state_selected",
+    "state_active",
+    "state_single",
+    "state_first",
+    "state_middle",
+    "state_last",
+    "state_pressed",
+    "state_expanded",
+    "state_empty",
+    "state_above_anchor",
+    "ellipsize",
+    "x",
+    "y",
+    "windowAnimationStyle",
+    "gravity",
+    "autoLink",
+    "linksClickable",
+    "entries",
+    "layout_gravity",
+    "windowEnterAnimation",
+    "windowExitAnimation",
+    "windowShowAnimation",
+    "windowHideAnimation",
+    "activityOpenEnterAnimation",
+    "activityOpenExitAnimation",
+    "activityCloseEnterAnimation",
+    "activityCloseExitAnimation",
+    "taskOpenEnterAnimation",
+    "taskOpenExitAnimation",
+    "taskCloseEnterAnimation",
+    "taskCloseExitAnimation",
+    "taskToFrontEnterAnimation",
+    "taskToFrontExitAnimation",
+    "taskToBackEnterAnimation",
+    "taskToBackExitAnimation",
+    "orientation",
+    "keycode",
+    "fullDark",
+    "topDark",
+    "centerDark",
+    "bottomDark",
+    "fullBright",
+    "topBright",
+    "centerBright",
+    "bottomBright",
+    "bottomMedium",
+    "centerMedium",
+    "id",
+    "tag",
+    "scrollX",
+    "scrollY",
+    "background",
+    "padding",
+    "paddingLeft",
+    "paddingTop",
+    "paddingRight",
+    "paddingBottom",
+    "focusable",
+    "focusableInTouchMode",
+    "visibility",
+    "fitsSystemWindows",
+    "scrollbars",
+    "fadingEdge",
+    "fadingEdgeLength",
+    "nextFocusLeft",
+    "nextFocusRight",
+    "nextFocusUp",
+    "nextFocusDown",
+    "clickable",
+    "longClickable",
+    "saveEnabled",
+    "drawingCacheQuality",
+    "duplicateParentState",
+    "clipChildren",
+    "clipToPadding",
+    "layoutAnimation",
+    "animationCache",
+    "persistentDrawingCache",
+    "alwaysDrawnWithCache",
+    "addStatesFromChildren",
+    "descendantFocusability",
+    "layout",
+    "inflatedId",
+    "layout_width",
+    "layout_height",
+    "layout_margin",
+    "layout_marginLeft",
+    "layout_marginTop",
+    "layout_marginRight",
+    "layout_marginBottom",
+    "listSelector",
+    "drawSelectorOnTop",
+    "stackFromBottom",
+    "scrollingCache",
+    "textFilterEnabled",
+    "transcriptMode",
+    "cacheColorHint",
+    "dial",
+    "hand_hour",
+    "hand_minute",
+    "format",
+    "checked",
+    "button",
+    "checkMark",
+    "foreground",
+    "measureAllChildren",
+    "groupIndicator",
+    "childIndicator",
+    "indicatorLeft",
+    "indicatorRight",
+    "childIndicatorLeft",
+    "childIndicatorRight",
+    "childDivider",
+    "animationDuration",
+    "spacing",
+    "horizontalSpacing",
+    "verticalSpacing",
+    "stretchMode",
+    "columnWidth",
+    "numColumns",
+    "src",
+    "antialias",
+    "filter",
+    "dither",
+    "scaleType",
+    "adjustViewBounds",
+    "maxWidth",
+    "maxHeight",
+    "tint",
+    "baselineAlignBottom",
+    "cropToPadding",
+    "textOn",
+    "textOff",
+    "baselineAligned",
+    "baselineAlignedChildIndex",
+    "weightSum",
+    "divider",
+    "dividerHeight",
+    "choiceMode",
+    "itemTextAppearance",
+    "horizontalDivider",
+    "verticalDivider",
+    "headerBackground",
+    "itemBackground",
+    "itemIconDisabledAlpha",
+    "rowHeight",
+    "maxRows",
+    "maxItemsPerRow",
+    "moreIcon",
+    "max",
+    "progress",
+    "secondaryProgress",
+    "indeterminate",
+    "indeterminateOnly",
+    "indeterminateDrawable",
+    "progressDrawable",
+    "indeterminateDuration",
+    "indeterminateBehavior",
+    "minWidth",
+    "minHeight",
+    "interpolator",
+    "thumb",
+    "thumbOffset",
+    "numStars",
+    "rating",
+    "stepSize",
+    "isIndicator",
+    "checkedButton",
+    "stretchColumns",
+    "shrinkColumns",
+    "collapseColumns",
+    "layout_column",
+    "layout_span",
+    "bufferType",
+    "text",
+    "hint",
+    "textScaleX",
+    "cursorVisible",
+    "maxLines",
+    "lines",
+    "height",
+    "minLines",
+    "maxEms",
+    "ems",
+    "width",
+    "minEms",
+    "scrollHorizontally",
+    "password",
+    "singleLine",
+    "selectAllOnFocus",
+    "includeFontPadding",
+    "maxLength",
+    "shadowColor",
+    "shadowDx",
+    "shadowDy",
+    "shadowRadius",
+    "numeric",
+    "digits",
+    "phoneNumber",
+    "inputMethod",
+    "capitalize",
+    "autoText",
+    "editable",
+    "freezesText",
+    "drawableTop",
+    "drawableBottom",
+    "drawableLeft",
+    "drawableRight",
+    "drawablePadding",
+    "completionHint",
+    "completionHintView",
+    "completionThreshold",
+    "dropDownSelector",
+    "popupBackground",
+    "inAnimation",
+    "outAnimation",
+    "flipInterval",
+    "fillViewport",
+    "prompt",
+    "startYear",
+    "endYear",
+    "mode",
+    "layout_x",
+    "layout_y",
+    "layout_weight",
+    "layout_toLeftOf",
+    "layout_toRightOf",
+    "layout_above",
+    "layout_below",
+    "layout_alignBaseline",
+    "layout_alignLeft",
+    "layout_alignTop",
+    "layout_alignRight",
+    "layout_alignBottom",
+    "layout_alignParentLeft",
+    "layout_alignParentTop",
+    "layout_alignParentRight",
+    "layout_alignParentBottom",
+    "layout_centerInParent",
+    "layout_centerHorizontal",
+    "layout_centerVertical",
+    "layout_alignWithParentIfMissing",
+    "layout_scale",
+    "visible",
+    "variablePadding",
+    "constantSize",
+    "oneshot",
+    "duration",
+    "drawable",
+    "shape",
+    "innerRadiusRatio",
+    "thicknessRatio",
+    "startColor",
+    "endColor",
+    "useLevel",
+    "angle",
+    "type",
+    "centerX",
+    "centerY",
+    "gradientRadius",
+    "color",
+    "dashWidth",
+    "dashGap",
+    "radius",
+    "topLeftRadius",
+    "topRightRadius",
+    "bottomLeftRadius",
+    "bottomRightRadius",
+    "left",
+    "top",
+    "right",
+    "bottom",
+    "minLevel",
+    "maxLevel",
+    "fromDegrees",
+    "toDegrees",
+    "pivotX",
+    "pivotY",
+    "insetLeft",
+    "insetRight",
+    "insetTop",
+    "insetBottom",
+    "shareInterpolator",
+    "fillBefore",
+    "fillAfter",
+    "startOffset",
+    "repeatCount",
+    "repeatMode",
+    "zAdjustment",
+    "fromXScale",
+    "toXScale",
+    "fromYScale",
+    "toYScale",
+    "fromXDelta",
+    "toXDelta",
+    "fromYDelta",
+    "toYDelta",
+    "fromAlpha",
+    "toAlpha",
+    "delay",
+    "animation",
+    "animationOrder",
+    "columnDelay",
+    "rowDelay",
+    "direction",
+    "directionPriority",
+    "factor",
+    "cycles",
+    "searchMode",
+    "searchSuggestAuthority",
+    "searchSuggestPath",
+    "searchSuggestSelection",
+    "searchSuggestIntentAction",
+    "searchSuggestIntentData",
+    "queryActionMsg",
+    "suggestActionMsg",
+    "suggestActionMsgColumn",
+    "menuCategory",
+    "orderInCategory",
+    "checkableBehavior",
+    "title",
+    "titleCondensed",
+    "alphabeticShortcut",
+    "numericShortcut",
+    "checkable",
+    "selectable",
+    "orderingFromXml",
+    "key",
+    "summary",
+    "order",
+    "widgetLayout",
+    "dependency",
+    "defaultValue",
+    "shouldDisableView",
+    "summaryOn",
+    "summaryOff",
+    "disableDependentsState",
+    "dialogTitle",
+    "dialogMessage",
+    "dialogIcon",
+    "positiveButtonText",
+    "negativeButtonText",
+    "dialogLayout",
+    "entryValues",
+    "ringtoneType",
+    "showDefault",
+    "showSilent",
+    "scaleWidth",
+    "scaleHeight",
+    "scaleGravity",
+    "ignoreGravity",
+    "foregroundGravity",
+    "tileMode",
+    "targetActivity",
+    "alwaysRetainTaskState",
+    "allowTaskReparenting",
+    "searchButtonText",
+    "colorForegroundInverse",
+    "textAppearanceButton",
+    "listSeparatorTextViewStyle",
+    "streamType",
+    "clipOrientation",
+    "centerColor",
+    "minSdkVersion",
+    "windowFullscreen",
+    "unselectedAlpha",
+    "progressBarStyleSmallTitle",
+    "ratingBarStyleIndicator",
+    "apiKey",
+    "textColorTertiary",
+    "textColorTertiaryInverse",
+    "listDivider",
+    "soundEffectsEnabled",
+    "keepScreenOn",
+    "lineSpacingExtra",
+    "lineSpacingMultiplier",
+    "listChoiceIndicatorSingle",
+    "listChoiceIndicatorMultiple",
+    "versionCode",
+    "versionName",
+    "marqueeRepeatLimit",
+    "windowNoDisplay",
+    "backgroundDimEnabled",
+    "inputType",
+    "isDefault",
+    "windowDisablePreview",
+    "privateImeOptions",
+    "editorExtras",
+    "settingsActivity",
+    "fastScrollEnabled",
+    "reqTouchScreen",
+    "reqKeyboardType",
+    "reqHardKeyboard",
+    "reqNavigation",
+    "windowSoftInputMode",
+    "imeFullscreenBackground",
+    "noHistory",
+    "headerDividersEnabled",
+    "footerDividersEnabled",
+    "candidatesTextStyleSpans",
+    "smoothScrollbar",
+    "reqFiveWayNav",
+    "keyBackground",
+    "keyTextSize",
+    "labelTextSize",
+    "keyTextColor",
+    "keyPreviewLayout",
+    "keyPreviewOffset",
+    "keyPreviewHeight",
+    "verticalCorrection",
+    "popupLayout",
+    "state_long_pressable",
+    "keyWidth",
+    "keyHeight",
+    "horizontalGap",
+    "verticalGap",
+    "rowEdgeFlags",
+    "codes",
+    "popupKeyboard",
+    "popupCharacters",
+    "keyEdgeFlags",
+    "isModifier",
+    "isSticky",
+    "isRepeatable",
+    "iconPreview",
+    "keyOutputText",
+    "keyLabel",
+    "keyIcon",
+    "keyboardMode",
+    "isScrollContainer",
+    "fillEnabled",
+    "updatePeriodMillis",
+    "initialLayout",
+    "voiceSearchMode",
+    "voiceLanguageModel",
+    "voicePromptText",
+    "voiceLanguage",
+    "voiceMaxResults",
+    "bottomOffset",
+    "topOffset",
+    "allowSingleTap",
+    "handle",
+    "content",
+    "animateOnClick",
+    "configure",
+    "hapticFeedbackEnabled",
+    "innerRadius",
+    "thickness",
+    "sharedUserLabel",
+    "dropDownWidth",
+    "dropDownAnchor",
+    "imeOptions",
+    "imeActionLabel",
+    "imeActionId",
+    "UNKNOWN",
+    "imeExtractEnterAnimation",
+    "imeExtractExitAnimation",
+    "tension",
+    "extraTension",
+    "anyDensity",
+    "searchSuggestThreshold",
+    "includeInGlobalSearch",
+    "onClick",
+    "targetSdkVersion",
+    "maxSdkVersion",
+    "testOnly",
+    "contentDescription",
+    "gestureStrokeWidth",
+    "gestureColor",
+    "uncertainGestureColor",
+    "fadeOffset",
+    "fadeDuration",
+    "gestureStrokeType",
+    "gestureStrokeLengthThreshold",
+    "gestureStrokeSquarenessThreshold",
+    "gestureStrokeAngleThreshold",
+    "eventsInterceptionEnabled",
+    "fadeEnabled",
+    "backupAgent",
+    "allowBackup",
+    "glEsVersion",
+    "queryAfterZeroResults",
+    "dropDownHeight",
+    "smallScreens",
+    "normalScreens",
+    "largeScreens",
+    "progressBarStyleInverse",
+    "progressBarStyleSmallInverse",
+    "progressBarStyleLargeInverse",
+    "searchSettingsDescription",
+    "textColorPrimaryInverseDisableOnly",
+    "autoUrlDetect",
+    "resizeable",
+    "required",
+    "accountType",
+    "contentAuthority",
+    "userVisible",
+    "windowShowWallpaper",
+    "wallpaperOpenEnterAnimation",
+    "wallpaperOpenExitAnimation",
+    "wallpaperCloseEnterAnimation",
+    "wallpaperCloseExitAnimation",
+    "wallpaperIntraOpenEnterAnimation",
+    "wallpaperIntraOpenExitAnimation",
+    "wallpaperIntraCloseEnterAnimation",
+    "wallpaperIntraCloseExitAnimation",
+    "supportsUploading",
+    "killAfterRestore",
+    "restoreNeedsApplication",
+    "smallIcon",
+    "accountPreferences",
+    "textAppearanceSearchResultSubtitle",
+    "textAppearanceSearchResultTitle",
+    "summaryColumn",
+    "detailColumn",
+    "detailSocialSummary",
+    "thumbnail",
+    "detachWallpaper",
+    "finishOnCloseSystemDialogs",
+    "scrollbarFadeDuration",
+    "scrollbarDefaultDelayBeforeFade",
+    "fadeScrollbars",
+    "colorBackgroundCacheHint",
+    "dropDownHorizontalOffset",
+    "dropDownVerticalOffset",
+    "quickContactBadgeStyleWindowSmall",
+    "quickContactBadgeStyleWindowMedium",
+    "quickContactBadgeStyleWindowLarge",
+    "quickContactBadgeStyleSmallWindowSmall",
+    "quickContactBadgeStyleSmallWindowMedium",
+    "quickContactBadgeStyleSmallWindowLarge",
+    "author",
+    "autoStart",
+    "expandableListViewWhiteStyle",
+    "installLocation",
+    "vmSafeMode",
+    "webTextViewStyle",
+    "restoreAnyVersion",
+    "tabStripLeft",
+    "tabStripRight",
+    "tabStripEnabled",
+    "logo",
+    "xlargeScreens",
+    "immersive",
+    "overScrollMode",
+    "overScrollHeader",
+    "overScrollFooter",
+    "filterTouchesWhenObscured",
+    "textSelectHandleLeft",
+    "textSelectHandleRight",
+    "textSelectHandle",
+    "textSelectHandleWindowStyle",
+    "popupAnimationStyle",
+    "screenSize",
+    "screenDensity",
+    "allContactsName",
+    "windowActionBar",
+    "actionBarStyle",
+    "navigationMode",
+    "displayOptions",
+    "subtitle",
+    "customNavigationLayout",
+    "hardwareAccelerated",
+    "measureWithLargestChild",
+    "animateFirstView",
+    "dropDownSpinnerStyle",
+    "actionDropDownStyle",
+    "actionButtonStyle",
+    "showAsAction",
+    "previewImage",
+    "actionModeBackground",
+    "actionModeCloseDrawable",
+    "windowActionModeOverlay",
+    "valueFrom",
+    "valueTo",
+    "valueType",
+    "propertyName",
+    "ordering",
+    "fragment",
+    "windowActionBarOverlay",
+    "fragmentOpenEnterAnimation",
+    "fragmentOpenExitAnimation",
+    "fragmentCloseEnterAnimation",
+    "fragmentCloseExitAnimation",
+    "fragmentFadeEnterAnimation",
+    "fragmentFadeExitAnimation",
+    "actionBarSize",
+    "imeSubtypeLocale",
+    "imeSubtypeMode",
+    "imeSubtypeExtraValue",
+    "splitMotionEvents",
+    "listChoiceBackgroundIndicator",
+    "spinnerMode",
+    "animateLayoutChanges",
+    "actionBarTabStyle",
+    "actionBarTabBarStyle",
+    "actionBarTabTextStyle",
+    "actionOverflowButtonStyle",
+    "actionModeCloseButtonStyle",
+    "titleTextStyle",
+    "subtitleTextStyle",
+    "iconifiedByDefault",
+    "actionLayout",
+    "actionViewClass",
+    "activatedBackgroundIndicator",
+    "state_activated",
+    "listPopupWindowStyle",
+    "popupMenuStyle",
+    "textAppearanceLargePopupMenu",
+    "textAppearanceSmallPopupMenu",
+    "breadCrumbTitle",
+    "breadCrumbShortTitle",
+    "listDividerAlertDialog",
+    "textColorAlertDialogListItem",
+    "loopViews",
+    "dialogTheme",
+    "alertDialogTheme",
+    "dividerVertical",
+    "homeAsUpIndicator",
+    "enterFadeDuration",
+    "exitFadeDuration",
+    "selectableItemBackground",
+    "autoAdvanceViewId",
+    "useIntrinsicSizeAsMinimum",
+    "actionModeCutDrawable",
+    "actionModeCopyDrawable",
+    "actionModePasteDrawable",
+    "textEditPasteWindowLayout",
+    "textEditNoPasteWindowLayout",
+    "textIsSelectable",
+    "windowEnableSplitTouch",
+    "indeterminateProgressStyle",
+    "progressBarPadding",
+    "animationResolution",
+    "state_accelerated",
+    "baseline",
+    "homeLayout",
+    "opacity",
+    "alpha",
+    "transformPivotX",
+    "transformPivotY",
+    "translationX",
+    "translationY",
+    "scaleX",
+    "scaleY",
+    "rotation",
+    "rotationX",
+    "rotationY",
+    "showDividers",
+    "dividerPadding",
+    "borderlessButtonStyle",
+    "dividerHorizontal",
+    "itemPadding",
+    "buttonBarStyle",
+    "buttonBarButtonStyle",
+    "segmentedButtonStyle",
+    "staticWallpaperPreview",
+    "allowParallelSyncs",
+    "isAlwaysSyncable",
+    "verticalScrollbarPosition",
+    "fastScrollAlwaysVisible",
+    "fastScrollThumbDrawable",
+    "fastScrollPreviewBackgroundLeft",
+    "fastScrollPreviewBackgroundRight",
+    "fastScrollTrackDrawable",
+    "fastScrollOverlayPosition",
+    "customTokens",
+    "nextFocusForward",
+    "firstDayOfWeek",
+    "showWeekNumber",
+    "minDate",
+    "maxDate",
+    "shownWeekCount",
+    "selectedWeekBackgroundColor",
+    "focusedMonthDateColor",
+    "unfocusedMonthDateColor",
+    "weekNumberColor",
+    "weekSeparatorLineColor",
+    "selectedDateVerticalBar",
+    "weekDayTextAppearance",
+    "dateTextAppearance",
+    "UNKNOWN",
+    "spinnersShown",
+    "calendarViewShown",
+    "state_multiline",
+    "detailsElementBackground",
+    "textColorHighlightInverse",
+    "textColorLinkInverse",
+    "editTextColor",
+    "editTextBackground",
+    "horizontalScrollViewStyle",
+    "layerType",
+    "alertDialogIcon",
+    "windowMinWidthMajor",
+    "windowMinWidthMinor",
+    "queryHint",
+    "fastScrollTextColor",
+    "largeHeap",
+    "windowCloseOnTouchOutside",
+    "datePickerStyle",
+    "calendarViewStyle",
+    "textEditSidePasteWindowLayout",
+    "textEditSideNoPasteWindowLayout",
+    "actionMenuTextAppearance",
+    "actionMenuTextColor",
+    "textCursorDrawable",
+    "resizeMode",
+    "requiresSmallestWidthDp",
+    "compatibleWidthLimitDp",
+    "largestWidthLimitDp",
+    "state_hovered",
+    "state_drag_can_accept",
+    "state_drag_hovered",
+    "stopWithTask",
+    "switchTextOn",
+    "switchTextOff",
+    "switchPreferenceStyle",
+    "switchTextAppearance",
+    "track",
+    "switchMinWidth",
+    "switchPadding",
+    "thumbTextPadding",
+    "textSuggestionsWindowStyle",
+    "textEditSuggestionItemLayout",
+    "rowCount",
+    "rowOrderPreserved",
+    "columnCount",
+    "columnOrderPreserved",
+    "useDefaultMargins",
+    "alignmentMode",
+    "layout_row",
+    "layout_rowSpan",
+    "layout_columnSpan",
+    "actionModeSelectAllDrawable",
+    "isAuxiliary",
+    "accessibilityEventTypes",
+    "packageNames",
+    "accessibilityFeedbackType",
+    "notificationTimeout",
+    "accessibilityFlags",
+    "canRetrieveWindowContent",
+    "listPreferredItemHeightLarge",
+    "listPreferredItemHeightSmall",
+    "actionBarSplitStyle",
+    "actionProviderClass",
+    "backgroundStacked",
+    "backgroundSplit",
+    "textAllCaps",
+    "colorPressedHighlight",
+    "colorLongPressedHighlight",
+    "colorFocusedHighlight",
+    "colorActivatedHighlight",
+    "colorMultiSelectHighlight",
+    "drawableStart",
+    "drawableEnd",
+    "actionModeStyle",
+    "minResizeWidth",
+    "minResizeHeight",
+    "actionBarWidgetTheme",
+    "uiOptions",
+    "subtypeLocale",
+    "subtypeExtraValue",
+    "actionBarDivider",
+    "actionBarItemBackground",
+    "actionModeSplitBackground",
+    "textAppearanceListItem",
+    "textAppearanceListItemSmall",
+    "targetDescriptions",
+    "directionDescriptions",
+    "overridesImplicitlyEnabledSubtype",
+    "listPreferredItemPaddingLeft",
+    "listPreferredItemPaddingRight",
+    "requiresFadingEdge",
+    "publicKey",
+    "parentActivityName",
+    "UNKNOWN",
+    "isolatedProcess",
+    "importantForAccessibility",
+    "keyboardLayout",
+    "fontFamily",
+    "mediaRouteButtonStyle",
+    "mediaRouteTypes",
+    "supportsRtl",
+    "textDirection",
+    "textAlignment",
+    "layoutDirection",
+    "paddingStart",
+    "paddingEnd",
+    "layout_marginStart",
+    "layout_marginEnd",
+    "layout_toStartOf",
+    "layout_toEndOf",
+    "layout_alignStart",
+    "layout_alignEnd",
+    "layout_alignParentStart",
+    "layout_alignParentEnd",
+    "listPreferredItemPaddingStart",
+    "listPreferredItemPaddingEnd",
+    "singleUser",
+    "presentationTheme",
+    "subtypeId",
+    "initialKeyguardLayout",
+    "UNKNOWN",
+    "widgetCategory",
+    "permissionGroupFlags",
+    "labelFor",
+    "permissionFlags",
+    "checkedTextViewStyle",
+    "showOnLockScreen",
+    "format12Hour",
+    "format24Hour",
+    "timeZone",
+    "mipMap",
+    "mirrorForRtl",
+    "windowOverscan",
+    "requiredForAllUsers",
+    "indicatorStart",
+    "indicatorEnd",
+    "childIndicatorStart",
+    "childIndicatorEnd",
+    "restrictedAccountType",
+    "requiredAccountType",
+    "canRequestTouchExplorationMode",
+    "canRequestEnhancedWebAccessibility",
+    "canRequestFilterKeyEvents",
+    "layoutMode",
+    "keySet",
+    "targetId",
+    "fromScene",
+    "toScene",
+    "transition",
+    "transitionOrdering",
+    "fadingMode",
+    "startDelay",
+    "ssp",
+    "sspPrefix",
+    "sspPattern",
+    "addPrintersActivity",
+    "vendor",
+    "category",
+    "isAsciiCapable",
+    "autoMirrored",
+    "supportsSwitchingToNextInputMethod",
+    "requireDeviceUnlock",
+    "apduServiceBanner",
+    "accessibilityLiveRegion",
+    "windowTranslucentStatus",
+    "windowTranslucentNavigation",
+    "advancedPrintOptionsActivity",
+    "banner",
+    "windowSwipeToDismiss",
+    "isGame",
+    "allowEmbedded",
+    "setupActivity",
+    "fastScrollStyle",
+    "windowContentTransitions",
+    "windowContentTransitionManager",
+    "translationZ",
+    "tintMode",
+    "controlX1",
+    "controlY1",
+    "controlX2",
+    "controlY2",
+    "transitionName",
+    "transitionGroup",
+    "viewportWidth",
+    "viewportHeight",
+    "fillColor",
+    "pathData",
+    "strokeColor",
+    "strokeWidth",
+    "trimPathStart",
+    "trimPathEnd",
+    "trimPathOffset",
+    "strokeLineCap",
+    "strokeLineJoin",
+    "strokeMiterLimit",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "colorControlNormal",
+    "colorControlActivated",
+    "colorButtonNormal",
+    "colorControlHighlight",
+    "persistableMode",
+    "titleTextAppearance",
+    "subtitleTextAppearance",
+    "slideEdge",
+    "actionBarTheme",
+    "textAppearanceListItemSecondary",
+    "colorPrimary",
+    "colorPrimaryDark",
+    "colorAccent",
+    "nestedScrollingEnabled",
+    "windowEnterTransition",
+    "windowExitTransition",
+    "windowSharedElementEnterTransition",
+    "windowSharedElementExitTransition",
+    "windowAllowReturnTransitionOverlap",
+    "windowAllowEnterTransitionOverlap",
+    "sessionService",
+    "stackViewStyle",
+    "switchStyle",
+    "elevation",
+    "excludeId",
+    "excludeClass",
+    "hideOnContentScroll",
+    "actionOverflowMenuStyle",
+    "documentLaunchMode",
+    "maxRecents",
+    "autoRemoveFromRecents",
+    "stateListAnimator",
+    "toId",
+    "fromId",
+    "reversible",
+    "splitTrack",
+    "targetName",
+    "excludeName",
+    "matchOrder",
+    "windowDrawsSystemBarBackgrounds",
+    "statusBarColor",
+    "navigationBarColor",
+    "contentInsetStart",
+    "contentInsetEnd",
+    "contentInsetLeft",
+    "contentInsetRight",
+    "paddingMode",
+    "layout_rowWeight",
+    "layout_columnWeight",
+    "translateX",
+    "translateY",
+    "selectableItemBackgroundBorderless",
+    "elegantTextHeight",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "windowTransitionBackgroundFadeDuration",
+    "overlapAnchor",
+    "progressTint",
+    "progressTintMode",
+    "progressBackgroundTint",
+    "progressBackgroundTintMode",
+    "secondaryProgressTint",
+    "secondaryProgressTintMode",
+    "indeterminateTint",
+    "indeterminateTintMode",
+    "backgroundTint",
+    "backgroundTintMode",
+    "foregroundTint",
+    "foregroundTintMode",
+    "buttonTint",
+    "buttonTintMode",
+    "thumbTint",
+    "thumbTintMode",
+    "fullBackupOnly",
+    "propertyXName",
+    "propertyYName",
+    "relinquishTaskIdentity",
+    "tileModeX",
+    "tileModeY",
+    "actionModeShareDrawable",
+    "actionModeFindDrawable",
+    "actionModeWebSearchDrawable",
+    "transitionVisibilityMode",
+    "minimumHorizontalAngle",
+    "minimumVerticalAngle",
+    "maximumAngle",
+    "searchViewStyle",
+    "closeIcon",
+    "goIcon",
+    "searchIcon",
+    "voiceIcon",
+    "commitIcon",
+    "suggestionRowLayout",
+    "queryBackground",
+    "submitBackground",
+    "buttonBarPositiveButtonStyle",
+    "buttonBarNeutralButtonStyle",
+    "buttonBarNegativeButtonStyle",
+    "popupElevation",
+    "actionBarPopupTheme",
+    "multiArch",
+    "touchscreenBlocksFocus",
+    "windowElevation",
+    "launchTaskBehindTargetAnimation",
+    "launchTaskBehindSourceAnimation",
+    "restrictionType",
+    "dayOfWeekBackground",
+    "dayOfWeekTextAppearance",
+    "headerMonthTextAppearance",
+    "headerDayOfMonthTextAppearance",
+    "headerYearTextAppearance",
+    "yearListItemTextAppearance",
+    "yearListSelectorColor",
+    "calendarTextColor",
+    "recognitionService",
+    "timePickerStyle",
+    "timePickerDialogTheme",
+    "headerTimeTextAppearance",
+    "headerAmPmTextAppearance",
+    "numbersTextColor",
+    "numbersBackgroundColor",
+    "numbersSelectorColor",
+    "amPmTextColor",
+    "amPmBackgroundColor",
+    "UNKNOWN",
+    "checkMarkTint",
+    "checkMarkTintMode",
+    "popupTheme",
+    "toolbarStyle",
+    "windowClipToOutline",
+    "datePickerDialogTheme",
+    "showText",
+    "windowReturnTransition",
+    "windowReenterTransition",
+    "windowSharedElementReturnTransition",
+    "windowSharedElementReenterTransition",
+    "resumeWhilePausing",
+    "datePickerMode",
+    "timePickerMode",
+    "inset",
+    "letterSpacing",
+    "fontFeatureSettings",
+    "outlineProvider",
+    "contentAgeHint",
+    "country",
+    "windowSharedElementsUseOverlay",
+    "reparent",
+    "reparentWithOverlay",
+    "ambientShadowAlpha",
+    "spotShadowAlpha",
+    "navigationIcon",
+    "navigationContentDescription",
+    "fragmentExitTransition",
+    "fragmentEnterTransition",
+    "fragmentSharedElementEnterTransition",
+    "fragmentReturnTransition",
+    "fragmentSharedElementReturnTransition",
+    "fragmentReenterTransition",
+    "fragmentAllowEnterTransitionOverlap",
+    "fragmentAllowReturnTransitionOverlap",
+    "patternPathData",
+    "strokeAlpha",
+    "fillAlpha",
+    "windowActivityTransitions",
+    "colorEdgeEffect",
+    "resizeClip",
+    "collapseContentDescription",
+    "accessibilityTraversalBefore",
+    "accessibilityTraversalAfter",
+    "dialogPreferredPadding",
+    "searchHintIcon",
+    "revisionCode",
+    "drawableTint",
+    "drawableTintMode",
+    "fraction",
+    "trackTint",
+    "trackTintMode",
+    "start",
+    "end",
+    "breakStrategy",
+    "hyphenationFrequency",
+    "allowUndo",
+    "windowLightStatusBar",
+    "numbersInnerTextColor",
+    "colorBackgroundFloating",
+    "titleTextColor",
+    "subtitleTextColor",
+    "thumbPosition",
+    "scrollIndicators",
+    "contextClickable",
+    "fingerprintAuthDrawable",
+    "logoDescription",
+    "extractNativeLibs",
+    "fullBackupContent",
+    "usesCleartextTraffic",
+    "lockTaskMode",
+    "autoVerify",
+    "showForAllUsers",
+    "supportsAssist",
+    "supportsLaunchVoiceAssistFromKeyguard",
+    "listMenuViewStyle",
+    "subMenuArrow",
+    "defaultWidth",
+    "defaultHeight",
+    "resizeableActivity",
+    "supportsPictureInPicture",
+    "titleMargin",
+    "titleMarginStart",
+    "titleMarginEnd",
+    "titleMarginTop",
+    "titleMarginBottom",
+    "maxButtonHeight",
+    "buttonGravity",
+    "collapseIcon",
+    "level",
+    "contextPopupMenuStyle",
+    "textAppearancePopupMenuHeader",
+    "windowBackgroundFallback",
+    "defaultToDeviceProtectedStorage",
+    "directBootAware",
+    "preferenceFragmentStyle",
+    "canControlMagnification",
+    "languageTag",
+    "pointerIcon",
+    "tickMark",
+    "tickMarkTint",
+    "tickMarkTintMode",
+    "canPerformGestures",
+    "externalService",
+    "supportsLocalInteraction",
+    "startX",
+    "startY",
+    "endX",
+    "endY",
+    "offset",
+    "use32bitAbi",
+    "bitmap",
+    "hotSpotX",
+    "hotSpotY",
+    "version",
+    "backupInForeground",
+    "countDown",
+    "canRecord",
+    "tunerCount",
+    "fillType",
+    "popupEnterTransition",
+    "popupExitTransition",
+    "forceHasOverlappingRendering",
+    "contentInsetStartWithNavigation",
+    "contentInsetEndWithActions",
+    "numberPickerStyle",
+    "enableVrMode",
+    "UNKNOWN",
+    "networkSecurityConfig",
+    "shortcutId",
+    "shortcutShortLabel",
+    "shortcutLongLabel",
+    "shortcutDisabledMessage",
+    "roundIcon",
+    "contextUri",
+    "contextDescription",
+    "showMetadataInPreview",
+    "colorSecondary",
+];
+
+const RESOURCE_ID_BASE: u32 = 0x0101_0000;
+
+pub(crate) fn get_resource_string(resource_id: u32) -> Option<String> {
+    get_resource_name(resource_id).map(str::to_string)
+}
+
+pub(crate) fn get_resource_name(resource_id: u32) -> Option<&'static str> {
+    let i = resource_id.checked_sub(RESOURCE_ID_BASE)?;
+
+    RESOURCE_STRINGS.get(usize::try_from(i).unwrap()).copied()
+}
+
+pub(crate) fn get_resource_id(name: &str) -> Option<u32> {
+    if name == "UNKNOWN" {
+        return None;
+    }
+
+    let i = RESOURCE_STRINGS.iter().position(|s| *s == name)?;
+
+    Some(RESOURCE_ID_BASE + u32::try_from(i).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binaryxml::{
+        self, ChunkHeader, ResourceType, XmlAttrExt, XmlCdata, XmlEndElement, XmlNode,
+        XmlNodeHeader, XmlNodeType, XmlStartElement, XmlStartNameSpace,
+    };
+    use crate::resource_value::{ResourceValue, ResourceValueType};
+    use crate::stringpool::StringPool;
+    use crate::{parse, parse_with_options, ParseError, ParseOptions};
+    use deku::DekuContainerWrite;
+
+    fn node(typ: ResourceType, element: XmlNodeType) -> XmlNode {
+        let mut node = XmlNode {
+            header: XmlNodeHeader {
+                chunk_header: ChunkHeader {
+                    typ,
+                    header_size: 16,
+                    size: 0,
+                },
+                line_no: 1,
+                comment: u32::MAX,
+            },
+            element,
+        };
+        node.header.chunk_header.size = u32::try_from(node.to_bytes().unwrap().len()).unwrap();
+        node
+    }
+
+    fn document(strings: &[&str], elements: &[XmlNode]) -> Vec<u8> {
+        let string_pool = StringPool::encode(strings).unwrap();
+        binaryxml::write_document(&string_pool, Vec::new(), elements).unwrap()
+    }
+
+    fn start_namespace_node(prefix: u32, uri: u32) -> XmlNode {
+        node(
+            ResourceType::XmlStartNameSpace,
+            XmlNodeType::XmlStartNameSpace(XmlStartNameSpace { prefix, uri }),
+        )
+    }
+
+    fn start_element_node(name: u32) -> XmlNode {
+        ns_start_element_node(u32::MAX, name)
+    }
+
+    fn ns_start_element_node(ns: u32, name: u32) -> XmlNode {
+        node(
+            ResourceType::XmlStartElement,
+            XmlNodeType::XmlStartElement(start_element(ns, name)),
+        )
+    }
+
+    fn end_element_node(name: u32) -> XmlNode {
+        node(
+            ResourceType::XmlEndElement,
+            XmlNodeType::XmlEndElement(XmlEndElement { ns: u32::MAX, name }),
+        )
+    }
+
+    fn cdata_node(data: u32) -> XmlNode {
+        node(
+            ResourceType::XmlCdata,
+            XmlNodeType::XmlCdata(XmlCdata {
+                data,
+                typed_data: ResourceValue {
+                    size: 8,
+                    res: 0,
+                    data_type: ResourceValueType::Null,
+                    data: 0,
+                },
+            }),
+        )
+    }
+
+    fn start_element(ns: u32, name: u32) -> XmlStartElement {
+        XmlStartElement {
+            attr_ext: XmlAttrExt {
+                ns,
+                name,
+                attribute_start: 20,
+                attribute_size: 20,
+                attribute_count: 0,
+                id_index: 0,
+                class_index: 0,
+                style_index: 0,
+            },
+            attributes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_element_namespace() {
+        let data = document(
+            &[
+                "amazon",
+                "http://schemas.amazon.com/apk/res/android",
+                "activity",
+                "http://example.com/undeclared",
+            ],
+            &[
+                start_namespace_node(0, 1),
+                start_element_node(2),
+                ns_start_element_node(1, 2),
+                end_element_node(2),
+                ns_start_element_node(3, 2),
+                end_element_node(2),
+                end_element_node(2),
+            ],
+        );
+        let xml = parse(&data).unwrap();
+
+        let root = xml.root().as_element().unwrap();
+        assert_eq!(root.get_tag(), "activity");
+
+        let tags: Vec<&str> = root
+            .iter_children_elements()
+            .map(Element::get_tag)
+            .collect();
+        assert_eq!(tags, ["amazon:activity", "undeclared:activity"]);
+
+        // The undeclared namespace is declared on the element that uses it.
+        let undeclared = root.get_children()[1].as_element().unwrap();
+        assert_eq!(
+            undeclared.get_declared_namespaces(),
+            [(
+                "undeclared".to_string(),
+                "http://example.com/undeclared".to_string()
+            )]
+        );
+        assert_eq!(
+            xml.get_namespaces().get("undeclared").map(String::as_str),
+            Some("http://example.com/undeclared")
+        );
+    }
+
+    #[test]
+    fn test_unexpected_end_element() {
+        let data = document(&["manifest"], &[end_element_node(0)]);
+        assert_eq!(parse(&data), Err(ParseError::UnexpectedEndElement));
+    }
+
+    #[test]
+    fn test_lenient_string_not_found() {
+        let data = document(
+            &["string"],
+            &[start_element_node(0), cdata_node(7), end_element_node(0)],
+        );
+        assert_eq!(parse(&data), Err(ParseError::StringNotFound(7)));
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        let xml = parse_with_options(&data, &options).unwrap();
+        let root = xml.root().as_element().unwrap();
+        assert!(
+            matches!(root.get_children().first(), Some(Node::Cdata(c)) if c.get_data() == "!!unresolved-string-0x7!!")
+        );
+        assert_eq!(
+            xml.get_warnings(),
+            ["String pool index 0x7 is out of range at offset 100"]
+        );
+    }
+
+    #[test]
+    fn test_cdata_outside_element() {
+        let data = document(
+            &["string", "text"],
+            &[start_element_node(0), cdata_node(1), end_element_node(0)],
+        );
+        let xml = parse(&data).unwrap();
+        let Node::Element(root) = xml.root() else {
+            panic!("missing root element");
+        };
+        assert!(
+            matches!(root.get_children().first(), Some(Node::Cdata(c)) if c.get_data() == "text")
+        );
+
+        let data = document(&["text"], &[cdata_node(0)]);
+        assert_eq!(parse(&data), Err(ParseError::CdataOutsideElement));
+    }
+
+    #[test]
+    fn test_interned_strings() {
+        let data = include_bytes!("../examples/AndroidManifest.xml");
+        let doc = crate::parse(data).unwrap();
+        let application = doc.find_first_element_by_tag("application").unwrap();
+        let activity = doc.find_first_element_by_tag("activity").unwrap();
+
+        let (a, a_value) = application
+            .attributes
+            .get_key_value("android:label")
+            .unwrap();
+        let (b, b_value) = activity.attributes.get_key_value("android:label").unwrap();
+        assert!(Arc::ptr_eq(a, b));
+        assert!(Arc::ptr_eq(a_value, b_value));
+        assert_eq!(doc.get_distinct_string_count(), 24);
+
+        // Strings set later reuse the copies the document already holds.
+        let mut doc = doc.clone();
+        let tag = doc
+            .find_first_element_by_tag("activity")
+            .unwrap()
+            .tag
+            .clone();
+        let root = doc.root_mut().as_element_mut().unwrap();
+        root.set_attribute("android:label", "activity");
+        let (name, value) = root.attributes.get_key_value("android:label").unwrap();
+        assert!(Arc::ptr_eq(name, a));
+        assert!(Arc::ptr_eq(value, &tag));
+
+        let mut child = Element::new("activity");
+        child.set_tag("activity");
+        root.add_child(Node::Element(child));
+        let added = root.iter_children_elements().last().unwrap();
+        assert!(Arc::ptr_eq(&added.tag, &tag));
+        assert_eq!(doc.get_distinct_string_count(), 24);
+    }
+
+    #[test]
+    fn test_get_resource_string() {
+        assert_eq!(get_resource_string(0x0101_0003), Some("name".to_string()));
+        assert_eq!(get_resource_string(0x0000_0003), None);
+        assert_eq!(get_resource_string(0x0101_ffff), None);
+    }
+
+    #[test]
+    fn test_unexpected_eof() {
+        let data = document(
+            &["manifest", "application"],
+            &[start_element_node(0), start_element_node(1)],
+        );
+        assert_eq!(parse(&data), Err(ParseError::UnexpectedEof));
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        let xml = parse_with_options(&data, &options).unwrap();
+        let root = xml.root().as_element().unwrap();
+        assert_eq!(root.get_tag(), "manifest");
+        assert!(root.find_child_by_tag("application").is_some());
+        assert_eq!(
+            xml.get_warnings(),
+            ["Document ended with 2 unclosed element(s)"]
+        );
+    }
+
+    #[test]
+    fn test_extra_roots() {
+        let data = document(
+            &["manifest", "decoy"],
+            &[
+                start_element_node(0),
+                end_element_node(0),
+                start_element_node(1),
+                end_element_node(1),
+            ],
+        );
+        let xml = parse(&data).unwrap();
+
+        let root = xml.root().as_element().unwrap();
+        assert_eq!(root.get_tag(), "manifest");
+        let extra: Vec<&str> = xml
+            .get_extra_roots()
+            .iter()
+            .filter_map(Node::as_element)
+            .map(Element::get_tag)
+            .collect();
+        assert_eq!(extra, ["decoy"]);
+        assert_eq!(
+            xml.get_warnings(),
+            ["Found 1 top-level element(s) after the root element"]
+        );
+        assert_eq!(xml.find_elements_by_tag("decoy").len(), 0);
+    }
+
+    #[test]
+    fn test_invalid_data_after_root() {
+        let data = document(
+            &["manifest"],
+            &[
+                start_element_node(0),
+                end_element_node(0),
+                end_element_node(0),
+            ],
+        );
+        let xml = parse(&data).unwrap();
+        assert_eq!(xml.root().as_element().unwrap().get_tag(), "manifest");
+        assert_eq!(
+            xml.get_warnings(),
+            ["Ignored invalid data after the root element: End element found without a matching start element"]
+        );
+
+        // Only invalid nodes are ignored, not the limits on the document.
+        let data = document(
+            &["manifest"],
+            &[
+                start_element_node(0),
+                end_element_node(0),
+                start_element_node(0),
+                start_element_node(0),
+                end_element_node(0),
+                end_element_node(0),
+            ],
+        );
+        let options = ParseOptions {
+            max_depth: Some(1),
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            parse_with_options(&data, &options),
+            Err(ParseError::MaxDepthExceeded(1))
+        );
+    }
+
+    #[test]
+    fn test_no_root_element() {
+        let data = document(&["manifest"], &[]);
+        assert_eq!(parse(&data), Err(ParseError::NoRootElement));
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            parse_with_options(&data, &options),
+            Err(ParseError::NoRootElement)
+        );
+    }
+
+    #[test]
+    fn test_nested_same_tag() {
+        let mut inner = Element::new("LinearLayout");
+        inner.add_child(Node::Cdata(Cdata::new("text")));
+        inner.add_child(Node::Element(Element::new("TextView")));
+        let mut outer = Element::new("LinearLayout");
+        outer.add_child(Node::Element(inner));
+        outer.add_child(Node::Cdata(Cdata::new("more")));
+        let mut root = Element::new("FrameLayout");
+        root.add_child(Node::Element(outer));
+        let doc = XmlDocument::new(root);
+
+        let root = doc.root().as_element().unwrap();
+        assert_eq!(root.iter_descendant_nodes().count(), 5);
+        assert_eq!(root.iter_descendants_by_tag("LinearLayout").count(), 2);
+        assert_eq!(doc.iter_elements_by_tag("LinearLayout").count(), 2);
+        assert_eq!(doc.iter_elements_by_tag("FrameLayout").count(), 1);
+
+        let data: Vec<&str> = root
+            .iter_descendant_nodes()
+            .filter_map(Node::as_cdata)
+            .map(Cdata::get_data)
+            .collect();
+        assert_eq!(data, ["text", "more"]);
+
+        let path = "FrameLayout/LinearLayout/LinearLayout/TextView";
+        assert_eq!(doc.iter_elements_by_path(path).count(), 1);
+        assert_eq!(
+            doc.iter_elements_by_path("FrameLayout/LinearLayout")
+                .count(),
+            1
+        );
+        assert!(doc.find_element_by_path("FrameLayout/TextView").is_none());
+        assert!(doc.find_element_by_path("").is_none());
+    }
+
+    #[test]
+    fn test_node_accessors() {
+        let data = document(
+            &["string", "text"],
+            &[start_element_node(0), cdata_node(1), end_element_node(0)],
+        );
+        let xml = parse(&data).unwrap();
+        let root = xml.root();
+        #[allow(deprecated)]
+        let deprecated_root = xml.get_root();
+        assert_eq!(deprecated_root, Some(root));
+        assert!(root.as_cdata().is_none());
+
+        let root = root.as_element().unwrap();
+        assert_eq!(root.get_tag(), "string");
+
+        let cdata = root.get_children().first().unwrap();
+        assert!(cdata.as_element().is_none());
+        assert_eq!(cdata.as_cdata().unwrap().get_data(), "text");
+
+        let mut children = root
+            .get_children()
+            .iter()
+            .filter_map(Node::as_cdata)
+            .map(Cdata::get_data);
+        assert_eq!(children.next(), Some("text"));
+        assert_eq!(children.next(), None);
+
+        let root = xml.root.into_element().unwrap();
+        let cdata = root.children.into_iter().next().unwrap();
+        assert_eq!(cdata.into_cdata().unwrap().data, "text");
+    }
 }
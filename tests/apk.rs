@@ -0,0 +1,40 @@
+#![cfg(feature = "zip")]
+
+use std::io::Cursor;
+
+use axmldecoder::{parse_apk, parse_apk_reader, parse_file, ParseError};
+
+fn fixture(path: &str) -> String {
+    format!("{}/{}", env!("CARGO_MANIFEST_DIR"), path)
+}
+
+#[test]
+fn test_parse_apk() {
+    let expected = parse_file(fixture("examples/AndroidManifest.xml")).unwrap();
+    assert_eq!(
+        parse_apk(fixture("tests/fixtures/apk/Example.apk")).unwrap(),
+        expected
+    );
+
+    let data = std::fs::read(fixture("tests/fixtures/apk/Example.apk")).unwrap();
+    assert_eq!(parse_apk_reader(Cursor::new(data)).unwrap(), expected);
+}
+
+#[test]
+fn test_parse_apk_errors() {
+    assert!(matches!(
+        parse_apk(fixture("tests/fixtures/apk/NoManifest.apk")),
+        Err(ParseError::ZipError(zip::result::ZipError::FileNotFound))
+    ));
+
+    // A binary XML file is not a zip archive
+    assert!(matches!(
+        parse_apk(fixture("examples/AndroidManifest.xml")),
+        Err(ParseError::ZipError(_))
+    ));
+
+    assert!(matches!(
+        parse_apk(fixture("examples/Missing.apk")),
+        Err(ParseError::IoError(_))
+    ));
+}
@@ -0,0 +1,7 @@
+use axmldecoder::{parse_file, XmlDocument};
+
+// Parses the named file from the examples directory.
+pub fn parse_example(name: &str) -> XmlDocument {
+    let path = format!("{}/examples/{}", env!("CARGO_MANIFEST_DIR"), name);
+    parse_file(path).unwrap()
+}
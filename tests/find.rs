@@ -0,0 +1,363 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use axmldecoder::{encode, parse, Element, ParseError, XmlDocument};
+use common::parse_example;
+
+fn root(doc: &XmlDocument) -> &Element {
+    doc.root().as_element().unwrap()
+}
+
+#[test]
+fn test_find_child_by_tag() {
+    let doc = parse_example("AndroidManifest.xml");
+    let manifest = root(&doc);
+
+    let application = manifest.find_child_by_tag("application").unwrap();
+    assert_eq!(application.get_tag(), "application");
+
+    let activity = application.find_child_by_tag("activity").unwrap();
+    assert_eq!(activity.get_attribute("android:name"), Some("TCActivity"));
+
+    // Only direct children are searched.
+    assert!(manifest.find_child_by_tag("activity").is_none());
+    assert!(manifest.find_child_by_tag("service").is_none());
+}
+
+#[test]
+fn test_find_children_by_tag() {
+    let doc = parse_example("AndroidManifest.xml");
+    let intent_filter = root(&doc)
+        .find_child_by_tag("application")
+        .and_then(|e| e.find_child_by_tag("activity"))
+        .and_then(|e| e.find_child_by_tag("intent-filter"))
+        .unwrap();
+
+    let actions = intent_filter.find_children_by_tag("action");
+    assert_eq!(actions.len(), 1);
+    assert_eq!(
+        actions[0].get_attribute("android:name"),
+        Some("android.intent.action.MAIN")
+    );
+
+    assert!(intent_filter.find_children_by_tag("data").is_empty());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_find_element_by_tag_recursive() {
+    let doc = parse_example("AndroidManifest.xml");
+
+    let manifest = doc.find_element_by_tag_recursive("manifest").unwrap();
+    assert_eq!(
+        manifest.get_attribute("package"),
+        Some("org.t0t0.androguard.TC")
+    );
+
+    let category = doc.find_element_by_tag_recursive("category").unwrap();
+    assert_eq!(
+        category.get_attribute("android:name"),
+        Some("android.intent.category.LAUNCHER")
+    );
+
+    assert!(doc.find_element_by_tag_recursive("receiver").is_none());
+}
+
+#[test]
+fn test_attribute_order() {
+    let doc = parse_example("AndroidManifest.xml");
+    let manifest = root(&doc);
+
+    // Attributes are kept in the order they appear in the binary XML.
+    let keys: Vec<&str> = manifest.get_attributes().map(|(key, _)| key).collect();
+    assert_eq!(
+        keys,
+        [
+            "xmlns:android",
+            "android:versionCode",
+            "android:versionName",
+            "package"
+        ]
+    );
+
+    assert_eq!(
+        manifest.get_attribute("package"),
+        Some("org.t0t0.androguard.TC")
+    );
+    assert_eq!(manifest.get_attribute("android:versionName"), Some("1.0"));
+    assert_eq!(manifest.get_attribute("android:missing"), None);
+}
+
+#[test]
+fn test_get_attribute_ns() {
+    const ANDROID: &str = "http://schemas.android.com/apk/res/android";
+
+    // The Android namespace is declared with the prefix "a".
+    let doc = parse_example("AndroidManifestCustomPrefix.xml");
+    let activity = doc.find_first_element_by_tag("activity").unwrap();
+    assert_eq!(activity.get_attribute("android:name"), None);
+    assert_eq!(
+        activity.get_attribute_ns(ANDROID, "name"),
+        Some(".MainActivity")
+    );
+    assert_eq!(
+        activity.get_attribute_ns("http://example.com", "name"),
+        None
+    );
+    assert_eq!(activity.get_attribute_local("name"), Some(".MainActivity"));
+
+    let manifest = root(&doc);
+    assert_eq!(
+        manifest.get_attribute_ns("", "package"),
+        Some("com.example")
+    );
+    assert_eq!(manifest.get_attribute_ns(ANDROID, "package"), None);
+
+    let mut element = Element::new("activity");
+    element.set_attribute("android:exported", true);
+    assert_eq!(element.get_attribute_ns(ANDROID, "exported"), Some("true"));
+    assert_eq!(element.get_attribute_local("exported"), Some("true"));
+    assert_eq!(element.get_attribute_local("missing"), None);
+}
+
+#[test]
+fn test_attribute_shorthands() {
+    let doc = parse_example("AndroidManifest.xml");
+    let manifest = root(&doc);
+
+    assert_eq!(manifest.get_attribute_or("android:versionName", "0"), "1.0");
+    assert_eq!(manifest.get_attribute_or("android:missing", "0"), "0");
+
+    assert_eq!(
+        manifest.require_attribute("package"),
+        Ok("org.t0t0.androguard.TC")
+    );
+    assert_eq!(
+        manifest.require_attribute("android:missing"),
+        Err(ParseError::AttributeNotFound("android:missing".to_string()))
+    );
+}
+
+#[test]
+fn test_iter_elements() {
+    let doc = parse_example("AndroidManifest.xml");
+
+    let tags: Vec<&str> = doc.iter_elements().map(Element::get_tag).collect();
+    assert_eq!(
+        tags,
+        [
+            "manifest",
+            "application",
+            "activity",
+            "intent-filter",
+            "action",
+            "category"
+        ]
+    );
+
+    let doc = parse_example("AndroidManifestWithComment.xml");
+    assert_eq!(doc.iter_elements().count(), 77);
+}
+
+#[test]
+fn test_iter_elements_skips_cdata() {
+    fn count_elements(e: &Element) -> usize {
+        1 + e
+            .get_children()
+            .iter()
+            .filter_map(|n| n.as_element())
+            .map(count_elements)
+            .sum::<usize>()
+    }
+
+    let doc = parse_example("AndroidManifestTextChunksXML.xml");
+    assert_eq!(doc.iter_elements().count(), count_elements(root(&doc)));
+}
+
+#[test]
+fn test_document_counts() {
+    let doc = parse_example("AndroidManifest.xml");
+    assert_eq!(doc.get_element_count(), 6);
+    assert_eq!(doc.get_depth(), 5);
+    assert_eq!(doc.get_cdata_count(), 0);
+    assert_eq!(doc.get_attribute_count(), 10);
+
+    let doc = parse_example("AndroidManifestMixedContent.xml");
+    assert_eq!(doc.get_element_count(), 3);
+    assert_eq!(doc.get_depth(), 3);
+    assert_eq!(doc.get_cdata_count(), 3);
+    assert_eq!(doc.get_attribute_count(), 1);
+}
+
+#[test]
+fn test_iter_descendants() {
+    let doc = parse_example("AndroidManifest.xml");
+    let activity = doc.find_first_element_by_tag("activity").unwrap();
+
+    let tags: Vec<&str> = activity.iter_descendants().map(Element::get_tag).collect();
+    assert_eq!(tags, ["intent-filter", "action", "category"]);
+
+    let action = doc.find_first_element_by_tag("action").unwrap();
+    assert_eq!(action.iter_descendants().count(), 0);
+}
+
+#[test]
+fn test_find_elements_by_tag() {
+    let doc = parse_example("AndroidManifestWithComment.xml");
+
+    let activities = doc.find_elements_by_tag("activity");
+    let names: Vec<&str> = activities
+        .iter()
+        .filter_map(|e| e.get_attribute("android:name"))
+        .collect();
+    assert_eq!(
+        names[..3],
+        [
+            "com.phone2.stop.activity.MainActivity",
+            "com.phone2.stop.activity.DeleteActivity",
+            "com.phone2.stop.activity.DefaultSmsActivity"
+        ]
+    );
+
+    // Exact match only
+    assert!(doc.find_elements_by_tag("Activity").is_empty());
+    assert!(doc.find_elements_by_tag("activit").is_empty());
+
+    let first = doc.find_first_element_by_tag("activity").unwrap();
+    assert_eq!(first, activities[0]);
+    assert!(doc.find_first_element_by_tag("provider-missing").is_none());
+}
+
+#[test]
+fn test_typed_attribute_accessors() {
+    let doc = parse_example("AndroidManifestExtraNamespace.xml");
+    let manifest = root(&doc);
+
+    // Decimal
+    assert_eq!(
+        manifest.get_attribute_as_int("android:versionCode"),
+        Some(507_000)
+    );
+    assert_eq!(
+        manifest.get_attribute_as_u32("android:versionCode"),
+        Some(507_000)
+    );
+    assert_eq!(manifest.get_attribute_as_int("android:versionName"), None);
+    assert_eq!(manifest.get_attribute_as_int("android:missing"), None);
+
+    // Hexadecimal
+    let feature = manifest
+        .find_children_by_tag("uses-feature")
+        .into_iter()
+        .find(|e| e.get_attribute("android:glEsVersion").is_some())
+        .unwrap();
+    assert_eq!(
+        feature.get_attribute_as_u32("android:glEsVersion"),
+        Some(0x0002_0000)
+    );
+    assert_eq!(
+        feature.get_attribute_as_int("android:glEsVersion"),
+        Some(0x0002_0000)
+    );
+
+    // Boolean
+    assert_eq!(
+        feature.get_attribute_as_bool("android:required"),
+        Some(true)
+    );
+
+    let doc = parse_example("AndroidManifest.xml");
+    let application = root(&doc).find_child_by_tag("application").unwrap();
+    assert_eq!(
+        application.get_attribute_as_bool("android:debuggable"),
+        Some(true)
+    );
+    assert_eq!(application.get_attribute_as_bool("android:label"), None);
+    assert_eq!(application.get_attribute_as_bool("android:missing"), None);
+}
+
+#[test]
+fn test_iter_children() {
+    let doc = parse_example("AndroidManifestTextChunksXML.xml");
+
+    let mut cdatas = 0;
+    for e in doc.iter_elements() {
+        let elements = e.iter_children_elements().count();
+        let data = e.iter_children_cdatas().count();
+        assert_eq!(elements + data, e.get_children().len());
+        cdatas += data;
+    }
+    assert!(cdatas > 0);
+}
+
+#[test]
+fn test_find_by_path() {
+    let doc = parse_example("AndroidManifestWithComment.xml");
+    let root = root(&doc);
+
+    let sdk = doc.find_by_path(&["uses-sdk"]).unwrap();
+    assert_eq!(sdk.get_attribute("android:minSdkVersion"), Some("8"));
+
+    // The intent filter of the first activity has no data element, so the
+    // search continues with the second activity.
+    let path = ["application", "activity", "intent-filter", "data"];
+    let data = doc.find_by_path(&path).unwrap();
+    assert_eq!(data.get_attribute("android:scheme"), Some("package"));
+    assert_eq!(root.find_descendant_by_tag_path(&path), Some(data));
+
+    let application = root.find_child_by_tag("application").unwrap();
+    assert_eq!(
+        application.find_descendant_by_tag_path(&path[1..]),
+        Some(data)
+    );
+    assert_eq!(root.find_descendant_by_tag_path(&[]), Some(root));
+
+    assert!(doc.find_by_path(&["activity"]).is_none());
+    assert!(doc.find_by_path(&["application", "missing"]).is_none());
+    assert!(doc.find_by_path(&["manifest", "application"]).is_none());
+}
+
+#[test]
+fn test_attribute_resource_ids() {
+    let doc = parse_example("AndroidManifest.xml");
+    let manifest = root(&doc);
+    assert_eq!(
+        manifest.get_attribute_resource_id("android:versionCode"),
+        Some(0x0101_021b)
+    );
+    assert_eq!(
+        manifest.get_attribute_resource_id("android:versionName"),
+        Some(0x0101_021c)
+    );
+    assert_eq!(manifest.get_attribute_resource_id("package"), None);
+    assert_eq!(manifest.get_attribute_resource_id("xmlns:android"), None);
+
+    let activity = doc.find_first_element_by_tag("activity").unwrap();
+    assert_eq!(
+        activity.get_attribute_resource_id("android:name"),
+        Some(0x0101_0003)
+    );
+
+    let mut activity = activity.clone();
+    activity.remove_attribute("android:name");
+    assert_eq!(activity.get_attribute_resource_id("android:name"), None);
+
+    // Android attributes that are added are given the framework ID, which
+    // is written to the resource map when encoded.
+    activity.set_attribute("android:label", "Example");
+    activity.set_attribute("tools:label", "Example");
+    assert_eq!(
+        activity.get_attribute_resource_id("android:label"),
+        Some(0x0101_0001)
+    );
+    assert_eq!(activity.get_attribute_resource_id("tools:label"), None);
+
+    let mut element = Element::new("activity");
+    element.set_attribute("android:label", "Example");
+    let decoded = parse(&encode(&XmlDocument::new(element)).unwrap()).unwrap();
+    assert_eq!(
+        root(&decoded).get_attribute_resource_id("android:label"),
+        Some(0x0101_0001)
+    );
+}
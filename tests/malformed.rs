@@ -0,0 +1,290 @@
+#![cfg(feature = "std")]
+
+use axmldecoder::{
+    parse, parse_file, parse_reader, parse_with_options, AttributeValue, DocumentBuilder,
+    ParseError, ParseOptions, XmlDocument,
+};
+
+// Every fixture used to panic while reading the string pool.
+#[test]
+fn test_malformed_string_pools() {
+    let dir = format!("{}/tests/fixtures/malformed", env!("CARGO_MANIFEST_DIR"));
+    let mut count = 0;
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        assert!(parse_file(&path).is_err(), "{}", path.display());
+
+        let data = std::fs::read(&path).unwrap();
+        assert!(
+            parse_with_options(&data, &lenient()).is_err(),
+            "{}",
+            path.display()
+        );
+        count += 1;
+    }
+    assert_eq!(count, 6);
+}
+
+#[test]
+fn test_invalid_chunk_sizes() {
+    let check = |name: &str, typ: u16, header_size: u16, declared: u32, available: usize| {
+        let path = format!(
+            "{}/tests/fixtures/chunk_size/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        let data = std::fs::read(path).unwrap();
+        assert_eq!(
+            parse(&data),
+            Err(ParseError::InvalidChunkSize {
+                typ,
+                header_size,
+                declared,
+                available
+            }),
+            "{}",
+            name
+        );
+    };
+
+    check("DocumentSizeTooLarge.xml", 0x0003, 8, 376, 120);
+    check("NodeSizeTooLarge.xml", 0x0102, 16, 0x1000, 60);
+    check("NodeSizeTooSmall.xml", 0x0103, 16, 4, 24);
+    check("HeaderSizeTooLarge.xml", 0x0102, 44, 36, 60);
+}
+
+fn lenient() -> ParseOptions {
+    ParseOptions {
+        lenient: true,
+        ..ParseOptions::default()
+    }
+}
+
+#[test]
+fn test_plain_text_xml() {
+    let inputs: [&[u8]; 3] = [
+        b"<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<manifest/>",
+        b"<manifest package=\"com.example\"/>",
+        b"\xef\xbb\xbf\n  <manifest package=\"com.example\"/>",
+    ];
+    for input in inputs {
+        assert_eq!(parse(input), Err(ParseError::PlainTextXml), "{:?}", input);
+        assert_eq!(
+            parse_with_options(input, &lenient()),
+            Err(ParseError::PlainTextXml)
+        );
+        assert_eq!(parse_reader(input).unwrap_err(), ParseError::PlainTextXml);
+    }
+}
+
+#[test]
+fn test_looks_like_zip() {
+    let apk = b"PK\x03\x04\x14\0\0\0\x08\0\0\0!\0";
+    assert_eq!(parse(apk), Err(ParseError::LooksLikeZip));
+    assert_eq!(
+        parse_with_options(apk, &lenient()),
+        Err(ParseError::LooksLikeZip)
+    );
+    assert_eq!(
+        parse_reader(&apk[..]).unwrap_err(),
+        ParseError::LooksLikeZip
+    );
+}
+
+#[test]
+fn test_not_binary_xml() {
+    let inputs: [(&[u8], u16); 4] = [
+        (
+            b"dex\n035\0\x12\x34\x56\x78\x9a\xbc\xde\xf0\x70\0\0\0",
+            0x6564,
+        ),
+        (
+            b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x10\0\0\0\x10\x08\x06\0\0\0",
+            0x5089,
+        ),
+        // The header of resources.arsc
+        (
+            &[
+                0x02, 0x00, 0x0c, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            ],
+            0x0002,
+        ),
+        (
+            &[0x5e, 0x1f, 0xd3, 0x07, 0x9a, 0x44, 0x21, 0xc8, 0x0b, 0x6e],
+            0x1f5e,
+        ),
+    ];
+    for (input, found) in inputs {
+        assert_eq!(
+            parse(input),
+            Err(ParseError::NotBinaryXml { found }),
+            "{:?}",
+            input
+        );
+        assert_eq!(
+            parse_reader(input).unwrap_err(),
+            ParseError::NotBinaryXml { found }
+        );
+        // Lenient parsing does not check the type, but still fails
+        assert!(parse_with_options(input, &lenient()).is_err());
+    }
+
+    assert!(parse(&[]).is_err());
+}
+
+#[test]
+fn test_invalid_magic_or_header() {
+    // The XML chunk type, but sizes that make no sense
+    let header = [0x03, 0x00, 0x04, 0x00, 0x00, 0x01, 0x00, 0x00];
+    assert_eq!(parse(&header), Err(ParseError::InvalidMagicOrHeader));
+    assert_eq!(
+        parse_reader(&header[..]).unwrap_err(),
+        ParseError::InvalidMagicOrHeader
+    );
+    let header = [0x03, 0x00, 0x08, 0x00, 0x04, 0x00, 0x00, 0x00];
+    assert_eq!(parse(&header), Err(ParseError::InvalidMagicOrHeader));
+}
+
+fn parse_lenient(name: &str) -> (Result<XmlDocument, ParseError>, XmlDocument) {
+    let path = format!(
+        "{}/tests/fixtures/lenient/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    let data = std::fs::read(path).unwrap();
+    (parse(&data), parse_with_options(&data, &lenient()).unwrap())
+}
+
+#[test]
+fn test_lenient_truncated_element() {
+    let (strict, xml) = parse_lenient("TruncatedElement.xml");
+    assert!(matches!(
+        strict,
+        Err(ParseError::InvalidChunkSize { typ: 0x0003, .. })
+    ));
+
+    let action = xml.find_first_element_by_tag("action").unwrap();
+    assert_eq!(
+        action.get_attribute("android:name"),
+        Some("android.intent.action.MAIN")
+    );
+    assert!(xml.find_first_element_by_tag("category").is_none());
+    assert_eq!(
+        xml.get_warnings(),
+        [
+            "Document is truncated: Invalid size 1340 for chunk of type 0x0003 with header size 8 and 1170 bytes available",
+            "Stopped reading at offset 1140: Invalid size 56 for chunk of type 0x0102 with header size 16 and 30 bytes available",
+            "Document ended with 4 unclosed element(s)",
+        ]
+    );
+}
+
+#[test]
+fn test_lenient_corrupt_attribute_count() {
+    let (strict, xml) = parse_lenient("CorruptAttributeCount.xml");
+    assert!(strict.is_err());
+
+    // The children of the skipped <activity> are kept in its parent
+    assert!(xml.find_first_element_by_tag("activity").is_none());
+    let application = xml.find_first_element_by_tag("application").unwrap();
+    let filter = application.find_child_by_tag("intent-filter").unwrap();
+    assert!(filter.find_child_by_tag("category").is_some());
+    assert_eq!(xml.get_warnings().len(), 1);
+    assert!(
+        xml.get_warnings()[0].starts_with("Skipped invalid chunk of type 0x0102 at offset 948: ")
+    );
+}
+
+#[test]
+fn test_chunk_decode_context() {
+    // The attribute count of <activity>, the fourth node, runs past the end
+    // of its chunk.
+    let (strict, _) = parse_lenient("CorruptAttributeCount.xml");
+    let error = strict.unwrap_err();
+    assert!(
+        matches!(
+            error,
+            ParseError::ChunkDecode {
+                offset: 948,
+                typ: 0x0102,
+                size: 76,
+                index: 3,
+                ..
+            }
+        ),
+        "{:?}",
+        error
+    );
+    assert!(error
+        .to_string()
+        .starts_with("Invalid chunk of type 0x0102 and size 76 at offset 948 (node 3): "));
+}
+
+// Flipping any single byte of a valid document must never panic.
+#[test]
+fn test_flipped_bytes() {
+    let data = DocumentBuilder::new()
+        .start_namespace("android", "http://schemas.android.com/apk/res/android")
+        .start_element(
+            "manifest",
+            &[
+                ("package", AttributeValue::from("com.example")),
+                ("android:versionCode", AttributeValue::Int(1)),
+            ],
+        )
+        .start_element(
+            "application",
+            &[("android:label", AttributeValue::Reference(0x7f01_0000))],
+        )
+        .cdata("text")
+        .build()
+        .unwrap();
+    assert!(parse(&data).is_ok());
+
+    let lenient = lenient();
+    for i in 0..data.len() {
+        let mut data = data.clone();
+        data[i] ^= 0xff;
+        let _ = parse(&data);
+        let _ = parse_with_options(&data, &lenient);
+    }
+}
+
+// -1 string indexes used to make these fail, or panic.
+#[test]
+fn test_null_string_indexes() {
+    let parse_fixture = |name: &str| {
+        let path = format!(
+            "{}/tests/fixtures/null_strings/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        parse_file(path)
+    };
+
+    let doc = parse_fixture("NullCdata.xml").unwrap();
+    let string = doc.find_first_element_by_tag("string").unwrap();
+    let cdata = string.iter_children_cdatas().next().unwrap();
+    assert_eq!(cdata.get_data(), "");
+
+    let doc = parse_fixture("NullNamespacePrefix.xml").unwrap();
+    assert_eq!(
+        doc.get_namespaces().get(""),
+        Some(&"http://schemas.example.com/app".to_string())
+    );
+    let layout = doc.root().as_element().unwrap();
+    assert_eq!(layout.get_tag(), "layout");
+    assert_eq!(layout.get_attribute("value"), Some("x"));
+
+    let doc = parse_fixture("NullEndNamespacePrefix.xml").unwrap();
+    let manifest = doc.root().as_element().unwrap();
+    assert_eq!(
+        manifest.get_attribute_as_int("android:versionCode"),
+        Some(1)
+    );
+
+    assert_eq!(
+        parse_fixture("NullElementName.xml"),
+        Err(ParseError::StringNotFound(u32::MAX))
+    );
+}
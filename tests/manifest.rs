@@ -0,0 +1,216 @@
+#![cfg(all(feature = "std", feature = "manifest"))]
+
+use axmldecoder::{
+    encode, parse_file, validate_manifest, AttributeValue, DocumentBuilder, Element, Manifest,
+    ManifestWarning, ParseError, XmlDocument,
+};
+
+fn manifest(name: &str) -> Manifest {
+    let path = format!("{}/examples/{}", env!("CARGO_MANIFEST_DIR"), name);
+    Manifest::from_document(&parse_file(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_manifest() {
+    let manifest = manifest("AndroidManifestWithComment.xml");
+
+    assert_eq!(manifest.get_package(), Some("com.zxfxxx660.sucruri"));
+    assert_eq!(manifest.get_version_code(), Some(98));
+    assert_eq!(manifest.get_version_name(), Some("5.5.464"));
+    assert_eq!(manifest.get_min_sdk(), Some(8));
+    assert_eq!(manifest.get_target_sdk(), Some(19));
+    assert_eq!(manifest.get_permissions().len(), 17);
+    assert_eq!(
+        manifest.get_permissions().first().map(String::as_str),
+        Some("android.permission.RECEIVE_WAP_PUSH")
+    );
+
+    let main = manifest.get_activities().first().unwrap();
+    assert_eq!(
+        main.get_name(),
+        Some("com.phone2.stop.activity.MainActivity")
+    );
+    assert_eq!(main.get_exported(), None);
+    let filter = main.get_intent_filters().first().unwrap();
+    assert_eq!(filter.get_actions(), ["android.intent.action.MAIN"]);
+    assert_eq!(
+        filter.get_categories(),
+        ["android.intent.category.LAUNCHER"]
+    );
+
+    let boot = manifest
+        .get_services()
+        .iter()
+        .find(|s| s.get_name() == Some("com.phone.stop6.service.BootService"))
+        .unwrap();
+    assert_eq!(boot.get_exported(), Some(true));
+    assert!(boot.get_intent_filters().is_empty());
+
+    assert!(!manifest.get_receivers().is_empty());
+}
+
+#[test]
+fn test_manifest_missing_attributes() {
+    let manifest = manifest("AndroidManifestNoResourceMap.xml");
+
+    assert_eq!(manifest.get_package(), Some("com.example"));
+    assert_eq!(manifest.get_version_code(), None);
+    assert_eq!(manifest.get_version_name(), None);
+    assert_eq!(manifest.get_min_sdk(), None);
+    assert!(manifest.get_permissions().is_empty());
+    assert!(manifest.get_services().is_empty());
+
+    let activity = manifest.get_activities().first().unwrap();
+    assert_eq!(activity.get_name(), Some(".MainActivity"));
+    assert_eq!(activity.get_exported(), None);
+}
+
+#[test]
+fn test_manifest_version_code_major() {
+    let version_code = |attributes: &[(&str, AttributeValue)]| {
+        let mut builder = DocumentBuilder::new();
+        builder.start_element("manifest", attributes).end_element();
+        Manifest::from_document(&builder.build_document().unwrap())
+            .unwrap()
+            .get_version_code()
+    };
+
+    // The version code is signed, but only the major version carries a sign.
+    assert_eq!(
+        version_code(&[("android:versionCode", AttributeValue::Int(-1))]),
+        Some(0xffff_ffff)
+    );
+    assert_eq!(
+        version_code(&[
+            ("android:versionCode", AttributeValue::Int(5)),
+            ("android:versionCodeMajor", AttributeValue::Int(2)),
+        ]),
+        Some(0x2_0000_0005)
+    );
+    assert_eq!(
+        version_code(&[("android:versionCodeMajor", AttributeValue::Int(1))]),
+        Some(1 << 32)
+    );
+}
+
+#[test]
+fn test_manifest_missing_package() {
+    let mut builder = DocumentBuilder::new();
+    builder.start_element("manifest", &[]).end_element();
+    let manifest = Manifest::from_document(&builder.build_document().unwrap()).unwrap();
+    assert_eq!(manifest.get_package(), None);
+
+    let mut builder = DocumentBuilder::new();
+    builder
+        .start_element("manifest", &[("package", AttributeValue::from(""))])
+        .end_element();
+    let manifest = Manifest::from_document(&builder.build_document().unwrap()).unwrap();
+    assert_eq!(manifest.get_package(), Some(""));
+}
+
+#[test]
+fn test_manifest_from_bytes() {
+    let path = format!(
+        "{}/examples/AndroidManifest.xml",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let manifest = Manifest::from_bytes(&std::fs::read(path).unwrap()).unwrap();
+    assert_eq!(manifest.get_package(), Some("org.t0t0.androguard.TC"));
+    assert_eq!(manifest.get_version_name(), Some("1.0"));
+
+    let layout = encode(&XmlDocument::new(Element::new("LinearLayout"))).unwrap();
+    assert_eq!(
+        Manifest::from_bytes(&layout),
+        Err(ParseError::NotManifest("LinearLayout".to_string()))
+    );
+    assert_eq!(
+        Manifest::from_bytes(&[0; 4]),
+        Err(ParseError::NotBinaryXml { found: 0 })
+    );
+}
+
+fn bad_manifest(target_sdk: i32) -> XmlDocument {
+    let mut builder = DocumentBuilder::new();
+    builder
+        .start_element(
+            "manifest",
+            &[("package", AttributeValue::from("com.example"))],
+        )
+        .start_element(
+            "uses-sdk",
+            &[("android:targetSdkVersion", AttributeValue::Int(target_sdk))],
+        )
+        .end_element()
+        .start_element(
+            "uses-permission",
+            &[(
+                "android:name",
+                AttributeValue::from("android.permission.INTERNET"),
+            )],
+        )
+        .end_element()
+        .start_element("uses-permission", &[])
+        .end_element()
+        .start_element(
+            "application",
+            &[("android:debuggable", AttributeValue::Boolean(true))],
+        );
+    for (name, exported) in [(".Main", None), (".Exported", Some(false))] {
+        let mut attributes = vec![("android:name", AttributeValue::from(name))];
+        if let Some(exported) = exported {
+            attributes.push(("android:exported", AttributeValue::Boolean(exported)));
+        }
+        builder
+            .start_element("activity", &attributes)
+            .start_element("intent-filter", &[])
+            .end_element()
+            .end_element();
+    }
+    builder
+        .start_element("receiver", &[])
+        .start_element("intent-filter", &[])
+        .end_element()
+        .end_element()
+        .start_element(
+            "service",
+            &[("android:name", AttributeValue::from(".NoFilter"))],
+        );
+    builder.build_document().unwrap()
+}
+
+#[test]
+fn test_validate_manifest() {
+    let warnings = validate_manifest(&bad_manifest(33));
+    assert_eq!(
+        warnings,
+        [
+            ManifestWarning::MissingPermissionName { line: 0 },
+            ManifestWarning::Debuggable { line: 0 },
+            ManifestWarning::MissingExported {
+                tag: "activity".to_string(),
+                name: Some(".Main".to_string()),
+                line: 0
+            },
+            ManifestWarning::MissingExported {
+                tag: "receiver".to_string(),
+                name: None,
+                line: 0
+            },
+        ]
+    );
+    assert_eq!(
+        warnings[2].to_string(),
+        "<activity android:name=\".Main\"> has an intent filter but no android:exported on line 0"
+    );
+
+    // android:exported is only required from API 31
+    let warnings = validate_manifest(&bad_manifest(30));
+    assert_eq!(warnings.len(), 2);
+
+    let layout = parse_file(format!(
+        "{}/examples/LayoutScopedNamespaces.xml",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap();
+    assert!(validate_manifest(&layout).is_empty());
+}
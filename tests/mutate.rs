@@ -0,0 +1,92 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use axmldecoder::{encode, parse, Cdata, Element, Node, XmlDocument};
+use common::parse_example;
+
+#[test]
+fn test_modify_parsed_document() {
+    let mut doc = parse_example("AndroidManifest.xml");
+    let manifest = doc.root_mut().as_element_mut().unwrap();
+
+    let application = manifest.find_child_by_tag_mut("application").unwrap();
+    application.set_attribute("android:debuggable", false);
+    assert_eq!(
+        application.remove_attribute("android:icon").as_deref(),
+        Some("@ref/0x7f020000")
+    );
+    assert_eq!(application.remove_attribute("android:icon"), None);
+    let activity = application.remove_child(0).unwrap();
+    assert!(application.remove_child(0).is_none());
+
+    let mut permission = Element::new("uses-permission");
+    permission.set_attribute("android:name", "android.permission.INTERNET");
+    manifest.add_child(Node::Element(permission));
+
+    let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" android:versionCode="1" android:versionName="1.0" package="org.t0t0.androguard.TC">
+  <application android:label="@ref/0x7f040000" android:debuggable="false"/>
+  <uses-permission android:name="android.permission.INTERNET"/>
+</manifest>
+"#;
+    assert_eq!(doc.to_xml_string(), expected);
+    assert_eq!(
+        activity.as_element().unwrap().get_attribute("android:name"),
+        Some("TCActivity")
+    );
+}
+
+#[test]
+fn test_build_document() {
+    let mut application = Element::new("application");
+    application.set_attribute("android:debuggable", true);
+    application.set_attribute("android:label", "Example");
+    application.add_child(Node::Cdata(Cdata::new("text")));
+
+    let mut manifest = Element::new("manifest");
+    manifest.set_attribute("package", "com.example");
+    manifest.set_attribute("android:versionCode", 3);
+    manifest.add_child(Node::Element(application));
+
+    let doc = XmlDocument::new(manifest);
+    let decoded = parse(&encode(&doc).unwrap()).unwrap();
+    assert_eq!(decoded.to_xml_string(), doc.to_xml_string());
+
+    let manifest = decoded.root().as_element().unwrap();
+    assert_eq!(
+        manifest.get_attribute_as_u32("android:versionCode"),
+        Some(3)
+    );
+    let application = manifest.find_child_by_tag("application").unwrap();
+    assert_eq!(
+        application.get_attribute_as_bool("android:debuggable"),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_replace_root() {
+    let mut doc = parse_example("AndroidManifest.xml");
+    let mut manifest = doc.root().as_element().unwrap().clone();
+    manifest.set_tag("decoy");
+    let old = doc.set_root(Node::Element(manifest));
+    assert_eq!(old.as_element().unwrap().get_tag(), "manifest");
+
+    let decoded = parse(&doc.to_binary().unwrap()).unwrap();
+    let root = decoded.root().as_element().unwrap();
+    assert_eq!(root.get_tag(), "decoy");
+    assert!(root.find_child_by_tag("application").is_some());
+}
+
+#[test]
+#[should_panic(expected = "element tag must not be empty")]
+fn test_empty_tag() {
+    let _ = Element::new("");
+}
+
+#[test]
+fn test_new_nodes_have_no_line_number() {
+    assert_eq!(Element::new("manifest").get_line_number(), 0);
+    assert_eq!(Cdata::new("text").get_line_number(), 0);
+}
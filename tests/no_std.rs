@@ -0,0 +1,22 @@
+// Only uses the parts of the API that are available without the `std`
+// feature. CI runs it with `--no-default-features`.
+
+use axmldecoder::{encode, parse, Node};
+
+#[test]
+fn test_parse_without_std() {
+    let data = include_bytes!("../examples/AndroidManifest.xml");
+    let doc = parse(data).unwrap();
+
+    let Node::Element(root) = doc.root() else {
+        panic!("missing root element");
+    };
+    assert_eq!(root.get_tag(), "manifest");
+    assert_eq!(
+        root.get_attribute("package"),
+        Some("org.t0t0.androguard.TC")
+    );
+
+    let decoded = parse(&encode(&doc).unwrap()).unwrap();
+    assert_eq!(decoded.root(), doc.root());
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use axmldecoder::parse_file;
+use common::parse_example;
+
+fn snapshot(name: &str) -> String {
+    let path = format!("{}/tests/snapshots/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let snapshot = std::fs::read_to_string(path).unwrap();
+
+    // The snapshots show framework references by ID
+    #[cfg(feature = "android-res-names")]
+    let snapshot = snapshot
+        .replace("@ref/0x0103000f", "@android:style/Theme.Translucent")
+        .replace(
+            "@ref/0x01030010",
+            "@android:style/Theme.Translucent.NoTitleBar",
+        );
+    snapshot
+}
+
+#[test]
+fn test_to_xml_string() {
+    for name in [
+        "AndroidManifest.xml",
+        "AndroidManifestTextChunksXML.xml",
+        "AndroidManifestEscaping.xml",
+    ] {
+        let doc = parse_example(name);
+        assert_eq!(doc.to_xml_string(), snapshot(name), "{}", name);
+        assert_eq!(doc.to_string(), doc.to_xml_string());
+    }
+}
+
+#[test]
+fn test_escaping_keeps_parsed_values() {
+    // Escaping is only applied when writing, the parsed values are unchanged.
+    let doc = parse_example("AndroidManifestEscaping.xml");
+    let activity = doc.find_first_element_by_tag("activity").unwrap();
+
+    assert_eq!(
+        activity.get_attribute("android:name"),
+        Some("Tom & Jerry <\"quoted\">")
+    );
+    assert_eq!(
+        activity.get_attribute("android:value"),
+        Some("tab\there\r\nbell\u{7}gone")
+    );
+}
+
+#[test]
+fn test_to_compact_xml() {
+    let doc = parse_example("AndroidManifest.xml");
+
+    let expected: String = snapshot("AndroidManifest.xml")
+        .lines()
+        .map(str::trim_start)
+        .collect();
+    assert_eq!(doc.to_compact_xml(), expected);
+}
+
+#[test]
+fn test_mixed_content_order() {
+    // Text and elements are kept in the order their chunks appear in.
+    let doc = parse_example("AndroidManifestMixedContent.xml");
+
+    let expected = concat!(
+        r#"<?xml version="1.0" encoding="utf-8"?>"#,
+        r#"<resources xmlns:android="http://schemas.android.com/apk/res/android">"#,
+        r#"<string name="greeting"><![CDATA[Hello ]]><b><![CDATA[world]]></b><![CDATA[, bye]]></string>"#,
+        "</resources>"
+    );
+    assert_eq!(doc.to_compact_xml(), expected);
+}
+
+#[test]
+fn test_write_xml() {
+    let doc = parse_example("AndroidManifest.xml");
+
+    let mut xml = String::new();
+    doc.write_xml(&mut xml, 2).unwrap();
+    assert_eq!(xml, doc.to_xml_string());
+
+    let mut xml = String::new();
+    doc.write_xml(&mut xml, 0).unwrap();
+    let expected: String = snapshot("AndroidManifest.xml")
+        .lines()
+        .map(|line| format!("{}\n", line.trim_start()))
+        .collect();
+    assert_eq!(xml, expected);
+}
+
+#[test]
+fn test_well_formed() {
+    use quick_xml::events::Event;
+
+    let mut examples = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    examples.push("examples");
+
+    for entry in std::fs::read_dir(examples).unwrap() {
+        let path = entry.unwrap().path();
+        let xml = parse_file(&path).unwrap().to_xml_string();
+
+        let mut reader = quick_xml::Reader::from_str(&xml);
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e) | Event::Empty(e)) => {
+                    for attr in e.attributes() {
+                        let attr = attr.unwrap_or_else(|err| {
+                            panic!("{}: invalid attribute: {}", path.display(), err)
+                        });
+                        attr.unescape_value().unwrap_or_else(|err| {
+                            panic!("{}: invalid attribute value: {}", path.display(), err)
+                        });
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(err) => panic!("{}: {}", path.display(), err),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "android-res-names")]
+#[test]
+fn test_android_res_names() {
+    let doc = parse_example("AndroidManifestTextChunksXML.xml");
+    let themes: Vec<&str> = doc
+        .iter_elements_by_tag("activity")
+        .filter_map(|e| e.get_attribute("android:theme"))
+        .filter(|theme| !theme.starts_with("@ref/0x7f"))
+        .collect();
+    assert_eq!(
+        themes,
+        [
+            "@android:style/Theme.Translucent",
+            "@android:style/Theme.Translucent.NoTitleBar"
+        ]
+    );
+
+    // References into the APK are not named
+    let doc = parse_example("AndroidManifest.xml");
+    let application = doc.find_first_element_by_tag("application").unwrap();
+    assert_eq!(
+        application.get_attribute("android:label"),
+        Some("@ref/0x7f040000")
+    );
+}
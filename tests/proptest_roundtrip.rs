@@ -0,0 +1,80 @@
+#![cfg(feature = "std")]
+
+use axmldecoder::{encode, parse, AttributeValue, Cdata, Element, Node, XmlDocument};
+use proptest::prelude::*;
+
+fn attribute_value() -> impl Strategy<Value = AttributeValue> {
+    prop_oneof![
+        "[ -~]{0,12}".prop_map(AttributeValue::String),
+        any::<i32>().prop_map(AttributeValue::Int),
+        any::<u32>().prop_map(AttributeValue::Hex),
+        any::<bool>().prop_map(AttributeValue::Boolean),
+        any::<u32>().prop_map(AttributeValue::Reference),
+    ]
+}
+
+// Tags start with an uppercase letter, like layout classes, so that they
+// never match the special handling of <manifest>.
+fn element(depth: u32) -> impl Strategy<Value = Element> {
+    let leaf = (
+        "[A-Z][a-zA-Z0-9]{0,8}",
+        prop::collection::vec(("[a-z][a-zA-Z0-9]{0,8}", attribute_value()), 0..4),
+    )
+        .prop_map(|(tag, attributes)| {
+            let mut e = Element::new(tag);
+            for (name, value) in attributes {
+                e.set_attribute(name, value);
+            }
+            e
+        });
+
+    leaf.prop_recursive(depth, 32, 4, |inner| {
+        (
+            inner.clone(),
+            prop::collection::vec(
+                prop_oneof![
+                    inner.prop_map(Node::Element),
+                    "[a-zA-Z0-9 ]{1,12}".prop_map(|s| Node::Cdata(Cdata::new(s))),
+                ],
+                0..4,
+            ),
+        )
+            .prop_map(|(mut e, children)| {
+                for child in children {
+                    e.add_child(child);
+                }
+                e
+            })
+    })
+}
+
+proptest! {
+    #[test]
+    fn test_encode_roundtrip(root in element(4)) {
+        let doc = XmlDocument::new(root);
+        let decoded = parse(&encode(&doc).unwrap()).unwrap();
+        prop_assert_eq!(decoded.to_xml_string(), doc.to_xml_string());
+        prop_assert_eq!(decoded.root(), doc.root());
+    }
+
+    #[test]
+    fn test_parse_arbitrary_bytes(data in prop::collection::vec(any::<u8>(), 0..512)) {
+        let _ = parse(&data);
+    }
+
+    // Random bytes rarely get past the document header, so also corrupt a
+    // valid document.
+    #[test]
+    fn test_parse_corrupted_document(
+        changes in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..8),
+        len in any::<prop::sample::Index>(),
+    ) {
+        let mut data = include_bytes!("../examples/AndroidManifest.xml").to_vec();
+        for (i, byte) in changes {
+            let i = i.index(data.len());
+            data[i] = byte;
+        }
+        data.truncate(len.index(data.len() + 1));
+        let _ = parse(&data);
+    }
+}
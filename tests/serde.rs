@@ -0,0 +1,92 @@
+#![cfg(all(feature = "std", feature = "serde"))]
+
+use axmldecoder::parse_file;
+
+#[test]
+fn test_serialize() {
+    let path = format!(
+        "{}/examples/AndroidManifest.xml",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let doc = parse_file(path).unwrap();
+
+    let snapshot = format!(
+        "{}/tests/snapshots/AndroidManifest.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let expected: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(snapshot).unwrap()).unwrap();
+
+    assert_eq!(serde_json::to_value(&doc).unwrap(), expected);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_to_json_string() {
+    let path = format!(
+        "{}/examples/AndroidManifest.xml",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let json = parse_file(path).unwrap().to_json_string();
+
+    let snapshot = format!(
+        "{}/tests/snapshots/AndroidManifest.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let expected: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(snapshot).unwrap()).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+        expected
+    );
+
+    // Attributes are written in document order.
+    let versions = json.find("android:versionCode").unwrap();
+    assert!(versions < json.find("android:versionName").unwrap());
+    assert!(versions < json.find("\"package\"").unwrap());
+}
+
+#[test]
+fn test_serialize_cdata() {
+    let path = format!(
+        "{}/examples/AndroidManifestTextChunksXML.xml",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let doc = parse_file(path).unwrap();
+
+    let element = doc.find_first_element_by_tag("span").unwrap();
+    let node = element.get_children().first().unwrap();
+    let cdata = node.as_cdata().unwrap();
+
+    assert_eq!(
+        serde_json::to_value(node).unwrap(),
+        serde_json::json!({ "cdata": cdata.get_data() })
+    );
+}
+
+#[test]
+fn test_serialize_structure() {
+    fn check_element(value: &serde_json::Value) {
+        assert!(value["tag"].is_string());
+        assert!(value["attributes"].is_object());
+        for child in value["children"].as_array().unwrap() {
+            if child.get("cdata").is_some() {
+                assert!(child["cdata"].is_string());
+            } else {
+                check_element(child);
+            }
+        }
+    }
+
+    let mut examples = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    examples.push("examples");
+
+    for entry in std::fs::read_dir(examples).unwrap() {
+        let doc = parse_file(entry.unwrap().path()).unwrap();
+
+        let json = serde_json::to_string(&doc).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["namespaces"].is_object());
+        check_element(&value["root"]);
+    }
+}
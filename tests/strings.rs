@@ -0,0 +1,77 @@
+#![cfg(feature = "std")]
+
+use std::collections::BTreeSet;
+
+use axmldecoder::{extract_strings, parse_events, AttributeValue, Event, ParseError};
+
+// Returns the strings that the events of a document show, with the prefixes
+// removed from tags and attribute names as they are stored in the pool.
+fn visible_strings(data: &[u8]) -> BTreeSet<String> {
+    let local = |name: &str| name.rsplit(':').next().unwrap().to_string();
+
+    let mut strings = BTreeSet::new();
+    for event in parse_events(data).unwrap() {
+        match event.unwrap() {
+            Event::StartNamespace { prefix, uri } => {
+                strings.insert(prefix);
+                strings.insert(uri);
+            }
+            Event::StartElement {
+                tag,
+                attributes,
+                raw_values,
+                ..
+            } => {
+                strings.insert(local(&tag));
+                for (name, value) in attributes {
+                    strings.insert(local(&name));
+                    if let AttributeValue::String(value) = value {
+                        strings.insert(value);
+                    }
+                }
+                strings.extend(raw_values.into_values());
+            }
+            Event::Cdata { data, .. } => {
+                strings.insert(data);
+            }
+            _ => {}
+        }
+    }
+    strings
+}
+
+#[test]
+fn test_extract_strings() {
+    let path = format!(
+        "{}/examples/AndroidManifest.xml",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let data = std::fs::read(path).unwrap();
+
+    let strings = extract_strings(&data).unwrap();
+    assert_eq!(strings[0], "versionCode");
+
+    // The pool also holds an empty string that no node uses.
+    let mut strings: BTreeSet<String> = strings.into_iter().collect();
+    assert!(strings.remove(""));
+    assert_eq!(strings, visible_strings(&data));
+}
+
+#[test]
+fn test_extract_strings_skips_nodes() {
+    // The chunks after the string pool are never read, so corrupting the
+    // last one does not matter.
+    let path = format!(
+        "{}/examples/AndroidManifest.xml",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let mut data = std::fs::read(path).unwrap();
+    let len = data.len();
+    data[len - 24..].fill(0xff);
+    assert!(extract_strings(&data).is_ok());
+
+    assert_eq!(
+        extract_strings(b"<manifest/>"),
+        Err(ParseError::PlainTextXml)
+    );
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::sync::Arc;
+use std::thread;
+
+use axmldecoder::{
+    AttributeValue, Cdata, DocumentInfo, Element, Event, EventReader, Node, ParseError, XmlDocument,
+};
+
+use common::parse_example;
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+#[test]
+fn test_public_types_are_send_sync() {
+    assert_send_sync::<XmlDocument>();
+    assert_send_sync::<Node>();
+    assert_send_sync::<Element>();
+    assert_send_sync::<Cdata>();
+    assert_send_sync::<AttributeValue>();
+    assert_send_sync::<DocumentInfo>();
+    assert_send_sync::<Event>();
+    assert_send_sync::<EventReader<'static>>();
+    assert_send_sync::<ParseError>();
+}
+
+#[test]
+fn test_send_document() {
+    let doc = parse_example("AndroidManifest.xml");
+    let tag = thread::spawn(move || doc.root().as_element().unwrap().get_tag().to_string())
+        .join()
+        .unwrap();
+    assert_eq!(tag, "manifest");
+}
+
+#[test]
+fn test_send_element() {
+    let doc = parse_example("AndroidManifest.xml");
+    let application = doc
+        .find_first_element_by_tag("application")
+        .unwrap()
+        .clone();
+    let activities = thread::spawn(move || application.find_children_by_tag("activity").len())
+        .join()
+        .unwrap();
+    assert_eq!(activities, 1);
+}
+
+#[test]
+fn test_share_document() {
+    let doc = Arc::new(parse_example("AndroidManifest.xml"));
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let doc = Arc::clone(&doc);
+            thread::spawn(move || doc.find_elements_by_tag("activity").len())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 1);
+    }
+}
+
+#[test]
+fn test_send_event_reader() {
+    let data = include_bytes!("../examples/AndroidManifest.xml");
+    let reader = EventReader::new(data).unwrap();
+    let count = thread::spawn(move || reader.count()).join().unwrap();
+    assert!(count > 0);
+}